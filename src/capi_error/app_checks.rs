@@ -0,0 +1,104 @@
+//! Pre-flight of the app's checks: evaluated with the current state before submitting the txs,
+//! to return the business failure without a round trip to the node.
+//! The node's rejections are decoded from the failing program counter instead (see [super::decode_rejection]).
+
+use super::CapiError;
+use crate::{
+    dao_algod::DaoAlgod,
+    state::{
+        account_state::asset_holdings, dao_app_state::dao_global_state,
+        dao_shares::dao_shares_with_dao_state,
+    },
+};
+use algonaut::transaction::{
+    transaction::ApplicationCallTransaction, SignedTransaction, TransactionType,
+};
+use anyhow::Result;
+use mbase::models::{dao_app_id::DaoAppId, timestamp::Timestamp};
+use std::convert::TryInto;
+
+/// The check of the DAO app that the txs would fail, `None` if it's not a known one (or the txs don't call the app).
+pub(crate) async fn failed_app_check(
+    algod: &dyn DaoAlgod,
+    txs: &[SignedTransaction],
+) -> Result<Option<CapiError>> {
+    let (sender, call) = match txs.iter().find_map(|tx| match &tx.transaction.txn_type {
+        TransactionType::ApplicationCallTransaction(call) => Some((tx.transaction.sender(), call)),
+        _ => None,
+    }) {
+        Some(app_call) => app_call,
+        None => return Ok(None),
+    };
+    let app_id = DaoAppId(call.app_id.unwrap_or_default());
+
+    let state = dao_global_state(algod, app_id).await?;
+    let raise_ended = Timestamp::now().0 >= state.min_funds_target_end_date.0;
+    let target_reached = state.raised.val() >= state.min_funds_target.val();
+
+    let error = match method(call).as_deref() {
+        Some("invest") => {
+            let shares =
+                dao_shares_with_dao_state(algod, app_id, state.shares_asset_id, &state).await?;
+            match u64_arg(call, 1) {
+                Some(amount) if amount > shares.available.val() => {
+                    Some(CapiError::NotEnoughSharesForSale)
+                }
+                _ => None,
+            }
+        }
+        Some("unlock") if !raise_ended => Some(CapiError::RaiseNotEnded),
+        Some("reclaim") => {
+            let owned = asset_holdings(algod, &sender, state.shares_asset_id).await?;
+            if !raise_ended {
+                Some(CapiError::RaiseNotEnded)
+            } else if target_reached {
+                Some(CapiError::FundsTargetReached)
+            } else if reclaimed_shares(txs, state.shares_asset_id) > owned.0 {
+                Some(CapiError::NotEnoughShares)
+            } else {
+                None
+            }
+        }
+        Some("withdraw") => {
+            if sender != state.owner {
+                Some(CapiError::NotOwner)
+            } else if !raise_ended {
+                Some(CapiError::RaiseNotEnded)
+            } else if !target_reached {
+                Some(CapiError::FundsTargetNotReached)
+            } else {
+                match u64_arg(call, 1) {
+                    Some(amount) if amount > state.available.val() => {
+                        Some(CapiError::NotEnoughFunds)
+                    }
+                    _ => None,
+                }
+            }
+        }
+        _ => None,
+    };
+    Ok(error)
+}
+
+fn method(call: &ApplicationCallTransaction) -> Option<String> {
+    let arg = call.app_arguments.as_ref()?.first()?;
+    String::from_utf8(arg.clone()).ok()
+}
+
+/// App argument encoded as big endian u64
+fn u64_arg(call: &ApplicationCallTransaction, index: usize) -> Option<u64> {
+    let arg = call.app_arguments.as_ref()?.get(index)?;
+    Some(u64::from_be_bytes(arg.as_slice().try_into().ok()?))
+}
+
+/// Shares sent to the app with the reclaim call
+fn reclaimed_shares(txs: &[SignedTransaction], shares_asset_id: u64) -> u64 {
+    txs.iter()
+        .filter_map(|tx| match &tx.transaction.txn_type {
+            TransactionType::AssetTransferTransaction(t) if t.xfer == shares_asset_id => {
+                Some(t.amount)
+            }
+            _ => None,
+        })
+        .sum()
+}
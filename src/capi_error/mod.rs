@@ -0,0 +1,295 @@
+use crate::dao_algod::DaoAlgod;
+use algonaut::{model::algod::v2::TransactionResponse, transaction::SignedTransaction};
+use anyhow::{anyhow, Error, Result};
+use mbase::{models::dao_app_id::DaoAppId, teal::TealSource};
+use std::fmt;
+
+mod app_checks;
+pub(crate) mod program;
+mod tests;
+
+/// Known business failures, decoded from the node's rejection messages.
+///
+/// The flows' submissions return them as the error (see [broadcast_app_txs]).
+/// With the rendered approval source, [decode_rejection] maps the failing check to its line, and to the error if the check is tagged:
+/// a `// err: <code>` comment, either on the `assert` (or `err`) line or on the comment line(s) directly above it.
+/// `<code>` is one of the codes returned by [CapiError::code].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CapiError {
+    /// E.g. unlocking shares before the funds raising end date
+    RaiseNotEnded,
+    /// E.g. withdrawing before the min funds target was raised
+    FundsTargetNotReached,
+    /// E.g. reclaiming (getting the investment back) when the target was raised
+    FundsTargetReached,
+    /// Operations restricted to the DAO's owner, e.g. withdrawing or updating the app
+    NotOwner,
+    /// Investing more than the shares still available to investors
+    NotEnoughSharesForSale,
+    /// Reclaiming or unlocking more shares than owned
+    NotEnoughShares,
+    /// Withdrawing more than the DAO's available (drained) funds
+    NotEnoughFunds,
+    NotOptedInToApp {
+        app_id: u64,
+    },
+    NotOptedInToAsset {
+        asset_id: u64,
+    },
+    /// The sender doesn't have enough of the transferred asset (or algos)
+    InsufficientBalance,
+    /// The app rejected the transaction, at a check without (or with an unknown) tag
+    /// `pc` is the program counter of the failing opcode, if reported, `line` the 0-based line in the source, if found.
+    Rejected {
+        pc: Option<u64>,
+        line: Option<usize>,
+    },
+}
+
+impl CapiError {
+    /// The tag used in TEAL to identify the check. `None` for errors not raised by TEAL checks.
+    pub fn code(&self) -> Option<&'static str> {
+        match self {
+            CapiError::RaiseNotEnded => Some("raise_not_ended"),
+            CapiError::FundsTargetNotReached => Some("funds_target_not_reached"),
+            CapiError::FundsTargetReached => Some("funds_target_reached"),
+            CapiError::NotOwner => Some("not_owner"),
+            CapiError::NotEnoughSharesForSale => Some("not_enough_shares_for_sale"),
+            CapiError::NotEnoughShares => Some("not_enough_shares"),
+            CapiError::NotEnoughFunds => Some("not_enough_funds"),
+            CapiError::NotOptedInToApp { .. }
+            | CapiError::NotOptedInToAsset { .. }
+            | CapiError::InsufficientBalance
+            | CapiError::Rejected { .. } => None,
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<CapiError> {
+        [
+            CapiError::RaiseNotEnded,
+            CapiError::FundsTargetNotReached,
+            CapiError::FundsTargetReached,
+            CapiError::NotOwner,
+            CapiError::NotEnoughSharesForSale,
+            CapiError::NotEnoughShares,
+            CapiError::NotEnoughFunds,
+        ]
+        .iter()
+        .find(|e| e.code() == Some(code))
+        .cloned()
+    }
+}
+
+impl fmt::Display for CapiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CapiError::RaiseNotEnded => write!(f, "The funds raising period hasn't ended yet"),
+            CapiError::FundsTargetNotReached => write!(f, "The funds target wasn't reached"),
+            CapiError::FundsTargetReached => write!(f, "The funds target was reached"),
+            CapiError::NotOwner => write!(f, "Only the DAO owner can do this"),
+            CapiError::NotEnoughSharesForSale => write!(f, "Not enough shares for sale"),
+            CapiError::NotEnoughShares => write!(f, "Not enough shares"),
+            CapiError::NotEnoughFunds => write!(f, "The DAO doesn't have enough funds"),
+            CapiError::NotOptedInToApp { app_id } => {
+                write!(f, "Not opted in to the app: {app_id}")
+            }
+            CapiError::NotOptedInToAsset { asset_id } => {
+                write!(f, "Not opted in to the asset: {asset_id}")
+            }
+            CapiError::InsufficientBalance => write!(f, "Insufficient balance"),
+            CapiError::Rejected { pc, line } => {
+                write!(f, "Rejected by the app (pc: {pc:?}, line: {line:?})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CapiError {}
+
+/// Broadcasts txs that call the DAO app. If they fail a known check of the app (evaluated before submitting, with the current state),
+/// or the node rejects them with a known failure, the error is the [CapiError] (with `downcast_ref`), otherwise the node's error.
+/// The node's app rejections are [CapiError::Rejected]: [to_capi_error] maps them to the failing check of the rendered approval.
+pub(crate) async fn broadcast_app_txs(
+    algod: &dyn DaoAlgod,
+    txs: &[SignedTransaction],
+) -> Result<TransactionResponse> {
+    match app_checks::failed_app_check(algod, txs).await {
+        Ok(Some(capi_error)) => return Err(Error::new(capi_error)),
+        Ok(None) => {}
+        Err(e) => log::warn!("Couldn't evaluate the app's checks: {e:#}"),
+    }
+
+    match algod.broadcast_signed_transactions(txs).await {
+        Ok(res) => Ok(res),
+        Err(e) => Err(to_submit_error(e)),
+    }
+}
+
+fn to_submit_error(error: Error) -> Error {
+    let message = format!("{error:#}");
+    match decode_message(&message) {
+        Some(capi_error) => {
+            log::debug!("Txs rejected: {message}");
+            Error::new(capi_error)
+        }
+        None => error,
+    }
+}
+
+/// Decodes the error returned when submitting a transaction to the DAO app.
+/// The approval program is fetched from the app, the rendered `approval_source` has to be its source.
+/// Returns `None` if the error isn't a rejection by the node (e.g. a network error).
+pub async fn to_capi_error(
+    algod: &dyn DaoAlgod,
+    app_id: DaoAppId,
+    approval_source: &TealSource,
+    error: &anyhow::Error,
+) -> Result<Option<CapiError>> {
+    let app = algod.application_information(app_id.0).await?;
+    decode_rejection(
+        &format!("{error:#}"),
+        approval_source,
+        &app.params.approval_program,
+    )
+}
+
+/// Decodes a rejection message of the node.
+/// `approval_program` is the compiled `approval_source`: needed to map the failing program counter to the source.
+pub fn decode_rejection(
+    message: &str,
+    approval_source: &TealSource,
+    approval_program: &[u8],
+) -> Result<Option<CapiError>> {
+    match decode_message(message) {
+        Some(CapiError::Rejected { pc: Some(pc), .. }) => {
+            let line = failed_check_line(approval_source, approval_program, pc)?;
+            Ok(Some(
+                line.and_then(|line| tagged_error(approval_source, line))
+                    .unwrap_or(CapiError::Rejected { pc: Some(pc), line }),
+            ))
+        }
+        decoded => Ok(decoded),
+    }
+}
+
+/// Decodes the rejection message without the source: app rejections are [CapiError::Rejected] without line.
+fn decode_message(message: &str) -> Option<CapiError> {
+    if let Some(app_id) = number_after(message, "has not opted in to application ") {
+        return Some(CapiError::NotOptedInToApp { app_id });
+    }
+    // "asset <id> missing from <address>"
+    if message.contains(" missing from ") {
+        if let Some(asset_id) = number_after(message, "asset ") {
+            return Some(CapiError::NotOptedInToAsset { asset_id });
+        }
+    }
+    if message.contains("underflow on subtracting") || message.contains("overspend") {
+        return Some(CapiError::InsufficientBalance);
+    }
+
+    if message.contains("logic eval error") {
+        return Some(CapiError::Rejected {
+            pc: number_after(message, "pc="),
+            line: None,
+        });
+    }
+    // the program finished with 0 on the stack: there's no failing check
+    if message.contains("rejected by ApprovalProgram") {
+        return Some(CapiError::Rejected {
+            pc: None,
+            line: None,
+        });
+    }
+
+    None
+}
+
+/// Source line of the `assert` / `err` at `pc`.
+/// These are mapped 1:1 by the assembler, so the n-th check in the program is the n-th check in the source.
+fn failed_check_line(source: &TealSource, program: &[u8], pc: u64) -> Result<Option<usize>> {
+    let check_index = match program::check_index_at(program, pc)? {
+        Some(index) => index,
+        // the failing opcode isn't a check (e.g. an overflow)
+        None => return Ok(None),
+    };
+
+    let source = source_str(source)?;
+    let line = source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| is_check(code(line)))
+        .nth(check_index)
+        .map(|(index, _)| index);
+
+    if line.is_none() {
+        return Err(anyhow!(
+            "Check {check_index} not found in the source: the source doesn't correspond to the program"
+        ));
+    }
+    Ok(line)
+}
+
+fn tagged_error(source: &TealSource, line: usize) -> Option<CapiError> {
+    let source = source_str(source).ok()?;
+    let lines: Vec<&str> = source.lines().collect();
+
+    let check_line_tag = lines.get(line).and_then(|l| tag(l));
+    // otherwise look in the comment-only lines directly above
+    let above_tag = || {
+        lines[..line]
+            .iter()
+            .rev()
+            .take_while(|l| code(l).is_empty() && !l.trim().is_empty())
+            .find_map(|l| tag(l))
+    };
+
+    check_line_tag
+        .or_else(above_tag)
+        .and_then(CapiError::from_code)
+}
+
+fn source_str(source: &TealSource) -> Result<&str> {
+    Ok(std::str::from_utf8(&source.0)?)
+}
+
+fn is_check(code: &str) -> bool {
+    matches!(code.split_whitespace().next(), Some("assert") | Some("err"))
+}
+
+/// The line without comment
+fn code(line: &str) -> &str {
+    line.split_at(comment_start(line).unwrap_or(line.len()))
+        .0
+        .trim()
+}
+
+fn tag(line: &str) -> Option<&str> {
+    let comment = &line[comment_start(line)? + 2..];
+    comment.trim().strip_prefix("err:").map(|code| code.trim())
+}
+
+/// Index of the `//` starting the comment, ignoring `//` in string literals
+fn comment_start(line: &str) -> Option<usize> {
+    let mut in_string = false;
+    let mut escaped = false;
+    let bytes = line.as_bytes();
+    for (i, b) in bytes.iter().enumerate() {
+        match b {
+            _ if escaped => escaped = false,
+            b'\\' if in_string => escaped = true,
+            b'"' => in_string = !in_string,
+            b'/' if !in_string && bytes.get(i + 1) == Some(&b'/') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn number_after(str: &str, prefix: &str) -> Option<u64> {
+    let start = str.find(prefix)? + prefix.len();
+    let digits: String = str[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
@@ -0,0 +1,219 @@
+//! Minimal decoding of compiled TEAL: only what's needed to locate the opcodes
+//! (i.e. the size of each opcode's immediate arguments) and read the constants.
+
+use anyhow::{anyhow, Result};
+
+const ERR: u8 = 0x00;
+const ASSERT: u8 = 0x44;
+
+/// If the opcode at `pc` is an `assert` or `err`, how many of these precede it in the program.
+pub fn check_index_at(program: &[u8], pc: u64) -> Result<Option<usize>> {
+    let pc = pc as usize;
+    let (_version, mut current) = read_varuint(program, 0)?;
+    let mut check_index = 0;
+
+    while current < program.len() {
+        let opcode = program[current];
+        let is_check = opcode == ASSERT || opcode == ERR;
+
+        if current == pc {
+            return Ok(if is_check { Some(check_index) } else { None });
+        }
+        if current > pc {
+            return Err(anyhow!("Pc: {pc} isn't at an opcode"));
+        }
+        if is_check {
+            check_index += 1;
+        }
+        current = next_opcode(program, current)?;
+    }
+
+    Err(anyhow!(
+        "Pc: {pc} is out of the program's bounds ({})",
+        program.len()
+    ))
+}
+
+/// The program's constants: the int and byte constant blocks and the pushed ints and bytes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProgramConstants {
+    pub ints: Vec<u64>,
+    pub bytes: Vec<Vec<u8>>,
+}
+
+pub fn constants(program: &[u8]) -> Result<ProgramConstants> {
+    let (_version, mut current) = read_varuint(program, 0)?;
+    let mut constants = ProgramConstants::default();
+
+    while current < program.len() {
+        let immediates_start = current + 1;
+        match program[current] {
+            // intcblock, pushints
+            0x20 | 0x83 => {
+                let (count, mut pos) = read_varuint(program, immediates_start)?;
+                for _ in 0..count {
+                    let (value, next) = read_varuint(program, pos)?;
+                    constants.ints.push(value);
+                    pos = next;
+                }
+            }
+            // pushint
+            0x81 => constants
+                .ints
+                .push(read_varuint(program, immediates_start)?.0),
+            // bytecblock, pushbytess
+            0x26 | 0x82 => {
+                let (count, mut pos) = read_varuint(program, immediates_start)?;
+                for _ in 0..count {
+                    let (value, next) = read_bytes_value(program, pos)?;
+                    constants.bytes.push(value.to_vec());
+                    pos = next;
+                }
+            }
+            // pushbytes
+            0x80 => constants
+                .bytes
+                .push(read_bytes_value(program, immediates_start)?.0.to_vec()),
+            _ => {}
+        }
+        current = next_opcode(program, current)?;
+    }
+
+    Ok(constants)
+}
+
+/// Position of the opcode following the one at `pc`
+fn next_opcode(program: &[u8], pc: usize) -> Result<usize> {
+    let immediates_start = pc + 1;
+    let end = match program[pc] {
+        // intcblock
+        0x20 => {
+            let (count, mut pos) = read_varuint(program, immediates_start)?;
+            for _ in 0..count {
+                pos = read_varuint(program, pos)?.1;
+            }
+            pos
+        }
+        // bytecblock
+        0x26 => {
+            let (count, mut pos) = read_varuint(program, immediates_start)?;
+            for _ in 0..count {
+                pos = read_bytes(program, pos)?;
+            }
+            pos
+        }
+        // pushbytes
+        0x80 => read_bytes(program, immediates_start)?,
+        // pushint
+        0x81 => read_varuint(program, immediates_start)?.1,
+        // pushbytess
+        0x82 => {
+            let (count, mut pos) = read_varuint(program, immediates_start)?;
+            for _ in 0..count {
+                pos = read_bytes(program, pos)?;
+            }
+            pos
+        }
+        // pushints
+        0x83 => {
+            let (count, mut pos) = read_varuint(program, immediates_start)?;
+            for _ in 0..count {
+                pos = read_varuint(program, pos)?.1;
+            }
+            pos
+        }
+        // switch, match: 1 byte label count + 2 bytes per label
+        0x8d | 0x8e => {
+            let count = *program
+                .get(immediates_start)
+                .ok_or_else(|| anyhow!("Unexpected end of program at: {immediates_start}"))?;
+            immediates_start + 1 + 2 * count as usize
+        }
+        opcode => immediates_start + fixed_immediates_size(opcode, pc)?,
+    };
+    Ok(end)
+}
+
+/// Size of the immediate arguments of the opcodes that don't have a variable size.
+/// Unknown opcodes are an error: assuming a size would misplace all the following opcodes.
+fn fixed_immediates_size(opcode: u8, pc: usize) -> Result<usize> {
+    Ok(match opcode {
+        // gtxna, gitxna
+        0x37 | 0xb8 => 3,
+        // gtxn, txna, gtxnsa, gload, bnz, bz, b, substring, extract, callsub, proto, itxna, gitxn, gtxnas, gitxnas
+        0x33 | 0x36 | 0x39 | 0x3a | 0x40 | 0x41 | 0x42 | 0x51 | 0x57 | 0x88 | 0x8a | 0xb5
+        | 0xb7 | 0xc1 | 0xc6 => 2,
+        // e.g. ecdsa_pk_recover, intc, bytec, txn, global, load, store, dig, frame_dig, itxn_field
+        0x05 | 0x06 | 0x07 | 0x21 | 0x27 | 0x2c | 0x31 | 0x32 | 0x34 | 0x35 | 0x38 | 0x3b
+        | 0x3c | 0x45 | 0x46 | 0x47 | 0x4b | 0x4e | 0x4f | 0x5c | 0x5e | 0x5f | 0x70 | 0x71
+        | 0x72 | 0x73 | 0x74 | 0x8b | 0x8c | 0xb2 | 0xb4 | 0xc0 | 0xc2 | 0xc5 | 0xd0 | 0xd1
+        | 0xe0 | 0xe1 | 0xe2 | 0xe3 | 0xe4 | 0xe5 | 0xe6 => 1,
+        // e.g. err, ed25519verify, intc_n, bytec_n, arithmetic, assert, dup, concat, balance, retsub, box ops
+        0x00..=0x04
+        | 0x08..=0x1f
+        | 0x22..=0x25
+        | 0x28..=0x2b
+        | 0x2d..=0x30
+        | 0x3d..=0x3f
+        | 0x43
+        | 0x44
+        | 0x48..=0x4a
+        | 0x4c
+        | 0x4d
+        | 0x50
+        | 0x52..=0x56
+        | 0x58..=0x5b
+        | 0x5d
+        | 0x60..=0x69
+        | 0x75
+        | 0x78
+        | 0x84..=0x86
+        | 0x89
+        | 0x90..=0x98
+        | 0xa0..=0xb1
+        | 0xb3
+        | 0xb6
+        | 0xb9..=0xbf
+        | 0xc3
+        | 0xc4
+        | 0xd2
+        | 0xd3 => 0,
+        _ => return Err(anyhow!("Unknown opcode: {opcode:#04x} at: {pc}")),
+    })
+}
+
+/// Returns the value and the position after it
+fn read_varuint(program: &[u8], pos: usize) -> Result<(u64, usize)> {
+    let mut value: u64 = 0;
+    let mut shift = 0;
+    let mut current = pos;
+    loop {
+        let byte = *program
+            .get(current)
+            .ok_or_else(|| anyhow!("Unexpected end of program at: {current}"))?;
+        if shift >= 64 {
+            return Err(anyhow!("Invalid varuint at: {pos}"));
+        }
+        value |= ((byte & 0x7f) as u64) << shift;
+        current += 1;
+        if byte & 0x80 == 0 {
+            return Ok((value, current));
+        }
+        shift += 7;
+    }
+}
+
+/// Length prefixed bytes: returns the position after them
+fn read_bytes(program: &[u8], pos: usize) -> Result<usize> {
+    Ok(read_bytes_value(program, pos)?.1)
+}
+
+/// Length prefixed bytes: returns the bytes and the position after them
+fn read_bytes_value(program: &[u8], pos: usize) -> Result<(&[u8], usize)> {
+    let (len, start) = read_varuint(program, pos)?;
+    let end = start + len as usize;
+    if end > program.len() {
+        return Err(anyhow!("Unexpected end of program at: {pos}"));
+    }
+    Ok((&program[start..end], end))
+}
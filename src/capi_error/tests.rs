@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod tests {
+    use crate::capi_error::{
+        decode_rejection,
+        program::{check_index_at, constants, ProgramConstants},
+        to_submit_error, CapiError,
+    };
+    use anyhow::{anyhow, Result};
+    use mbase::teal::TealSource;
+
+    const SOURCE: &str = r#"#pragma version 6
+int 1
+// the raise end date check
+// err: raise_not_ended
+assert
+pushbytes "D" // the byte is the assert opcode
+pop
+txn Sender
+global CreatorAddress
+==
+assert // err: not_owner
+int 1
+assert
+int 1"#;
+
+    // SOURCE, compiled
+    const PROGRAM: &[u8] = &[
+        0x06, // version
+        0x20, 0x01, 0x01, // intcblock 1
+        0x22, // pc 4: intc_0
+        0x44, // pc 5: assert
+        0x80, 0x01, 0x44, // pc 6: pushbytes "D"
+        0x48, // pc 9: pop
+        0x31, 0x00, // pc 10: txn Sender
+        0x32, 0x09, // pc 12: global CreatorAddress
+        0x12, // pc 14: ==
+        0x44, // pc 15: assert
+        0x22, // pc 16: intc_0
+        0x44, // pc 17: assert
+        0x22, // pc 18: intc_0
+    ];
+
+    fn source() -> TealSource {
+        TealSource(SOURCE.as_bytes().to_vec())
+    }
+
+    fn assert_failed_msg(pc: u64) -> String {
+        format!("HTTP error: TransactionPool.Remember: transaction ZKLYCEWKDO64V6WCGGZZUI64JWTYN37YCR6E44VZQB3YLL7OJC5Q: logic eval error: assert failed pc={pc}. Details: pc={pc}, opcodes=intc_0 // 1\nassert\n")
+    }
+
+    #[test]
+    fn test_decodes_check_tagged_above() -> Result<()> {
+        let error = decode_rejection(&assert_failed_msg(5), &source(), PROGRAM)?;
+        assert_eq!(Some(CapiError::RaiseNotEnded), error);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decodes_check_tagged_on_line() -> Result<()> {
+        let error = decode_rejection(&assert_failed_msg(15), &source(), PROGRAM)?;
+        assert_eq!(Some(CapiError::NotOwner), error);
+        Ok(())
+    }
+
+    #[test]
+    fn test_untagged_check_returns_line() -> Result<()> {
+        let error = decode_rejection(&assert_failed_msg(17), &source(), PROGRAM)?;
+        assert_eq!(
+            Some(CapiError::Rejected {
+                pc: Some(17),
+                line: Some(12)
+            }),
+            error
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_failing_opcode_that_is_not_a_check() -> Result<()> {
+        let msg = "transaction ZKLYCEWKDO64V6WCGGZZUI64JWTYN37YCR6E44VZQB3YLL7OJC5Q: logic eval error: + overflowed. Details: pc=14";
+        let error = decode_rejection(msg, &source(), PROGRAM)?;
+        assert_eq!(
+            Some(CapiError::Rejected {
+                pc: Some(14),
+                line: None
+            }),
+            error
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_index_after_opcodes_with_immediates() -> Result<()> {
+        let program = &[
+            0x0a, // version
+            0x74, 0x00, // pc 1: voter_params_get VoterBalance
+            0x44, // pc 3: assert
+            0xe0, 0x00, // pc 4: ec_add BN254g1
+            0x44, // pc 6: assert
+        ];
+        assert_eq!(Some(0), check_index_at(program, 3)?);
+        assert_eq!(Some(1), check_index_at(program, 6)?);
+        // an immediate isn't an opcode
+        assert!(check_index_at(program, 5).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_index_after_signature_opcodes() -> Result<()> {
+        let program = &[
+            0x07, // version
+            0x04, // pc 1: ed25519verify
+            0x44, // pc 2: assert
+            0x07, 0x00, // pc 3: ecdsa_pk_recover Secp256k1
+            0x44, // pc 5: assert
+        ];
+        assert_eq!(Some(0), check_index_at(program, 2)?);
+        assert_eq!(Some(1), check_index_at(program, 5)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_opcode_is_an_error() {
+        let program = &[
+            0x0a, // version
+            0x79, // pc 1: unknown
+            0x44, // pc 2: assert
+        ];
+        assert!(check_index_at(program, 2).is_err());
+        assert!(constants(program).is_err());
+    }
+
+    #[test]
+    fn test_program_constants() -> Result<()> {
+        assert_eq!(
+            ProgramConstants {
+                ints: vec![1],
+                bytes: vec![vec![0x44]],
+            },
+            constants(PROGRAM)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decodes_not_opted_in() -> Result<()> {
+        let app_msg = "TransactionPool.Remember: transaction ZKLYCEWKDO64V6WCGGZZUI64JWTYN37YCR6E44VZQB3YLL7OJC5Q: address FTPBN666KYZVB5YYYLRZ6GXWBKWLJJSXQ3N753USSWS2WIAYK7WJTTYRPI has not opted in to application 123";
+        assert_eq!(
+            Some(CapiError::NotOptedInToApp { app_id: 123 }),
+            decode_rejection(app_msg, &source(), PROGRAM)?
+        );
+
+        let asset_msg = "TransactionPool.Remember: transaction ZKLYCEWKDO64V6WCGGZZUI64JWTYN37YCR6E44VZQB3YLL7OJC5Q: asset 456 missing from FTPBN666KYZVB5YYYLRZ6GXWBKWLJJSXQ3N753USSWS2WIAYK7WJTTYRPI";
+        assert_eq!(
+            Some(CapiError::NotOptedInToAsset { asset_id: 456 }),
+            decode_rejection(asset_msg, &source(), PROGRAM)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_other_errors_are_not_decoded() -> Result<()> {
+        let error = decode_rejection("HTTP error: connection refused", &source(), PROGRAM)?;
+        assert_eq!(None, error);
+        Ok(())
+    }
+
+    #[test]
+    fn test_submit_error_keeps_the_rejection_pc() {
+        let error = to_submit_error(anyhow!(assert_failed_msg(15)));
+        // mapped to the check with the rendered approval, not re-evaluated
+        assert_eq!(
+            Some(&CapiError::Rejected {
+                pc: Some(15),
+                line: None
+            }),
+            error.downcast_ref::<CapiError>()
+        );
+    }
+}
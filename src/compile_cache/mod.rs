@@ -0,0 +1,209 @@
+use crate::dao_algod::DaoAlgod;
+use algonaut::core::CompiledTeal;
+use anyhow::{anyhow, Result};
+use data_encoding::{BASE64, HEXLOWER};
+use sha2::Digest;
+use std::{collections::HashMap, sync::Mutex};
+
+#[cfg(not(target_arch = "wasm32"))]
+use algonaut::{
+    core::{Address, SuggestedTransactionParams},
+    model::algod::v2::{
+        Account, Application, ApplicationLocalState, Asset, DryrunResponse, PendingTransaction,
+        TealKeyValue, TransactionResponse,
+    },
+    transaction::SignedTransaction,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use async_trait::async_trait;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+mod tests;
+
+/// Where the compiled programs are kept.
+/// Keys and values are strings (the program is base64 encoded), so browser storages (e.g. local storage) can implement it directly.
+pub trait CompileStore: StoreBounds {
+    fn get(&self, key: &str) -> Result<Option<String>>;
+    fn set(&self, key: &str, value: &str) -> Result<()>;
+}
+
+// Like the async traits' (?Send): the browser storages aren't Send + Sync
+#[cfg(not(target_arch = "wasm32"))]
+pub trait StoreBounds: Send + Sync {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send + Sync> StoreBounds for T {}
+#[cfg(target_arch = "wasm32")]
+pub trait StoreBounds {}
+#[cfg(target_arch = "wasm32")]
+impl<T> StoreBounds for T {}
+
+/// Keeps the programs while the process runs
+#[derive(Default)]
+pub struct MemoryCompileStore {
+    programs: Mutex<HashMap<String, String>>,
+}
+
+impl CompileStore for MemoryCompileStore {
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(self
+            .programs
+            .lock()
+            .map_err(|e| anyhow!("Couldn't lock compiled programs: {e}"))?
+            .get(key)
+            .cloned())
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        self.programs
+            .lock()
+            .map_err(|e| anyhow!("Couldn't lock compiled programs: {e}"))?
+            .insert(key.to_owned(), value.to_owned());
+        Ok(())
+    }
+}
+
+/// Keeps the programs in a directory, a file per program
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileCompileStore {
+    dir: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileCompileStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Result<FileCompileStore> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(FileCompileStore { dir })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CompileStore for FileCompileStore {
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        let path = self.dir.join(key);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(std::fs::read_to_string(path)?))
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        Ok(std::fs::write(self.dir.join(key), value)?)
+    }
+}
+
+/// Compiles TEAL with algod, only if the source wasn't compiled before.
+/// The programs are keyed by the hash of the source.
+pub struct CompileCache {
+    store: Box<dyn CompileStore>,
+}
+
+impl CompileCache {
+    pub fn new(store: impl CompileStore + 'static) -> CompileCache {
+        CompileCache {
+            store: Box::new(store),
+        }
+    }
+
+    pub fn key(source: &[u8]) -> String {
+        format!(
+            "teal-{}",
+            HEXLOWER.encode(&sha2::Sha512_256::digest(source))
+        )
+    }
+
+    pub async fn compile(&self, algod: &dyn DaoAlgod, source: &[u8]) -> Result<CompiledTeal> {
+        let key = CompileCache::key(source);
+
+        if let Some(program) = self.store.get(&key)? {
+            log::trace!("Compile cache hit: {key}");
+            return Ok(CompiledTeal(BASE64.decode(program.as_bytes())?));
+        }
+
+        let compiled = algod.compile_teal(source).await?;
+        self.store.set(&key, &BASE64.encode(&compiled.0))?;
+        Ok(compiled)
+    }
+}
+
+/// Algod that compiles through a [CompileCache]: can be passed to the flows instead of the algod.
+/// Not on wasm: [DaoAlgod] is Send + Sync, the stores there aren't.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct CachedCompileAlgod<A> {
+    algod: A,
+    cache: CompileCache,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<A: DaoAlgod> CachedCompileAlgod<A> {
+    pub fn new(algod: A, cache: CompileCache) -> CachedCompileAlgod<A> {
+        CachedCompileAlgod { algod, cache }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl<A: DaoAlgod> DaoAlgod for CachedCompileAlgod<A> {
+    async fn suggested_transaction_params(&self) -> Result<SuggestedTransactionParams> {
+        self.algod.suggested_transaction_params().await
+    }
+
+    async fn account_information(&self, address: &Address) -> Result<Account> {
+        self.algod.account_information(address).await
+    }
+
+    async fn asset_information(&self, asset_id: u64) -> Result<Asset> {
+        self.algod.asset_information(asset_id).await
+    }
+
+    async fn application_information(&self, app_id: u64) -> Result<Application> {
+        self.algod.application_information(app_id).await
+    }
+
+    async fn compile_teal(&self, source: &[u8]) -> Result<CompiledTeal> {
+        self.cache.compile(&self.algod, source).await
+    }
+
+    async fn broadcast_signed_transaction(
+        &self,
+        tx: &SignedTransaction,
+    ) -> Result<TransactionResponse> {
+        self.algod.broadcast_signed_transaction(tx).await
+    }
+
+    async fn broadcast_signed_transactions(
+        &self,
+        txs: &[SignedTransaction],
+    ) -> Result<TransactionResponse> {
+        self.algod.broadcast_signed_transactions(txs).await
+    }
+
+    async fn pending_transaction_with_id(&self, tx_id: &str) -> Result<PendingTransaction> {
+        self.algod.pending_transaction_with_id(tx_id).await
+    }
+
+    async fn last_round(&self) -> Result<u64> {
+        self.algod.last_round().await
+    }
+
+    async fn wait_for_round_after(&self, round: u64) -> Result<u64> {
+        self.algod.wait_for_round_after(round).await
+    }
+
+    async fn dryrun(&self, signed_txs: &[SignedTransaction]) -> Result<DryrunResponse> {
+        self.algod.dryrun(signed_txs).await
+    }
+
+    async fn app_global_state(&self, app_id: u64) -> Result<Vec<TealKeyValue>> {
+        self.algod.app_global_state(app_id).await
+    }
+
+    async fn app_local_state(
+        &self,
+        address: &Address,
+        app_id: u64,
+    ) -> Result<Option<ApplicationLocalState>> {
+        self.algod.app_local_state(address, app_id).await
+    }
+}
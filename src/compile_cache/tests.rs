@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        compile_cache::{
+            CachedCompileAlgod, CompileCache, CompileStore, FileCompileStore, MemoryCompileStore,
+        },
+        dao_algod::{fake::FakeAlgod, DaoAlgod},
+    };
+    use anyhow::Result;
+    use data_encoding::BASE64;
+    use tokio::test;
+
+    const SOURCE: &[u8] = b"#pragma version 6\nint 1";
+
+    #[test]
+    async fn test_miss_compiles_and_stores() -> Result<()> {
+        let cache = CompileCache::new(MemoryCompileStore::default());
+
+        // the fake algod compiles to the source
+        let compiled = cache.compile(&FakeAlgod::new(), SOURCE).await?;
+
+        assert_eq!(SOURCE.to_vec(), compiled.0);
+        assert_eq!(
+            Some(BASE64.encode(SOURCE)),
+            cache.store.get(&CompileCache::key(SOURCE))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    async fn test_hit_returns_stored_program() -> Result<()> {
+        let store = MemoryCompileStore::default();
+        store.set(&CompileCache::key(SOURCE), &BASE64.encode(b"stored"))?;
+        let cache = CompileCache::new(store);
+
+        let compiled = cache.compile(&FakeAlgod::new(), SOURCE).await?;
+
+        assert_eq!(b"stored".to_vec(), compiled.0);
+        Ok(())
+    }
+
+    #[test]
+    async fn test_key_depends_on_source() -> Result<()> {
+        assert_eq!(CompileCache::key(SOURCE), CompileCache::key(SOURCE));
+        assert_ne!(CompileCache::key(SOURCE), CompileCache::key(b"int 0"));
+        Ok(())
+    }
+
+    #[test]
+    async fn test_cached_compile_algod() -> Result<()> {
+        let store = MemoryCompileStore::default();
+        store.set(&CompileCache::key(SOURCE), &BASE64.encode(b"stored"))?;
+        let algod = CachedCompileAlgod::new(FakeAlgod::new(), CompileCache::new(store));
+
+        assert_eq!(b"stored".to_vec(), algod.compile_teal(SOURCE).await?.0);
+        // not cached: compiled by the wrapped algod
+        assert_eq!(b"int 0".to_vec(), algod.compile_teal(b"int 0").await?.0);
+        Ok(())
+    }
+
+    #[test]
+    async fn test_file_store() -> Result<()> {
+        let dir = std::env::temp_dir().join("capi_compile_cache_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = FileCompileStore::new(&dir)?;
+
+        assert_eq!(None, store.get("teal-key")?);
+        store.set("teal-key", "value")?;
+        assert_eq!(Some("value".to_owned()), store.get("teal-key")?);
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use super::DaoAlgod;
+use algonaut::{
+    core::{Address, CompiledTeal, MicroAlgos, Round, SuggestedTransactionParams},
+    crypto::HashDigest,
+    model::algod::v2::{
+        Account, Application, Asset, DryrunResponse, PendingTransaction, TransactionResponse,
+    },
+    transaction::SignedTransaction,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::{collections::HashMap, sync::Mutex};
+
+/// In-memory algod, to run the transaction construction (and submission) logic without a network.
+///
+/// - Accounts, assets, apps and pending transactions are returned only if they were added beforehand, otherwise the calls error (like algod's 404).
+/// - Compiling returns the source bytes as the program, so tests can inspect the rendered TEAL.
+/// - Broadcasting only records the transactions, see [FakeAlgod::broadcasted].
+/// - Rounds advance only when waiting for them (starting at the params' first valid round), unless stalled with [FakeAlgod::with_stalled_rounds].
+/// - Pending transactions added with [FakeAlgod::with_pending_transaction_confirmed_at] are returned unconfirmed until their round.
+/// - Dryruns return the response set with [FakeAlgod::with_dryrun_response] (there's no evaluator).
+pub struct FakeAlgod {
+    params: SuggestedTransactionParams,
+    // keyed by address bytes
+    accounts: HashMap<[u8; 32], Account>,
+    assets: HashMap<u64, Asset>,
+    apps: HashMap<u64, Application>,
+    pending_txs: HashMap<String, PendingTransaction>,
+    // round at which the pending tx is confirmed, keyed by tx id
+    confirmation_rounds: HashMap<String, u64>,
+    stalled_rounds: bool,
+    dryrun_response: Option<DryrunResponse>,
+    broadcasted: Mutex<Vec<SignedTransaction>>,
+    last_round: Mutex<u64>,
+}
+
+impl FakeAlgod {
+    pub fn new() -> FakeAlgod {
+        FakeAlgod::with_params(fake_params())
+    }
+
+    pub fn with_params(params: SuggestedTransactionParams) -> FakeAlgod {
+        FakeAlgod {
+            last_round: Mutex::new(params.first_valid.0),
+            params,
+            accounts: HashMap::new(),
+            assets: HashMap::new(),
+            apps: HashMap::new(),
+            pending_txs: HashMap::new(),
+            confirmation_rounds: HashMap::new(),
+            stalled_rounds: false,
+            dryrun_response: None,
+            broadcasted: Mutex::new(vec![]),
+        }
+    }
+
+    pub fn with_account(mut self, account: Account) -> FakeAlgod {
+        self.accounts.insert(account.address.0, account);
+        self
+    }
+
+    pub fn with_asset(mut self, asset: Asset) -> FakeAlgod {
+        self.assets.insert(asset.index, asset);
+        self
+    }
+
+    pub fn with_app(mut self, app: Application) -> FakeAlgod {
+        self.apps.insert(app.id, app);
+        self
+    }
+
+    pub fn with_pending_transaction(mut self, tx_id: &str, p_tx: PendingTransaction) -> FakeAlgod {
+        self.pending_txs.insert(tx_id.to_owned(), p_tx);
+        self
+    }
+
+    /// `p_tx` (with its confirmed round) is returned once the fake reaches `round`, before that it's in the pool
+    pub fn with_pending_transaction_confirmed_at(
+        mut self,
+        tx_id: &str,
+        p_tx: PendingTransaction,
+        round: u64,
+    ) -> FakeAlgod {
+        self.confirmation_rounds.insert(tx_id.to_owned(), round);
+        self.with_pending_transaction(tx_id, p_tx)
+    }
+
+    /// Waiting for a round never finishes, like a stuck node
+    pub fn with_stalled_rounds(mut self) -> FakeAlgod {
+        self.stalled_rounds = true;
+        self
+    }
+
+    pub fn with_dryrun_response(mut self, res: DryrunResponse) -> FakeAlgod {
+        self.dryrun_response = Some(res);
+        self
+    }
+
+    pub fn params(&self) -> &SuggestedTransactionParams {
+        &self.params
+    }
+
+    /// All the transactions broadcasted so far, in submission order
+    pub fn broadcasted(&self) -> Result<Vec<SignedTransaction>> {
+        Ok(self
+            .broadcasted
+            .lock()
+            .map_err(|e| anyhow!("Couldn't lock broadcasted txs: {e}"))?
+            .clone())
+    }
+
+    fn record(&self, txs: &[SignedTransaction]) -> Result<TransactionResponse> {
+        let first = txs
+            .first()
+            .ok_or_else(|| anyhow!("Can't broadcast an empty list of transactions"))?;
+        let tx_id = first.transaction.id()?;
+
+        self.broadcasted
+            .lock()
+            .map_err(|e| anyhow!("Couldn't lock broadcasted txs: {e}"))?
+            .extend(txs.iter().cloned());
+
+        Ok(TransactionResponse { tx_id })
+    }
+
+    fn lock_last_round(&self) -> Result<std::sync::MutexGuard<u64>> {
+        self.last_round
+            .lock()
+            .map_err(|e| anyhow!("Couldn't lock last round: {e}"))
+    }
+}
+
+impl Default for FakeAlgod {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Arbitrary valid params, with the protocol's min fee
+pub fn fake_params() -> SuggestedTransactionParams {
+    SuggestedTransactionParams {
+        genesis_id: "fake-v1".to_owned(),
+        genesis_hash: HashDigest([0; 32]),
+        consensus_version: "fake".to_owned(),
+        // 0, so the fee is always the min fee (independent of tx size)
+        fee_per_byte: MicroAlgos(0),
+        min_fee: MicroAlgos(1_000),
+        first_valid: Round(1),
+        last_valid: Round(1_001),
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl DaoAlgod for FakeAlgod {
+    async fn suggested_transaction_params(&self) -> Result<SuggestedTransactionParams> {
+        Ok(self.params.clone())
+    }
+
+    async fn account_information(&self, address: &Address) -> Result<Account> {
+        self.accounts
+            .get(&address.0)
+            .cloned()
+            .ok_or_else(|| anyhow!("Fake algod: account not found: {address}"))
+    }
+
+    async fn asset_information(&self, asset_id: u64) -> Result<Asset> {
+        self.assets
+            .get(&asset_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Fake algod: asset not found: {asset_id}"))
+    }
+
+    async fn application_information(&self, app_id: u64) -> Result<Application> {
+        self.apps
+            .get(&app_id)
+            .cloned()
+            // algod's 404 message, see [super::is_app_not_found]
+            .ok_or_else(|| anyhow!("Fake algod: application does not exist: {app_id}"))
+    }
+
+    async fn compile_teal(&self, source: &[u8]) -> Result<CompiledTeal> {
+        Ok(CompiledTeal(source.to_vec()))
+    }
+
+    async fn broadcast_signed_transaction(
+        &self,
+        tx: &SignedTransaction,
+    ) -> Result<TransactionResponse> {
+        self.record(&[tx.clone()])
+    }
+
+    async fn broadcast_signed_transactions(
+        &self,
+        txs: &[SignedTransaction],
+    ) -> Result<TransactionResponse> {
+        self.record(txs)
+    }
+
+    async fn pending_transaction_with_id(&self, tx_id: &str) -> Result<PendingTransaction> {
+        let p_tx = self
+            .pending_txs
+            .get(tx_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Fake algod: pending tx not found: {tx_id}"))?;
+
+        match self.confirmation_rounds.get(tx_id) {
+            Some(round) if *self.lock_last_round()? < *round => Ok(PendingTransaction {
+                confirmed_round: None,
+                ..p_tx
+            }),
+            _ => Ok(p_tx),
+        }
+    }
+
+    async fn last_round(&self) -> Result<u64> {
+        Ok(*self.lock_last_round()?)
+    }
+
+    async fn wait_for_round_after(&self, round: u64) -> Result<u64> {
+        if self.stalled_rounds {
+            return futures::future::pending().await;
+        }
+        let mut last_round = self.lock_last_round()?;
+        *last_round = (*last_round).max(round + 1);
+        Ok(*last_round)
+    }
+
+    async fn dryrun(&self, _signed_txs: &[SignedTransaction]) -> Result<DryrunResponse> {
+        self.dryrun_response
+            .clone()
+            .ok_or_else(|| anyhow!("Fake algod: no dryrun response set"))
+    }
+}
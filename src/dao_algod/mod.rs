@@ -0,0 +1,198 @@
+use algonaut::{
+    algod::v2::Algod,
+    core::{Address, CompiledTeal, Round, SuggestedTransactionParams},
+    model::algod::v2::{
+        Account, Application, ApplicationLocalState, Asset, DryrunResponse, PendingTransaction,
+        TealKeyValue, TransactionResponse,
+    },
+    transaction::SignedTransaction,
+    util::dryrun_printer::create_dryrun,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub mod fake;
+
+/// Whether the error is algod's answer for an app that doesn't exist (404 "application does not exist", e.g. a deleted app),
+/// as opposed to e.g. a network error
+pub fn is_app_not_found(e: &anyhow::Error) -> bool {
+    e.to_string().contains("application does not exist")
+}
+
+/// The algod calls the crate makes.
+/// Flows take this instead of the concrete [Algod], so transaction construction can run against [fake::FakeAlgod] (no network needed).
+// Send + Sync: the implementations are expected to only hold clients / immutable data (or sync their mutable state, like the fake)
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait DaoAlgod: Send + Sync {
+    async fn suggested_transaction_params(&self) -> Result<SuggestedTransactionParams>;
+    async fn account_information(&self, address: &Address) -> Result<Account>;
+    async fn asset_information(&self, asset_id: u64) -> Result<Asset>;
+    async fn application_information(&self, app_id: u64) -> Result<Application>;
+    async fn compile_teal(&self, source: &[u8]) -> Result<CompiledTeal>;
+    async fn broadcast_signed_transaction(
+        &self,
+        tx: &SignedTransaction,
+    ) -> Result<TransactionResponse>;
+    async fn broadcast_signed_transactions(
+        &self,
+        txs: &[SignedTransaction],
+    ) -> Result<TransactionResponse>;
+    async fn pending_transaction_with_id(&self, tx_id: &str) -> Result<PendingTransaction>;
+    /// The last round seen by the node
+    async fn last_round(&self) -> Result<u64>;
+    /// Waits until the round after `round` is reached, returning the (new) last round
+    async fn wait_for_round_after(&self, round: u64) -> Result<u64>;
+    /// Evaluates the transactions' programs against the current ledger state, without submitting them
+    async fn dryrun(&self, signed_txs: &[SignedTransaction]) -> Result<DryrunResponse>;
+
+    /// Raw (not decoded) global state of the app
+    async fn app_global_state(&self, app_id: u64) -> Result<Vec<TealKeyValue>> {
+        Ok(self
+            .application_information(app_id)
+            .await?
+            .params
+            .global_state)
+    }
+
+    /// Raw (not decoded) local state of the account for the app, None if the account isn't opted in
+    async fn app_local_state(
+        &self,
+        address: &Address,
+        app_id: u64,
+    ) -> Result<Option<ApplicationLocalState>> {
+        Ok(self
+            .account_information(address)
+            .await?
+            .apps_local_state
+            .into_iter()
+            .find(|s| s.id == app_id))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl DaoAlgod for Algod {
+    async fn suggested_transaction_params(&self) -> Result<SuggestedTransactionParams> {
+        Ok(Algod::suggested_transaction_params(self).await?)
+    }
+
+    async fn account_information(&self, address: &Address) -> Result<Account> {
+        Ok(Algod::account_information(self, address).await?)
+    }
+
+    async fn asset_information(&self, asset_id: u64) -> Result<Asset> {
+        Ok(Algod::asset_information(self, asset_id).await?)
+    }
+
+    async fn application_information(&self, app_id: u64) -> Result<Application> {
+        Ok(Algod::application_information(self, app_id).await?)
+    }
+
+    async fn compile_teal(&self, source: &[u8]) -> Result<CompiledTeal> {
+        Ok(Algod::compile_teal(self, source).await?)
+    }
+
+    async fn broadcast_signed_transaction(
+        &self,
+        tx: &SignedTransaction,
+    ) -> Result<TransactionResponse> {
+        Ok(Algod::broadcast_signed_transaction(self, tx).await?)
+    }
+
+    async fn broadcast_signed_transactions(
+        &self,
+        txs: &[SignedTransaction],
+    ) -> Result<TransactionResponse> {
+        Ok(Algod::broadcast_signed_transactions(self, txs).await?)
+    }
+
+    async fn pending_transaction_with_id(&self, tx_id: &str) -> Result<PendingTransaction> {
+        Ok(Algod::pending_transaction_with_id(self, tx_id).await?)
+    }
+
+    async fn last_round(&self) -> Result<u64> {
+        Ok(Algod::status(self).await?.last_round)
+    }
+
+    async fn wait_for_round_after(&self, round: u64) -> Result<u64> {
+        Ok(Algod::status_after_block(self, Round(round))
+            .await?
+            .last_round)
+    }
+
+    async fn dryrun(&self, signed_txs: &[SignedTransaction]) -> Result<DryrunResponse> {
+        let req = create_dryrun(self, signed_txs).await?;
+        Ok(Algod::dryrun_teal(self, &req).await?)
+    }
+}
+
+/// Allows to pass references to references (e.g. `&algod` where `algod: &Algod`), as done across the tests
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T> DaoAlgod for &T
+where
+    T: DaoAlgod + ?Sized,
+{
+    async fn suggested_transaction_params(&self) -> Result<SuggestedTransactionParams> {
+        (**self).suggested_transaction_params().await
+    }
+
+    async fn account_information(&self, address: &Address) -> Result<Account> {
+        (**self).account_information(address).await
+    }
+
+    async fn asset_information(&self, asset_id: u64) -> Result<Asset> {
+        (**self).asset_information(asset_id).await
+    }
+
+    async fn application_information(&self, app_id: u64) -> Result<Application> {
+        (**self).application_information(app_id).await
+    }
+
+    async fn compile_teal(&self, source: &[u8]) -> Result<CompiledTeal> {
+        (**self).compile_teal(source).await
+    }
+
+    async fn broadcast_signed_transaction(
+        &self,
+        tx: &SignedTransaction,
+    ) -> Result<TransactionResponse> {
+        (**self).broadcast_signed_transaction(tx).await
+    }
+
+    async fn broadcast_signed_transactions(
+        &self,
+        txs: &[SignedTransaction],
+    ) -> Result<TransactionResponse> {
+        (**self).broadcast_signed_transactions(txs).await
+    }
+
+    async fn pending_transaction_with_id(&self, tx_id: &str) -> Result<PendingTransaction> {
+        (**self).pending_transaction_with_id(tx_id).await
+    }
+
+    async fn last_round(&self) -> Result<u64> {
+        (**self).last_round().await
+    }
+
+    async fn wait_for_round_after(&self, round: u64) -> Result<u64> {
+        (**self).wait_for_round_after(round).await
+    }
+
+    async fn dryrun(&self, signed_txs: &[SignedTransaction]) -> Result<DryrunResponse> {
+        (**self).dryrun(signed_txs).await
+    }
+
+    async fn app_global_state(&self, app_id: u64) -> Result<Vec<TealKeyValue>> {
+        (**self).app_global_state(app_id).await
+    }
+
+    async fn app_local_state(
+        &self,
+        address: &Address,
+        app_id: u64,
+    ) -> Result<Option<ApplicationLocalState>> {
+        (**self).app_local_state(address, app_id).await
+    }
+}
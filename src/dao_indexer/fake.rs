@@ -0,0 +1,174 @@
+use super::DaoIndexer;
+use algonaut::{
+    core::Address,
+    model::indexer::v2::{
+        Account, QueryAccount, QueryAccountTransaction, QueryTransaction, Transaction,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use data_encoding::BASE64;
+use std::collections::HashMap;
+
+/// In-memory indexer, seeded with transactions and accounts.
+///
+/// Fixtures can be added as JSON, in the format returned by the indexer's REST API
+/// (e.g. the "transactions" array of a `/v2/transactions` response), so real responses can be pasted as they are.
+///
+/// Only the filters used by the crate are supported (address, asset id, app id, note prefix and min round), other query parameters are ignored.
+/// Entries are returned in the order they were added.
+pub struct FakeIndexer {
+    transactions: Vec<Transaction>,
+    accounts: Vec<Account>,
+    // accounts opted in to an app, by app id
+    app_accounts: HashMap<u64, Vec<Account>>,
+}
+
+impl FakeIndexer {
+    pub fn new() -> FakeIndexer {
+        FakeIndexer {
+            transactions: vec![],
+            accounts: vec![],
+            app_accounts: HashMap::new(),
+        }
+    }
+
+    pub fn with_transaction(mut self, tx: Transaction) -> FakeIndexer {
+        self.transactions.push(tx);
+        self
+    }
+
+    /// Expects a JSON array of indexer transactions
+    pub fn with_transactions_json(mut self, json: &str) -> Result<FakeIndexer> {
+        let txs: Vec<Transaction> = serde_json::from_str(json)?;
+        self.transactions.extend(txs);
+        Ok(self)
+    }
+
+    pub fn with_account(mut self, account: Account) -> FakeIndexer {
+        self.accounts.push(account);
+        self
+    }
+
+    /// Expects a JSON array of indexer accounts
+    pub fn with_accounts_json(mut self, json: &str) -> Result<FakeIndexer> {
+        let accounts: Vec<Account> = serde_json::from_str(json)?;
+        self.accounts.extend(accounts);
+        Ok(self)
+    }
+
+    /// Adds an account that's returned when querying accounts by app id (i.e. opted in to the app)
+    pub fn with_app_account(mut self, app_id: u64, account: Account) -> FakeIndexer {
+        self.app_accounts.entry(app_id).or_default().push(account);
+        self
+    }
+
+    fn transactions_involving(&self, address: &str) -> Vec<Transaction> {
+        self.transactions
+            .iter()
+            .filter(|tx| involves(tx, address))
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for FakeIndexer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether the tx passes the query's supported filters
+fn matches(tx: &Transaction, query: &QueryTransaction) -> bool {
+    query
+        .address
+        .as_ref()
+        .map(|address| involves(tx, address))
+        .unwrap_or(true)
+        && query
+            .application_id
+            .map(|app_id| {
+                tx.application_transaction
+                    .as_ref()
+                    .map(|app_tx| app_tx.application_id == app_id)
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true)
+        && query
+            .min_round
+            .map(|min_round| {
+                tx.confirmed_round
+                    .map(|round| round >= min_round)
+                    .unwrap_or(false)
+            })
+            .unwrap_or(true)
+        && query
+            .note_prefix
+            .as_ref()
+            .map(|prefix| has_note_prefix(tx, prefix))
+            .unwrap_or(true)
+}
+
+/// `prefix` is base64, like the indexer's note-prefix parameter
+fn has_note_prefix(tx: &Transaction, prefix: &str) -> bool {
+    let note = tx
+        .note
+        .as_ref()
+        .and_then(|note| BASE64.decode(note.as_bytes()).ok());
+    match (note, BASE64.decode(prefix.as_bytes())) {
+        (Some(note), Ok(prefix)) => note.starts_with(&prefix),
+        _ => false,
+    }
+}
+
+/// Whether the address participates in the tx (any role, including inner txs), as the indexer's address filter does
+fn involves(tx: &Transaction, address: &str) -> bool {
+    tx.sender == address
+        || tx
+            .payment_transaction
+            .as_ref()
+            .map(|p| p.receiver == address)
+            .unwrap_or(false)
+        || tx
+            .asset_transfer_transaction
+            .as_ref()
+            .map(|x| x.receiver == address)
+            .unwrap_or(false)
+        || tx.inner_txns.iter().any(|inner| involves(inner, address))
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl DaoIndexer for FakeIndexer {
+    async fn transactions(&self, query: &QueryTransaction) -> Result<Vec<Transaction>> {
+        Ok(self
+            .transactions
+            .iter()
+            .filter(|tx| matches(tx, query))
+            .cloned()
+            .collect())
+    }
+
+    async fn account_transactions(
+        &self,
+        address: &Address,
+        _query: &QueryAccountTransaction,
+    ) -> Result<Vec<Transaction>> {
+        Ok(self.transactions_involving(&address.to_string()))
+    }
+
+    async fn accounts(&self, query: &QueryAccount) -> Result<Vec<Account>> {
+        if let Some(app_id) = query.application_id {
+            return Ok(self.app_accounts.get(&app_id).cloned().unwrap_or_default());
+        }
+        Ok(match query.asset_id {
+            Some(asset_id) => self
+                .accounts
+                .iter()
+                .filter(|a| a.assets.iter().any(|h| h.asset_id == asset_id))
+                .cloned()
+                .collect(),
+            None => self.accounts.clone(),
+        })
+    }
+}
@@ -0,0 +1,73 @@
+use algonaut::{
+    core::Address,
+    indexer::v2::Indexer,
+    model::indexer::v2::{
+        Account, QueryAccount, QueryAccountTransaction, QueryTransaction, Transaction,
+    },
+};
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub mod fake;
+
+/// The indexer calls the crate makes.
+/// Queries take this instead of the concrete [Indexer], so they can run against [fake::FakeIndexer],
+/// which is deterministic and doesn't have to wait for indexing.
+/// Returns only the entries (e.g. transactions) of the responses, as the rest (round, pagination) isn't used.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait DaoIndexer: Send + Sync {
+    async fn transactions(&self, query: &QueryTransaction) -> Result<Vec<Transaction>>;
+    async fn account_transactions(
+        &self,
+        address: &Address,
+        query: &QueryAccountTransaction,
+    ) -> Result<Vec<Transaction>>;
+    async fn accounts(&self, query: &QueryAccount) -> Result<Vec<Account>>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl DaoIndexer for Indexer {
+    async fn transactions(&self, query: &QueryTransaction) -> Result<Vec<Transaction>> {
+        Ok(Indexer::transactions(self, query).await?.transactions)
+    }
+
+    async fn account_transactions(
+        &self,
+        address: &Address,
+        query: &QueryAccountTransaction,
+    ) -> Result<Vec<Transaction>> {
+        Ok(Indexer::account_transactions(self, address, query)
+            .await?
+            .transactions)
+    }
+
+    async fn accounts(&self, query: &QueryAccount) -> Result<Vec<Account>> {
+        Ok(Indexer::accounts(self, query).await?.accounts)
+    }
+}
+
+/// Allows to pass references to references, see the equivalent impl for DaoAlgod
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T> DaoIndexer for &T
+where
+    T: DaoIndexer + ?Sized,
+{
+    async fn transactions(&self, query: &QueryTransaction) -> Result<Vec<Transaction>> {
+        (**self).transactions(query).await
+    }
+
+    async fn account_transactions(
+        &self,
+        address: &Address,
+        query: &QueryAccountTransaction,
+    ) -> Result<Vec<Transaction>> {
+        (**self).account_transactions(address, query).await
+    }
+
+    async fn accounts(&self, query: &QueryAccount) -> Result<Vec<Account>> {
+        (**self).accounts(query).await
+    }
+}
@@ -1,15 +1,19 @@
+use crate::{
+    dao_algod::DaoAlgod,
+    signer::{Sign, SignedTxs, TxSigner},
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
     core::{Address, SuggestedTransactionParams},
     transaction::{builder::CallApplication, SignedTransaction, Transaction, TxnBuilder},
 };
 use anyhow::Result;
-use mbase::models::{dao_app_id::DaoAppId, timestamp::Timestamp, tx_id::TxId};
+use mbase::models::{dao_app_id::DaoAppId, timestamp::Timestamp};
 use serde::{Deserialize, Serialize};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn dev_settings(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     sender: &Address,
     app_id: DaoAppId,
     settings: &DevSettings,
@@ -47,13 +51,16 @@ pub fn dev_settings_app_call_tx(
     Ok(tx)
 }
 
-pub async fn submit_dev_settings(algod: &Algod, signed: &DevSettingsSigned) -> Result<TxId> {
+pub async fn submit_dev_settings(
+    algod: &dyn DaoAlgod,
+    signed: &DevSettingsSigned,
+) -> Result<Submission> {
     log::debug!("calling submit dev settings..");
 
     let res = algod
         .broadcast_signed_transactions(&[signed.app_call_tx.clone()])
         .await?;
-    res.tx_id.parse()
+    Submission::from_response(&res)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -65,3 +72,19 @@ pub struct DevSettingsToSign {
 pub struct DevSettingsSigned {
     pub app_call_tx: SignedTransaction,
 }
+
+impl Sign for DevSettingsToSign {
+    type Signed = DevSettingsSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<DevSettingsSigned> {
+        Ok(DevSettingsSigned {
+            app_call_tx: signer.sign_tx(self.app_call_tx)?,
+        })
+    }
+}
+
+impl SignedTxs for DevSettingsSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.app_call_tx.clone()]
+    }
+}
@@ -1,21 +1,25 @@
-use algonaut::{algod::v2::Algod, core::Address};
-use anyhow::{Error, Result};
-use mbase::{
-    models::{funds::FundsAmount, share_amount::ShareAmount},
-    state::dao_app_state::{dao_global_state, dao_investor_state},
+use algonaut::core::Address;
+use anyhow::{anyhow, Result};
+use mbase::models::{funds::FundsAmount, share_amount::ShareAmount};
+
+use crate::{
+    dao_algod::DaoAlgod,
+    flows::create_dao::model::Dao,
+    state::{
+        account_state::funds_holdings,
+        dao_app_state::{dao_global_state, dao_investor_state},
+    },
 };
 
-use crate::{flows::create_dao::model::Dao, state::account_state::funds_holdings};
-
 pub async fn claim_diagnostics(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     investor: &Address,
     dao: &Dao,
 ) -> Result<ClaimDiagnostics> {
     let central_total_received = dao_global_state(algod, dao.app_id).await?.received;
     let central_investor_state = dao_investor_state(algod, investor, dao.app_id)
-        .await
-        .map_err(Error::msg)?;
+        .await?
+        .ok_or_else(|| anyhow!("Investor: {investor} isn't opted in to the dao's app"))?;
 
     let app_balance = funds_holdings(algod, &dao.app_address(), dao.funds_asset_id).await?;
 
@@ -35,7 +39,11 @@ pub struct ClaimDiagnostics {
     pub investor_share_amount: ShareAmount,
 }
 
-pub async fn log_claim_diagnostics(algod: &Algod, investor: &Address, dao: &Dao) -> Result<()> {
+pub async fn log_claim_diagnostics(
+    algod: &dyn DaoAlgod,
+    investor: &Address,
+    dao: &Dao,
+) -> Result<()> {
     let diag = claim_diagnostics(algod, investor, dao).await?;
 
     log::info!("//////////////////////////////////////////////////////////");
@@ -0,0 +1,214 @@
+use crate::{dao_algod::DaoAlgod, signer::SignedTxs};
+use algonaut::{
+    core::Address,
+    model::algod::v2::{DryrunResponse, DryrunTxnResult, EvalDeltaKeyValue},
+    transaction::SignedTransaction,
+    util::dryrun_printer::{
+        app_trace_with_config, lsig_trace_with_config, BytesFormat, MaxColumnWidths,
+        StackPrinterConfig,
+    },
+};
+use anyhow::{anyhow, Error, Result};
+use data_encoding::BASE64;
+
+/// What the signed flow struct would do if submitted now, without submitting it.
+pub async fn dryrun(algod: &dyn DaoAlgod, signed: &dyn SignedTxs) -> Result<DryrunReport> {
+    dryrun_txs(algod, &signed.signed_txs()).await
+}
+
+pub async fn dryrun_txs(
+    algod: &dyn DaoAlgod,
+    signed_txs: &[SignedTransaction],
+) -> Result<DryrunReport> {
+    let res = algod.dryrun(signed_txs).await?;
+
+    if let Some(error) = res.error.as_ref().filter(|e| !e.is_empty()) {
+        return Err(anyhow!("Dryrun error: {error}. Complete response: {res:?}"));
+    }
+
+    log_traces(&res);
+
+    to_report(signed_txs, &res)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryrunReport {
+    /// In the order of the submitted transactions
+    pub txs: Vec<TxDryrunReport>,
+}
+
+impl DryrunReport {
+    /// Whether the transactions would be accepted: a group is rejected as a whole
+    pub fn passes(&self) -> bool {
+        self.txs
+            .iter()
+            .all(|tx| tx.outcome != DryrunOutcome::Reject)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxDryrunReport {
+    pub tx_id: String,
+    pub outcome: DryrunOutcome,
+    /// Opcode cost of the evaluated program(s), if any
+    pub cost: Option<u64>,
+    pub logs: Vec<Vec<u8>>,
+    pub global_delta: Vec<StateChange>,
+    pub local_deltas: Vec<AccountStateChanges>,
+    /// The evaluator's messages (e.g. the reason for a rejection)
+    pub messages: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DryrunOutcome {
+    Pass,
+    Reject,
+    /// The tx doesn't execute a program (e.g. a payment signed with a key)
+    NotEvaluated,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateChange {
+    pub key: Vec<u8>,
+    pub change: StateValueChange,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateValueChange {
+    SetBytes(Vec<u8>),
+    SetUint(u64),
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountStateChanges {
+    pub address: Address,
+    pub changes: Vec<StateChange>,
+}
+
+fn to_report(signed_txs: &[SignedTransaction], res: &DryrunResponse) -> Result<DryrunReport> {
+    if signed_txs.len() != res.txns.len() {
+        return Err(anyhow!(
+            "Unexpected: dryrun returned {} results for {} txs",
+            res.txns.len(),
+            signed_txs.len()
+        ));
+    }
+
+    let txs = signed_txs
+        .iter()
+        .zip(res.txns.iter())
+        .map(|(tx, tx_res)| to_tx_report(tx, tx_res))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(DryrunReport { txs })
+}
+
+fn to_tx_report(tx: &SignedTransaction, res: &DryrunTxnResult) -> Result<TxDryrunReport> {
+    // a tx can run a logic sig and an app call - both are reported (logic sig first, as it's evaluated first)
+    let messages: Vec<String> = res
+        .logic_sig_messages
+        .iter()
+        .flatten()
+        .chain(res.app_call_messages.iter().flatten())
+        .cloned()
+        .collect();
+
+    Ok(TxDryrunReport {
+        tx_id: tx.transaction.id()?,
+        outcome: to_outcome(&messages),
+        cost: res.cost,
+        logs: res
+            .logs
+            .iter()
+            .flatten()
+            .map(|log| Ok(BASE64.decode(log.as_bytes())?))
+            .collect::<Result<_>>()?,
+        global_delta: to_state_changes(res.global_delta.iter().flatten())?,
+        local_deltas: res
+            .local_deltas
+            .iter()
+            .flatten()
+            .map(|delta| {
+                Ok(AccountStateChanges {
+                    address: delta.address.parse().map_err(Error::msg)?,
+                    changes: to_state_changes(delta.delta.iter())?,
+                })
+            })
+            .collect::<Result<_>>()?,
+        messages,
+    })
+}
+
+/// The evaluator adds "PASS" or "REJECT" to the messages of each evaluated program
+fn to_outcome(messages: &[String]) -> DryrunOutcome {
+    if messages.iter().any(|m| m == "REJECT") {
+        DryrunOutcome::Reject
+    } else if messages.iter().any(|m| m == "PASS") {
+        DryrunOutcome::Pass
+    } else {
+        DryrunOutcome::NotEvaluated
+    }
+}
+
+fn to_state_changes<'a>(
+    deltas: impl Iterator<Item = &'a EvalDeltaKeyValue>,
+) -> Result<Vec<StateChange>> {
+    deltas.map(to_state_change).collect()
+}
+
+/// See https://developer.algorand.org/docs/rest-apis/algod/v2/#evaldelta
+fn to_state_change(delta: &EvalDeltaKeyValue) -> Result<StateChange> {
+    let change = match delta.value.action {
+        1 => StateValueChange::SetBytes(
+            BASE64.decode(
+                delta
+                    .value
+                    .bytes
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("Set bytes delta without bytes: {delta:?}"))?
+                    .as_bytes(),
+            )?,
+        ),
+        2 => StateValueChange::SetUint(
+            delta
+                .value
+                .uint
+                .ok_or_else(|| anyhow!("Set uint delta without uint: {delta:?}"))?,
+        ),
+        3 => StateValueChange::Delete,
+        action => return Err(anyhow!("Unknown state delta action: {action}")),
+    };
+
+    Ok(StateChange {
+        key: BASE64.decode(delta.key.as_bytes())?,
+        change,
+    })
+}
+
+/// Full stack traces, for debugging
+fn log_traces(res: &DryrunResponse) {
+    if !log::log_enabled!(log::Level::Trace) {
+        return;
+    }
+
+    let config = StackPrinterConfig {
+        max_column_widths: MaxColumnWidths {
+            stack: 1000,
+            ..MaxColumnWidths::default()
+        },
+        top_of_stack_first: false,
+        bytes_format: BytesFormat::AddressOrHex,
+    };
+
+    for tx in &res.txns {
+        match app_trace_with_config(tx, &config) {
+            Ok(trace) => log::trace!("App trace:\n{trace}"),
+            Err(e) => log::trace!("Couldn't print app trace: {e:?}"),
+        }
+        match lsig_trace_with_config(tx, &config) {
+            Ok(trace) => log::trace!("Logic sig trace:\n{trace}"),
+            Err(e) => log::trace!("Couldn't print logic sig trace: {e:?}"),
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use super::{
+    app_arg_u64, payment_amount, tx_explanation, xfer_amount, Explain, ExplainedAmount, Explanation,
+};
+use crate::{
+    dev_settings::DevSettingsToSign,
+    flows::{
+        claim::claim::ClaimToSign, drain::drain::DrainToSign, invest::model::InvestToSign,
+        lock::lock::LockToSign, reclaim::reclaim::ReclaimToSign, rekey::rekey::RekeyToSign,
+        unlock::unlock::UnlockToSign, update_data::update_data::UpdateAppToSign,
+        withdraw::withdraw::WithdrawToSign,
+    },
+    team::SetTeamToSign,
+};
+use mbase::models::{funds::FundsAmount, share_amount::ShareAmount};
+
+impl Explain for InvestToSign {
+    fn explain(&self) -> Explanation {
+        let share_amount = app_arg_u64(&self.app_call, 1).map(ShareAmount::new);
+        Explanation::new(
+            format!("Invest in {}", self.dao.name),
+            vec![
+                (
+                    &self.shares_asset_optin_tx,
+                    tx_explanation(
+                        &self.shares_asset_optin_tx,
+                        format!("Opt in to the shares asset of {}", self.dao.name),
+                        None,
+                        0,
+                    ),
+                ),
+                (
+                    &self.app_call,
+                    tx_explanation(
+                        &self.app_call,
+                        "Buy the shares: they're locked in the DAO, to receive dividends",
+                        share_amount.map(ExplainedAmount::Shares),
+                        // shares xfer to the investor
+                        1,
+                    ),
+                ),
+                (
+                    &self.payment_tx,
+                    tx_explanation(
+                        &self.payment_tx,
+                        "Pay the shares price to the DAO",
+                        Some(ExplainedAmount::Funds(self.total_price)),
+                        0,
+                    ),
+                ),
+            ],
+        )
+    }
+}
+
+impl Explain for LockToSign {
+    fn explain(&self) -> Explanation {
+        Explanation::new(
+            "Lock shares, to receive dividends",
+            vec![
+                (
+                    &self.central_app_call_setup_tx,
+                    tx_explanation(
+                        &self.central_app_call_setup_tx,
+                        "Register the locked shares in the DAO",
+                        None,
+                        0,
+                    ),
+                ),
+                (
+                    &self.shares_xfer_tx,
+                    tx_explanation(
+                        &self.shares_xfer_tx,
+                        "Send the shares to the DAO",
+                        xfer_amount(&self.shares_xfer_tx)
+                            .map(|a| ExplainedAmount::Shares(ShareAmount::new(a))),
+                        0,
+                    ),
+                ),
+            ],
+        )
+    }
+}
+
+impl Explain for UnlockToSign {
+    fn explain(&self) -> Explanation {
+        Explanation::new(
+            "Unlock shares",
+            vec![(
+                &self.central_app_optout_tx,
+                tx_explanation(
+                    &self.central_app_optout_tx,
+                    "Get all your locked shares back. You stop receiving dividends",
+                    None,
+                    // shares xfer to the investor
+                    1,
+                ),
+            )],
+        )
+    }
+}
+
+impl Explain for ClaimToSign {
+    fn explain(&self) -> Explanation {
+        Explanation::new(
+            "Claim dividend",
+            vec![(
+                &self.app_call_tx,
+                tx_explanation(
+                    &self.app_call_tx,
+                    "Receive the dividend for your locked shares",
+                    None,
+                    // dividend xfer to the claimer
+                    1,
+                ),
+            )],
+        )
+    }
+}
+
+impl Explain for DrainToSign {
+    fn explain(&self) -> Explanation {
+        Explanation::new(
+            "Make the DAO's income available",
+            vec![(
+                &self.app_call_tx,
+                tx_explanation(
+                    &self.app_call_tx,
+                    "Make the funds received by the DAO available for dividends and withdrawals, paying the Capi fee",
+                    None,
+                    // capi fee xfer
+                    1,
+                ),
+            )],
+        )
+    }
+}
+
+impl Explain for WithdrawToSign {
+    fn explain(&self) -> Explanation {
+        Explanation::new(
+            "Withdraw funds from the DAO",
+            vec![(
+                &self.withdraw_tx,
+                tx_explanation(
+                    &self.withdraw_tx,
+                    "Withdraw funds from the DAO to your account",
+                    app_arg_u64(&self.withdraw_tx, 1)
+                        .map(|a| ExplainedAmount::Funds(FundsAmount::new(a))),
+                    // funds xfer to the owner
+                    1,
+                ),
+            )],
+        )
+    }
+}
+
+impl Explain for ReclaimToSign {
+    fn explain(&self) -> Explanation {
+        Explanation::new(
+            "Reclaim your investment",
+            vec![
+                (
+                    &self.app_call_tx,
+                    tx_explanation(
+                        &self.app_call_tx,
+                        "Get your investment back, as the DAO didn't reach its funding target",
+                        None,
+                        // funds xfer to the reclaimer
+                        1,
+                    ),
+                ),
+                (
+                    &self.shares_xfer_tx,
+                    tx_explanation(
+                        &self.shares_xfer_tx,
+                        "Return the shares to the DAO",
+                        xfer_amount(&self.shares_xfer_tx)
+                            .map(|a| ExplainedAmount::Shares(ShareAmount::new(a))),
+                        0,
+                    ),
+                ),
+            ],
+        )
+    }
+}
+
+impl Explain for RekeyToSign {
+    fn explain(&self) -> Explanation {
+        // the rekey itself is reported as warning
+        Explanation::new(
+            "Change the account that signs for your account",
+            vec![(
+                &self.tx,
+                tx_explanation(&self.tx, "Rekey your account", None, 0),
+            )],
+        )
+    }
+}
+
+impl Explain for UpdateAppToSign {
+    fn explain(&self) -> Explanation {
+        let mut txs = vec![];
+        if let Some(tx) = &self.increase_min_balance_tx {
+            txs.push((
+                tx,
+                tx_explanation(
+                    tx,
+                    "Increase the DAO's min balance, to hold the image NFT",
+                    payment_amount(tx).map(ExplainedAmount::Algos),
+                    0,
+                ),
+            ));
+        }
+        txs.push((
+            &self.update,
+            tx_explanation(
+                &self.update,
+                "Update the DAO's data",
+                None,
+                // possible image nft creation
+                1,
+            ),
+        ));
+        Explanation::new("Update the DAO's data", txs)
+    }
+}
+
+impl Explain for SetTeamToSign {
+    fn explain(&self) -> Explanation {
+        Explanation::new(
+            "Update the team",
+            vec![(
+                &self.app_call_tx,
+                tx_explanation(&self.app_call_tx, "Save the team's data", None, 0),
+            )],
+        )
+    }
+}
+
+impl Explain for DevSettingsToSign {
+    fn explain(&self) -> Explanation {
+        Explanation::new(
+            "Update the dev settings",
+            vec![(
+                &self.app_call_tx,
+                tx_explanation(&self.app_call_tx, "Save the dev settings", None, 0),
+            )],
+        )
+    }
+}
@@ -0,0 +1,134 @@
+use algonaut::{
+    core::{Address, MicroAlgos},
+    transaction::{transaction::TransactionType, Transaction},
+};
+use mbase::models::{funds::FundsAmount, share_amount::ShareAmount};
+use std::convert::TryInto;
+
+mod flows;
+mod tests;
+
+/// Implemented by the flows' `*ToSign` structs: what the transactions do, to be shown to the user before signing.
+pub trait Explain {
+    fn explain(&self) -> Explanation;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub summary: String,
+    /// In the order they're submitted
+    pub txs: Vec<TxExplanation>,
+    /// Sum of the txs fees, including the pooled fees of the inner txs
+    pub total_fee: MicroAlgos,
+    pub warnings: Vec<Warning>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxExplanation {
+    pub description: String,
+    /// What's sent by the user or, for app calls, what the user requests the app to send
+    pub amount: Option<ExplainedAmount>,
+    pub fee: MicroAlgos,
+    /// Inner txs (sent by the app) whose fees are paid with this tx's fee
+    pub pooled_inner_txs: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExplainedAmount {
+    Funds(FundsAmount),
+    Shares(ShareAmount),
+    Algos(MicroAlgos),
+}
+
+/// Effects that can't be undone by the user, detected in the transactions themselves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// From now on, only `auth` can sign for `account`
+    Rekey { account: Address, auth: Address },
+    /// The remaining balance (of the asset or algos) of `account` is sent to `to`
+    CloseTo { account: Address, to: Address },
+}
+
+impl Warning {
+    pub fn message(&self) -> String {
+        match self {
+            Warning::Rekey { account, auth } => format!(
+                "This rekeys your account {account}: only {auth} will be able to sign its transactions"
+            ),
+            Warning::CloseTo { account, to } => {
+                format!("This sends all the remaining balance of {account} to {to}")
+            }
+        }
+    }
+}
+
+impl Explanation {
+    /// `txs`: the transactions with their explanations, in submit order
+    fn new(summary: impl Into<String>, txs: Vec<(&Transaction, TxExplanation)>) -> Explanation {
+        let total_fee = MicroAlgos(txs.iter().map(|(_, e)| e.fee.0).sum());
+        let warnings = txs.iter().flat_map(|(tx, _)| warnings(tx)).collect();
+        Explanation {
+            summary: summary.into(),
+            txs: txs.into_iter().map(|(_, e)| e).collect(),
+            total_fee,
+            warnings,
+        }
+    }
+}
+
+fn tx_explanation(
+    tx: &Transaction,
+    description: impl Into<String>,
+    amount: Option<ExplainedAmount>,
+    pooled_inner_txs: u64,
+) -> TxExplanation {
+    TxExplanation {
+        description: description.into(),
+        amount,
+        fee: tx.fee,
+        pooled_inner_txs,
+    }
+}
+
+fn warnings(tx: &Transaction) -> Vec<Warning> {
+    let account = tx.sender();
+    let mut warnings = vec![];
+    if let Some(auth) = tx.rekey_to {
+        warnings.push(Warning::Rekey { account, auth });
+    }
+    let close_to = match &tx.txn_type {
+        TransactionType::Payment(p) => p.close_remainder_to,
+        TransactionType::AssetTransferTransaction(t) => t.close_to,
+        _ => None,
+    };
+    if let Some(to) = close_to {
+        warnings.push(Warning::CloseTo { account, to });
+    }
+    warnings
+}
+
+/// Amount of an asset transfer
+fn xfer_amount(tx: &Transaction) -> Option<u64> {
+    match &tx.txn_type {
+        TransactionType::AssetTransferTransaction(t) => Some(t.amount),
+        _ => None,
+    }
+}
+
+fn payment_amount(tx: &Transaction) -> Option<MicroAlgos> {
+    match &tx.txn_type {
+        TransactionType::Payment(p) => Some(p.amount),
+        _ => None,
+    }
+}
+
+/// App argument encoded as big endian u64
+fn app_arg_u64(tx: &Transaction, index: usize) -> Option<u64> {
+    match &tx.txn_type {
+        TransactionType::ApplicationCallTransaction(c) => {
+            let arg = c.app_arguments.as_ref()?.get(index)?;
+            Some(u64::from_be_bytes(arg.as_slice().try_into().ok()?))
+        }
+        _ => None,
+    }
+}
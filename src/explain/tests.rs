@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dao_algod::fake::FakeAlgod,
+        explain::{Explain, ExplainedAmount, Warning},
+        flows::{
+            claim::claim::claim,
+            lock::lock::lock,
+            rekey::rekey::rekey,
+            withdraw::withdraw::{withdraw, WithdrawalInputs},
+        },
+    };
+    use anyhow::Result;
+    use mbase::models::{
+        dao_app_id::DaoAppId,
+        funds::{FundsAmount, FundsAssetId},
+        share_amount::ShareAmount,
+    };
+    use network_test_util::test_data::{investor1, investor2};
+    use tokio::test;
+
+    #[test]
+    async fn test_rekey_explanation_warns() -> Result<()> {
+        let algod = FakeAlgod::new();
+        let account = investor1();
+        let auth = investor2();
+
+        let explanation = rekey(&algod, &account.address(), &auth.address())
+            .await?
+            .explain();
+
+        assert_eq!(
+            vec![Warning::Rekey {
+                account: account.address(),
+                auth: auth.address()
+            }],
+            explanation.warnings
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_claim_explanation_includes_pooled_fee() -> Result<()> {
+        let algod = FakeAlgod::new();
+        let claimer = investor1();
+
+        let explanation = claim(&algod, &claimer.address(), DaoAppId(123), FundsAssetId(456))
+            .await?
+            .explain();
+
+        assert_eq!(1, explanation.txs.len());
+        assert_eq!(1, explanation.txs[0].pooled_inner_txs);
+        assert_eq!(algod.params().min_fee * 2, explanation.total_fee);
+        assert!(explanation.warnings.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_withdraw_explanation_has_funds_amount() -> Result<()> {
+        let algod = FakeAlgod::new();
+        let owner = investor1();
+        let amount = FundsAmount::new(1_000_000);
+
+        let explanation = withdraw(
+            &algod,
+            owner.address(),
+            &WithdrawalInputs {
+                amount,
+                description: "Withdrawing from tests".to_owned(),
+            },
+            DaoAppId(123),
+            FundsAssetId(456),
+        )
+        .await?
+        .explain();
+
+        assert_eq!(
+            Some(ExplainedAmount::Funds(amount)),
+            explanation.txs[0].amount
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_lock_explanation_has_share_amount() -> Result<()> {
+        let algod = FakeAlgod::new();
+        let investor = investor1();
+        let share_amount = ShareAmount::new(10);
+
+        let explanation = lock(&algod, investor.address(), share_amount, 456, DaoAppId(123))
+            .await?
+            .explain();
+
+        assert_eq!(2, explanation.txs.len());
+        assert_eq!(None, explanation.txs[0].amount);
+        assert_eq!(
+            Some(ExplainedAmount::Shares(share_amount)),
+            explanation.txs[1].amount
+        );
+        assert_eq!(algod.params().min_fee * 2, explanation.total_fee);
+
+        Ok(())
+    }
+}
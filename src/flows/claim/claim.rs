@@ -1,6 +1,11 @@
+use crate::{
+    capi_error::broadcast_app_txs,
+    dao_algod::DaoAlgod,
+    signer::{Sign, SignedTxs, TxSigner},
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
-    core::{Address, MicroAlgos, SuggestedTransactionParams},
+    core::{Address, SuggestedTransactionParams},
     transaction::{builder::CallApplication, SignedTransaction, Transaction, TxnBuilder},
 };
 use anyhow::{anyhow, Result};
@@ -10,18 +15,14 @@ use mbase::{
         funds::{FundsAmount, FundsAssetId},
         share_amount::ShareAmount,
         shares_percentage::SharesPercentage,
-        tx_id::TxId,
     },
     util::decimal_util::AsDecimal,
 };
 use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
 
-// TODO no constants
-pub const MIN_BALANCE: MicroAlgos = MicroAlgos(100_000);
-
 pub async fn claim(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     claimer: &Address,
     app_id: DaoAppId,
     funds_asset: FundsAssetId,
@@ -58,18 +59,17 @@ pub fn claim_app_call_tx(
     Ok(tx)
 }
 
-pub async fn submit_claim(algod: &Algod, signed: &ClaimSigned) -> Result<TxId> {
+pub async fn submit_claim(algod: &dyn DaoAlgod, signed: &ClaimSigned) -> Result<Submission> {
     log::debug!("Submit claim..");
     // crate::debug_msg_pack_submit_par::log_to_msg_pack(&signed);
 
-    let txs = vec![signed.app_call_tx_signed.clone()];
+    let txs = signed.signed_txs();
 
-    // crate::dryrun_util::dryrun_all(algod, &txs).await?;
     // mbase::teal::debug_teal_rendered(&txs, "dao_app_approval").unwrap();
 
-    let res = algod.broadcast_signed_transactions(&txs).await?;
+    let res = broadcast_app_txs(algod, &txs).await?;
     log::debug!("Claim tx id: {:?}", res.tx_id);
-    res.tx_id.parse()
+    Submission::from_response(&res)
 }
 
 /// The amount the investor is entitled to claim, based on locked shares, the investor's % defined by the dao,
@@ -178,3 +178,19 @@ pub struct ClaimToSign {
 pub struct ClaimSigned {
     pub app_call_tx_signed: SignedTransaction,
 }
+
+impl Sign for ClaimToSign {
+    type Signed = ClaimSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<ClaimSigned> {
+        Ok(ClaimSigned {
+            app_call_tx_signed: signer.sign_tx(self.app_call_tx)?,
+        })
+    }
+}
+
+impl SignedTxs for ClaimSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.app_call_tx_signed.clone()]
+    }
+}
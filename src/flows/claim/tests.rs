@@ -1,11 +1,18 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        flows::claim::claim::claimable_dividend,
+        dao_algod::fake::FakeAlgod,
+        dryrun::{dryrun, DryrunOutcome},
+        flows::{
+            claim::claim::{claim, claimable_dividend},
+            create_dao::shares_config::{shares_to_base_units, SharesAssetConfig},
+        },
+        signer::Sign,
         state::account_state::funds_holdings,
         testing::{
             flow::{
-                claim_flow::{claim_flow, claim_precs},
+                claim_flow::{claim_flow, claim_precs, test::claim_precs_with_dao},
+                create_dao_flow::test::create_dao_flow_with_shares_config,
                 invest_in_dao_flow::invests_flow,
             },
             network_test_util::{test_dao_init, TestDeps},
@@ -29,6 +36,7 @@ mod tests {
             CentralAppGlobalState,
         },
     };
+    use network_test_util::test_data::investor1;
     use rust_decimal::Decimal;
     use serial_test::serial;
     use std::{convert::TryInto, str::FromStr};
@@ -94,6 +102,70 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[serial]
+    async fn test_claim_max_with_shares_decimals() -> Result<()> {
+        let td = test_dao_init().await?;
+        let algod = &td.algod;
+
+        let drainer = &td.investor1;
+        let claimer = &td.investor2;
+
+        let config = SharesAssetConfig {
+            decimals: 2,
+            ..SharesAssetConfig::default()
+        };
+        let dao = create_dao_flow_with_shares_config(&td, &config).await?;
+
+        // flow
+
+        // the dividend is calculated with base units, which are the share amounts in the app
+        let buy_share_amount = shares_to_base_units(Decimal::from_str("0.1")?, config.decimals)?;
+        let pay_and_drain_amount = FundsAmount::new(10_000_000);
+
+        let precs = claim_precs_with_dao(
+            &td,
+            &dao,
+            buy_share_amount,
+            pay_and_drain_amount,
+            drainer,
+            claimer,
+        )
+        .await?;
+
+        let central_state_before_claim = dao_global_state(&algod, dao.app_id).await?;
+        let res = claim_flow(&td, &dao, claimer).await?;
+
+        // test
+
+        let dividend = claimable_dividend(
+            precs.drain_res.drained_amounts.dao,
+            FundsAmount::new(0),
+            td.specs.shares.supply,
+            buy_share_amount,
+            td.precision,
+            td.specs.investors_share,
+        )?;
+        assert!(dividend.val() > 0);
+
+        test_claim_result(
+            &algod,
+            &claimer,
+            dao.app_id,
+            td.funds_asset_id,
+            precs.drain_res.drained_amounts.dao,
+            res.claimer_balance_before_claiming.add(&dividend).unwrap(),
+            precs.app_balance_after_drain.sub(&dividend).unwrap(),
+            buy_share_amount,
+            dividend,
+            FundsAmount::new(0),
+            &central_state_before_claim,
+        )
+        .await?;
+
+        Ok(())
+    }
+
     #[test]
     #[serial]
     async fn test_claim_max_with_repeated_fractional_shares_percentage() -> Result<()> {
@@ -418,5 +490,75 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[serial]
+    async fn test_claim_dryrun_reports_without_submitting() -> Result<()> {
+        let td = test_dao_init().await?;
+        let algod = &td.algod;
+
+        let drainer = &td.investor1;
+        let claimer = &td.investor2;
+
+        // precs
+
+        let precs = claim_precs(
+            &td,
+            ShareAmount::new(10),
+            FundsAmount::new(10_000_000),
+            drainer,
+            claimer,
+        )
+        .await?;
+        let claimer_balance_before_dryrun =
+            funds_holdings(algod, &claimer.address(), td.funds_asset_id).await?;
+
+        // flow
+
+        let signed = claim(
+            algod,
+            &claimer.address(),
+            precs.dao.app_id,
+            td.funds_asset_id,
+        )
+        .await?
+        .sign(claimer)?;
+        let report = dryrun(algod, &signed).await?;
+
+        // test
+
+        assert!(report.passes());
+        assert_eq!(1, report.txs.len());
+        let app_call_report = &report.txs[0];
+        assert_eq!(DryrunOutcome::Pass, app_call_report.outcome);
+        assert!(app_call_report.cost.is_some());
+        // the claimed amount is updated in the claimer's local state
+        assert!(app_call_report
+            .local_deltas
+            .iter()
+            .any(|d| d.address == claimer.address()));
+
+        // nothing was submitted
+        let claimer_balance_after_dryrun =
+            funds_holdings(algod, &claimer.address(), td.funds_asset_id).await?;
+        assert_eq!(claimer_balance_before_dryrun, claimer_balance_after_dryrun);
+
+        Ok(())
+    }
+
     // TODO test: can't claim not available amount
+
+    #[test]
+    async fn test_claim_tx_pays_dividend_xfer_fee() -> Result<()> {
+        // no network needed: we only check the generated tx
+        let algod = FakeAlgod::new();
+        let claimer = investor1().address();
+
+        let to_sign = claim(&algod, &claimer, DaoAppId(123), FundsAssetId(456)).await?;
+
+        // app call + inner dividend xfer
+        assert_eq!(algod.params().min_fee * 2, to_sign.app_call_tx.fee);
+        assert_eq!(claimer, to_sign.app_call_tx.sender());
+
+        Ok(())
+    }
 }
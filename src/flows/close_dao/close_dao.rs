@@ -0,0 +1,374 @@
+use super::note::{
+    base64_is_closing_note, base64_maybe_closure_note_to_dao, closing_to_note, closure_to_note,
+};
+use crate::{
+    dao_algod::DaoAlgod,
+    dao_indexer::DaoIndexer,
+    flows::{
+        create_dao::model::Dao,
+        drain::drain::{drain_app_call_tx, to_drain_amounts},
+        update_data::update_data::{
+            update_data, UpdatableDaoData, UpdateAppToSign, UPDATE_DATA_APP_ARG,
+        },
+        withdraw::withdraw::{withdraw, WithdrawalInputs},
+    },
+    signer::{Sign, SignedTxs, TxSigner},
+    state::dao_app_state::dao_global_state,
+    submission::Submission,
+};
+use algonaut::{
+    core::Address,
+    model::indexer::v2::{self, OnCompletion, QueryTransaction},
+    transaction::{
+        builder::DeleteApplication, tx_group::TxGroup, SignedTransaction, Transaction, TxnBuilder,
+    },
+};
+use anyhow::{anyhow, Error, Result};
+use data_encoding::BASE64;
+use mbase::models::{
+    capi_deps::CapiAssetDaoDeps, dao_app_id::DaoAppId, dao_id::DaoId, funds::FundsAmount,
+    share_amount::ShareAmount,
+};
+use serde::{Deserialize, Serialize};
+
+/// Description of the withdrawal of the remaining funds, when closing
+pub const CLOSURE_WITHDRAWAL_DESCRIPTION: &str = "Closing the DAO";
+
+/// First step to close the DAO: stops new investments, setting the max investment to 0 (the rest of the data stays as it is).
+/// The update's note marks the DAO as closing (see [is_closing]).
+/// Investors can still unlock their shares. Until the DAO is closed, the owner can allow investments again, updating the data.
+/// Submit with [crate::flows::update_data::update_data::submit_update_data].
+pub async fn stop_investments(
+    algod: &dyn DaoAlgod,
+    owner: &Address,
+    dao: &Dao,
+) -> Result<UpdateAppToSign> {
+    let mut to_sign = update_data(
+        algod,
+        owner,
+        dao.app_id,
+        &UpdatableDaoData {
+            project_name: dao.name.clone(),
+            project_desc_url: dao.descr_url.clone(),
+            // no image url: keeps the current image nft
+            image_url: None,
+            image_nft_metadata: None,
+            social_media_url: dao.social_media_url.clone(),
+            prospectus: dao.prospectus.clone(),
+            min_invest_shares: ShareAmount::new(0),
+            max_invest_shares: ShareAmount::new(0),
+        },
+    )
+    .await?;
+    // without image url the update isn't grouped: the note can be set after building it
+    to_sign.update.note = Some(closing_to_note(dao.id()));
+    Ok(to_sign)
+}
+
+/// Whether the owner's last data update is the one stopping the investments to close the DAO (see [stop_investments]).
+/// Other updates, e.g. allowing the investments again, end the closing.
+pub async fn is_closing(indexer: &dyn DaoIndexer, dao: &Dao) -> Result<bool> {
+    let closings = owner_data_updates(
+        indexer,
+        dao,
+        &QueryTransaction {
+            application_id: Some(dao.app_id.0),
+            note_prefix: Some(BASE64.encode(&closing_to_note(dao.id()))),
+            ..QueryTransaction::default()
+        },
+    )
+    .await?;
+    let last_closing_round = match closings.iter().filter_map(|tx| tx.confirmed_round).max() {
+        Some(round) => round,
+        None => return Ok(false),
+    };
+
+    // only the updates since the last closing (included) can end it
+    let updates = owner_data_updates(
+        indexer,
+        dao,
+        &QueryTransaction {
+            application_id: Some(dao.app_id.0),
+            min_round: Some(last_closing_round),
+            ..QueryTransaction::default()
+        },
+    )
+    .await?;
+    let last_update = updates.iter().max_by_key(|tx| tx.confirmed_round);
+
+    match last_update.and_then(|tx| tx.note.as_ref()) {
+        Some(note) => base64_is_closing_note(note, dao.id()),
+        None => Ok(false),
+    }
+}
+
+/// The owner's data updates of the DAO, of the app txs the query returns
+async fn owner_data_updates(
+    indexer: &dyn DaoIndexer,
+    dao: &Dao,
+    query: &QueryTransaction,
+) -> Result<Vec<v2::Transaction>> {
+    let owner = dao.owner.to_string();
+    let update_data_arg = BASE64.encode(UPDATE_DATA_APP_ARG.as_bytes());
+    Ok(indexer
+        .transactions(query)
+        .await?
+        .into_iter()
+        .filter(|tx| {
+            tx.sender == owner
+                && tx
+                    .application_transaction
+                    .as_ref()
+                    .map(|app_tx| {
+                        app_tx.application_id == dao.app_id.0
+                            && app_tx.application_args.first() == Some(&update_data_arg)
+                    })
+                    .unwrap_or(false)
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClosingReadiness {
+    pub investments_stopped: bool,
+    /// Shares the investors still have to unlock
+    pub locked_shares: ShareAmount,
+    /// Assets the owner has to opt in to, to receive the app's remaining funds and shares
+    pub owner_missing_optins: Vec<u64>,
+    /// Drained when closing
+    pub not_yet_drained: FundsAmount,
+    /// Withdrawn to the owner when closing (incl. the drained part)
+    pub to_withdraw: FundsAmount,
+}
+
+impl ClosingReadiness {
+    pub fn can_close(&self) -> bool {
+        self.investments_stopped
+            && self.locked_shares.val() == 0
+            && self.owner_missing_optins.is_empty()
+    }
+}
+
+/// What's missing to close the DAO (with [close_dao]) and what closing will move
+pub async fn closing_readiness(
+    algod: &dyn DaoAlgod,
+    dao: &Dao,
+    capi_deps: &CapiAssetDaoDeps,
+) -> Result<ClosingReadiness> {
+    let state = dao_global_state(algod, dao.app_id).await?;
+    let amounts = to_drain_amounts(
+        algod,
+        capi_deps.escrow_percentage,
+        dao.funds_asset_id,
+        dao.app_id,
+    )
+    .await?;
+
+    let owner_infos = algod.account_information(&state.owner).await?;
+    let owner_missing_optins = [dao.funds_asset_id.0, dao.shares_asset_id]
+        .iter()
+        .filter(|asset_id| !owner_infos.assets.iter().any(|h| h.asset_id == **asset_id))
+        .cloned()
+        .collect();
+
+    Ok(ClosingReadiness {
+        investments_stopped: state.max_invest_amount.val() == 0,
+        locked_shares: state.locked_shares,
+        owner_missing_optins,
+        not_yet_drained: FundsAmount::new(amounts.dao.val() + amounts.capi.val()),
+        to_withdraw: FundsAmount::new(state.available.val() + amounts.dao.val()),
+    })
+}
+
+/// Last step to close the DAO, after stopping the investments (see [stop_investments]) and the investors unlocking their shares:
+/// drains the not yet drained funds, withdraws all the funds to the owner and deletes the app.
+/// When deleted, the app closes out its assets (the remaining shares go to the owner, the image nft is destroyed)
+/// and sends its ALGO, incl. the min balance, to the owner.
+///
+/// `dao` should be freshly loaded: it's saved in the deletion's note, to show the DAO after closing (see [load_closed_dao]).
+pub async fn close_dao(
+    algod: &dyn DaoAlgod,
+    owner: &Address,
+    dao: &Dao,
+    capi_deps: &CapiAssetDaoDeps,
+) -> Result<CloseDaoToSign> {
+    if &dao.owner != owner {
+        return Err(anyhow!(
+            "{owner} isn't the owner of the dao: {:?}",
+            dao.id()
+        ));
+    }
+    let readiness = closing_readiness(algod, dao, capi_deps).await?;
+    if !readiness.can_close() {
+        return Err(anyhow!("The dao can't be closed yet: {readiness:?}"));
+    }
+
+    let params = algod.suggested_transaction_params().await?;
+
+    let mut drain_tx = if readiness.not_yet_drained.val() > 0 {
+        let mut tx = drain_app_call_tx(
+            dao.app_id,
+            &params,
+            owner,
+            &capi_deps.address,
+            dao.funds_asset_id,
+        )?;
+        // pay for the capi fee inner tx
+        tx.fee = tx.fee * 2;
+        Some(tx)
+    } else {
+        None
+    };
+
+    let mut withdraw_tx = if readiness.to_withdraw.val() > 0 {
+        let inputs = WithdrawalInputs {
+            amount: readiness.to_withdraw,
+            description: CLOSURE_WITHDRAWAL_DESCRIPTION.to_owned(),
+        };
+        Some(
+            withdraw(algod, *owner, &inputs, dao.app_id, dao.funds_asset_id)
+                .await?
+                .withdraw_tx,
+        )
+    } else {
+        None
+    };
+
+    let mut assets = vec![dao.funds_asset_id.0, dao.shares_asset_id];
+    if let Some(nft) = &dao.image_nft {
+        assets.push(nft.asset_id);
+    }
+    let mut delete_tx = TxnBuilder::with(
+        &params,
+        DeleteApplication::new(*owner, dao.app_id.0)
+            .foreign_assets(assets.clone())
+            .build(),
+    )
+    .note(closure_to_note(dao)?)
+    .build()?;
+    // pay for the inner txs: closing out each asset (destroying the nft) and closing the ALGO account
+    delete_tx.fee = delete_tx.fee * (assets.len() as u64 + 2);
+
+    let mut txs_for_group = vec![];
+    if let Some(tx) = drain_tx.as_mut() {
+        txs_for_group.push(tx);
+    }
+    if let Some(tx) = withdraw_tx.as_mut() {
+        txs_for_group.push(tx);
+    }
+    txs_for_group.push(&mut delete_tx);
+    TxGroup::assign_group_id(&mut txs_for_group)?;
+
+    Ok(CloseDaoToSign {
+        drain_tx,
+        withdraw_tx,
+        delete_tx,
+    })
+}
+
+pub async fn submit_close_dao(algod: &dyn DaoAlgod, signed: &CloseDaoSigned) -> Result<Submission> {
+    log::debug!("calling submit close dao..");
+
+    let txs = signed.signed_txs();
+
+    let res = algod.broadcast_signed_transactions(&txs).await?;
+    log::debug!("Close dao tx id: {:?}", res.tx_id);
+    Submission::from_response(&res)
+}
+
+/// The DAO as it was when closed, `None` if it wasn't closed.
+/// The app's inner txs send from its address, so the deletion is found with the address's txs.
+pub async fn load_closed_dao(indexer: &dyn DaoIndexer, dao_id: DaoId) -> Result<Option<Dao>> {
+    let txs = indexer
+        .transactions(&QueryTransaction {
+            address: Some(dao_id.0.address().to_string()),
+            ..QueryTransaction::default()
+        })
+        .await?;
+
+    for tx in txs {
+        match to_closed_dao(&tx) {
+            Ok(Some(dao)) => {
+                if dao.id() == dao_id {
+                    return Ok(Some(dao));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => log::debug!("Ignoring invalid dao closure: {e:?}, tx: {tx:?}"),
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the DAO saved in the tx, if the tx deleted a DAO's app.
+/// Indexer txs are confirmed: the app accepted the deletion, so the sender was the owner,
+/// also if the saved data was loaded before an ownership transfer.
+pub(crate) fn to_closed_dao(tx: &v2::Transaction) -> Result<Option<Dao>> {
+    let app_tx = match &tx.application_transaction {
+        Some(app_tx) if app_tx.on_completion == OnCompletion::Delete => app_tx,
+        _ => return Ok(None),
+    };
+    let note = match &tx.note {
+        Some(note) => note,
+        // e.g. a non-Capi app
+        None => return Ok(None),
+    };
+
+    let dao_id = DaoId(DaoAppId(app_tx.application_id));
+    match base64_maybe_closure_note_to_dao(note, dao_id)? {
+        Some(dao) => {
+            let sender = tx.sender.parse::<Address>().map_err(Error::msg)?;
+            if dao.owner != sender {
+                log::debug!(
+                    "Closure of dao: {dao_id:?} sent by: {sender}, saved owner: {}. Taking the sender as owner.",
+                    dao.owner
+                );
+            }
+            Ok(Some(Dao {
+                owner: sender,
+                ..dao
+            }))
+        }
+        None => Ok(None),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloseDaoToSign {
+    pub drain_tx: Option<Transaction>,
+    pub withdraw_tx: Option<Transaction>,
+    pub delete_tx: Transaction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CloseDaoSigned {
+    pub drain_tx: Option<SignedTransaction>,
+    pub withdraw_tx: Option<SignedTransaction>,
+    pub delete_tx: SignedTransaction,
+}
+
+impl Sign for CloseDaoToSign {
+    type Signed = CloseDaoSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<CloseDaoSigned> {
+        Ok(CloseDaoSigned {
+            drain_tx: self.drain_tx.map(|tx| signer.sign_tx(tx)).transpose()?,
+            withdraw_tx: self.withdraw_tx.map(|tx| signer.sign_tx(tx)).transpose()?,
+            delete_tx: signer.sign_tx(self.delete_tx)?,
+        })
+    }
+}
+
+impl SignedTxs for CloseDaoSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        let mut txs = vec![];
+        if let Some(tx) = &self.drain_tx {
+            txs.push(tx.clone());
+        }
+        if let Some(tx) = &self.withdraw_tx {
+            txs.push(tx.clone());
+        }
+        txs.push(self.delete_tx.clone());
+        txs
+    }
+}
@@ -0,0 +1,7 @@
+//! Winding down a DAO: [close_dao::stop_investments], the investors unlock their shares, then [close_dao::close_dao]
+//! drains, withdraws the remaining funds to the owner and deletes the app.
+
+#[allow(clippy::module_inception)]
+pub mod close_dao;
+mod note;
+mod tests;
@@ -0,0 +1,106 @@
+use crate::{
+    flows::create_dao::{
+        model::{Dao, DaoStatus},
+        validate_specs::MAX_NOTE_LEN,
+    },
+    note::capi_note_prefix,
+};
+use anyhow::{anyhow, Result};
+use data_encoding::BASE64;
+use mbase::models::dao_id::DaoId;
+use std::convert::TryInto;
+
+fn closure_note_identifier() -> [u8; 4] {
+    // utf-8 encoding of "clos"
+    [99, 108, 111, 115]
+}
+
+fn closing_note_identifier() -> [u8; 4] {
+    // utf-8 encoding of "clsg"
+    [99, 108, 115, 103]
+}
+
+/// Note of the data update that stops the investments: marks the DAO as closing (see [super::close_dao::is_closing]).
+pub fn closing_to_note(dao_id: DaoId) -> Vec<u8> {
+    let version_bytes = u16::to_be_bytes(1);
+    [
+        capi_note_prefix().as_slice(),
+        &closing_note_identifier(),
+        &version_bytes,
+        &dao_id.bytes(),
+    ]
+    .concat()
+}
+
+/// Whether the note is the closing note of the dao
+pub fn base64_is_closing_note(note: &str, dao_id: DaoId) -> Result<bool> {
+    let bytes = BASE64.decode(note.as_bytes())?;
+    Ok(bytes == closing_to_note(dao_id))
+}
+
+/// Note of the app deletion: the DAO's data, as it was when closed, so it can still be shown after the app (and its state) is gone.
+pub fn closure_to_note(dao: &Dao) -> Result<Vec<u8>> {
+    let snapshot = Dao {
+        status: DaoStatus::Closed,
+        ..dao.clone()
+    };
+    // not named: the field names would take a big part of the note
+    let serialized = rmp_serde::to_vec(&snapshot)?;
+    let version_bytes = u16::to_be_bytes(1);
+    let note = [
+        capi_note_prefix().as_slice(),
+        &closure_note_identifier(),
+        &version_bytes,
+        &dao.id().bytes(),
+        &serialized,
+    ]
+    .concat();
+
+    if note.len() > MAX_NOTE_LEN {
+        return Err(anyhow!(
+            "The dao's data: {} bytes doesn't fit in the closure note: max {MAX_NOTE_LEN} bytes. Shorten the urls (update the data) before closing.",
+            note.len()
+        ));
+    }
+    Ok(note)
+}
+
+/// Parses a possible closure note.
+/// If the note is not a closure note or belongs to a different dao, it returns Ok(None).
+pub fn base64_maybe_closure_note_to_dao(note: &str, dao_id: DaoId) -> Result<Option<Dao>> {
+    let bytes = BASE64.decode(note.as_bytes())?;
+    maybe_closure_note_to_dao(&bytes, dao_id)
+}
+
+fn maybe_closure_note_to_dao(note: &[u8], dao_id: DaoId) -> Result<Option<Dao>> {
+    if note.get(0..4) != Some(capi_note_prefix().as_slice())
+        || note.get(4..8) != Some(closure_note_identifier().as_slice())
+    {
+        return Ok(None);
+    }
+
+    let version_bytes = note
+        .get(8..10)
+        .ok_or_else(|| anyhow!("Not enough bytes in note to get version. Note: {note:?}"))?;
+    let version = u16::from_be_bytes(version_bytes.try_into()?);
+    if version != 1 {
+        return Err(anyhow!("Not supported closure version in note: {version}"));
+    }
+
+    let dao_id_bytes = note
+        .get(10..18)
+        .ok_or_else(|| anyhow!("Not enough bytes in note to get dao id. Note: {note:?}"))?;
+    let note_dao_id: DaoId = dao_id_bytes.try_into()?;
+    if note_dao_id != dao_id {
+        return Ok(None);
+    }
+
+    let dao = rmp_serde::from_slice::<Dao>(&note[18..])?;
+    // Sanity check, like for the roadmap items
+    if dao.id() != dao_id {
+        return Err(anyhow!(
+            "Invalid state: dao id in prefix doesn't match the payload dao id."
+        ));
+    }
+    Ok(Some(dao))
+}
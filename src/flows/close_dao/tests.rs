@@ -0,0 +1,469 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dao_algod::{fake::FakeAlgod, is_app_not_found, DaoAlgod},
+        dao_indexer::fake::FakeIndexer,
+        flows::{
+            close_dao::{
+                close_dao::{
+                    close_dao, closing_readiness, is_closing, load_closed_dao, stop_investments,
+                    submit_close_dao,
+                },
+                note::{
+                    base64_is_closing_note, base64_maybe_closure_note_to_dao, closing_to_note,
+                    closure_to_note,
+                },
+            },
+            create_dao::{
+                model::{Dao, DaoStatus},
+                storage::load_dao::{load_dao, load_dao_or_closed},
+            },
+            pay_dao::pay_dao::{pay_dao_app, submit_pay_dao},
+            update_data::update_data::submit_update_data,
+        },
+        signer::Sign,
+        state::account_state::funds_holdings,
+        testing::{
+            fixtures::{dao_fixture, FIXTURE_APP_ID},
+            flow::{
+                create_dao_flow::create_dao_flow,
+                invest_in_dao_flow::{invests_flow, invests_optins_flow},
+                unlock_flow::unlock_flow,
+            },
+            network_test_util::test_dao_init,
+        },
+    };
+    use anyhow::{anyhow, Error, Result};
+    use data_encoding::BASE64;
+    use mbase::{
+        models::{
+            dao_app_id::DaoAppId, dao_id::DaoId, funds::FundsAmount, share_amount::ShareAmount,
+        },
+        util::network_util::wait_for_pending_transaction,
+    };
+    use serial_test::serial;
+    use tokio::test;
+
+    const OWNER: &str = "FTPBN666KYZVB5YYYLRZ6GXWBKWLJJSXQ3N753USSWS2WIAYK7WJTTYRPI";
+    const OTHER: &str = "2QFRITR4DMZCISHVXLQROMMSY6AE4L3GUEWHURMU7PX4BUGK5JMXBO4RP4";
+
+    /// A DAO whose investments were stopped
+    fn dao(owner: &str) -> Result<Dao> {
+        Ok(Dao {
+            max_invest_amount: ShareAmount::new(0),
+            status: DaoStatus::Closing,
+            ..dao_fixture(owner.parse().map_err(Error::msg)?)?
+        })
+    }
+
+    /// The app deletion (indexer format), with the app closing its ALGO to the sender
+    fn delete_app_json(sender: &str, note: Option<String>) -> String {
+        let app_address = DaoAppId(FIXTURE_APP_ID).address();
+        let note = note
+            .map(|n| format!(r#""note": "{n}","#))
+            .unwrap_or_default();
+        format!(
+            r#"{{
+            "application-transaction": {{
+                "accounts": [],
+                "application-args": [],
+                "application-id": {FIXTURE_APP_ID},
+                "foreign-apps": [],
+                "foreign-assets": [],
+                "global-state-schema": {{ "num-byte-slice": 0, "num-uint": 0 }},
+                "local-state-schema": {{ "num-byte-slice": 0, "num-uint": 0 }},
+                "on-completion": "delete"
+            }},
+            "close-rewards": 0,
+            "closing-amount": 0,
+            "confirmed-round": 10,
+            "fee": 5000,
+            "first-valid": 1,
+            "genesis-hash": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+            "genesis-id": "sandnet-v1",
+            "id": "ZKLYCEWKDO64V6WCGGZZUI64JWTYN37YCR6E44VZQB3YLL7OJC5Q",
+            "inner-txns": [{{
+                "payment-transaction": {{ "amount": 0, "close-amount": 300000, "close-remainder-to": "{sender}", "receiver": "{sender}" }},
+                "close-rewards": 0,
+                "closing-amount": 300000,
+                "confirmed-round": 10,
+                "fee": 0,
+                "first-valid": 1,
+                "intra-round-offset": 0,
+                "last-valid": 1001,
+                "receiver-rewards": 0,
+                "round-time": 1650000010,
+                "sender": "{app_address}",
+                "sender-rewards": 0,
+                "tx-type": "pay"
+            }}],
+            "intra-round-offset": 0,
+            "last-valid": 1001,
+            {note}
+            "receiver-rewards": 0,
+            "round-time": 1650000010,
+            "sender": "{sender}",
+            "sender-rewards": 0,
+            "signature": {{}},
+            "tx-type": "appl"
+        }}"#
+        )
+    }
+
+    /// A data update of the fixture dao's app (indexer format)
+    fn update_data_json(id: &str, sender: &str, round: u64, note: Option<String>) -> String {
+        app_update_data_json(id, sender, FIXTURE_APP_ID, round, note)
+    }
+
+    /// A data update of the app (indexer format)
+    fn app_update_data_json(
+        id: &str,
+        sender: &str,
+        app_id: u64,
+        round: u64,
+        note: Option<String>,
+    ) -> String {
+        let update_data_arg = BASE64.encode(b"update_data");
+        let note = note
+            .map(|n| format!(r#""note": "{n}","#))
+            .unwrap_or_default();
+        format!(
+            r#"{{
+            "application-transaction": {{
+                "accounts": [],
+                "application-args": ["{update_data_arg}"],
+                "application-id": {app_id},
+                "foreign-apps": [],
+                "foreign-assets": [],
+                "global-state-schema": {{ "num-byte-slice": 0, "num-uint": 0 }},
+                "local-state-schema": {{ "num-byte-slice": 0, "num-uint": 0 }},
+                "on-completion": "noop"
+            }},
+            "close-rewards": 0,
+            "closing-amount": 0,
+            "confirmed-round": {round},
+            "fee": 2000,
+            "first-valid": 1,
+            "genesis-hash": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+            "genesis-id": "sandnet-v1",
+            "id": "{id}",
+            "intra-round-offset": 0,
+            "last-valid": 1001,
+            {note}
+            "receiver-rewards": 0,
+            "round-time": 1650000010,
+            "sender": "{sender}",
+            "sender-rewards": 0,
+            "signature": {{}},
+            "tx-type": "appl"
+        }}"#
+        )
+    }
+
+    #[test]
+    async fn test_closing_note() -> Result<()> {
+        let dao = dao(OWNER)?;
+        let note = BASE64.encode(&closing_to_note(dao.id()));
+
+        assert!(base64_is_closing_note(&note, dao.id())?);
+        // closing of another dao
+        assert!(!base64_is_closing_note(
+            &note,
+            DaoId(DaoAppId(FIXTURE_APP_ID + 1))
+        )?);
+        // the closure (deletion) note isn't a closing note
+        let closure_note = BASE64.encode(&closure_to_note(&dao)?);
+        assert!(!base64_is_closing_note(&closure_note, dao.id())?);
+        Ok(())
+    }
+
+    #[test]
+    async fn test_is_closing() -> Result<()> {
+        let dao = dao(OWNER)?;
+        let closing_note = BASE64.encode(&closing_to_note(dao.id()));
+
+        // investments stopped to close
+        let indexer = FakeIndexer::new().with_transactions_json(&format!(
+            "[{}, {}]",
+            update_data_json("TX1", OWNER, 10, None),
+            update_data_json("TX2", OWNER, 11, Some(closing_note.clone())),
+        ))?;
+        assert!(is_closing(&indexer, &dao).await?);
+
+        // allowed again after stopping them
+        let indexer = FakeIndexer::new().with_transactions_json(&format!(
+            "[{}, {}]",
+            update_data_json("TX1", OWNER, 10, Some(closing_note.clone())),
+            update_data_json("TX2", OWNER, 11, None),
+        ))?;
+        assert!(!is_closing(&indexer, &dao).await?);
+
+        // max investment set to 0 without closing
+        let indexer = FakeIndexer::new()
+            .with_transactions_json(&format!("[{}]", update_data_json("TX1", OWNER, 10, None)))?;
+        assert!(!is_closing(&indexer, &dao).await?);
+
+        // only the owner can update the data
+        let indexer = FakeIndexer::new().with_transactions_json(&format!(
+            "[{}]",
+            update_data_json("TX1", OTHER, 10, Some(closing_note.clone()))
+        ))?;
+        assert!(!is_closing(&indexer, &dao).await?);
+
+        // the updates of other apps don't end the closing
+        let indexer = FakeIndexer::new().with_transactions_json(&format!(
+            "[{}, {}]",
+            update_data_json("TX1", OWNER, 10, Some(closing_note.clone())),
+            app_update_data_json("TX2", OWNER, FIXTURE_APP_ID + 1, 11, None),
+        ))?;
+        assert!(is_closing(&indexer, &dao).await?);
+
+        // closed again after allowing the investments
+        let indexer = FakeIndexer::new().with_transactions_json(&format!(
+            "[{}, {}, {}]",
+            update_data_json("TX1", OWNER, 10, Some(closing_note.clone())),
+            update_data_json("TX2", OWNER, 11, None),
+            update_data_json("TX3", OWNER, 12, Some(closing_note)),
+        ))?;
+        assert!(is_closing(&indexer, &dao).await?);
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_app_not_found() -> Result<()> {
+        let algod = FakeAlgod::new();
+        let err = algod
+            .application_information(FIXTURE_APP_ID)
+            .await
+            .unwrap_err();
+
+        assert!(is_app_not_found(&err));
+        assert!(!is_app_not_found(&anyhow!("error sending request")));
+        Ok(())
+    }
+
+    #[test]
+    async fn test_load_dao_or_closed_loads_closed_dao() -> Result<()> {
+        let dao = dao(OWNER)?;
+        let note = BASE64.encode(&closure_to_note(&dao)?);
+        let indexer = FakeIndexer::new()
+            .with_transactions_json(&format!("[{}]", delete_app_json(OWNER, Some(note))))?;
+        // the app was deleted
+        let algod = FakeAlgod::new();
+
+        let loaded = load_dao_or_closed(&algod, &indexer, dao.id()).await?;
+
+        assert_eq!(DaoStatus::Closed, loaded.status);
+        Ok(())
+    }
+
+    #[test]
+    async fn test_deserializes_dao_without_status() -> Result<()> {
+        let dao = dao(OWNER)?;
+        let mut json = serde_json::to_value(&dao)?;
+        json.as_object_mut()
+            .ok_or_else(|| anyhow!("Unexpected: dao isn't a json object"))?
+            .remove("status");
+
+        let deserialized: Dao = serde_json::from_value(json)?;
+
+        assert_eq!(DaoStatus::Active, deserialized.status);
+        Ok(())
+    }
+
+    #[test]
+    async fn test_closure_note() -> Result<()> {
+        let dao = dao(OWNER)?;
+        let note = BASE64.encode(&closure_to_note(&dao)?);
+
+        let saved = base64_maybe_closure_note_to_dao(&note, dao.id())?;
+
+        assert_eq!(
+            Some(Dao {
+                status: DaoStatus::Closed,
+                ..dao.clone()
+            }),
+            saved
+        );
+        // closure of another dao
+        assert_eq!(
+            None,
+            base64_maybe_closure_note_to_dao(&note, DaoId(DaoAppId(FIXTURE_APP_ID + 1)))?
+        );
+        // e.g. a withdrawal description
+        assert_eq!(
+            None,
+            base64_maybe_closure_note_to_dao(&BASE64.encode(b"\x00\x01hello"), dao.id())?
+        );
+        Ok(())
+    }
+
+    #[test]
+    async fn test_closure_note_too_large() -> Result<()> {
+        let dao = Dao {
+            descr_url: Some("a".repeat(1024)),
+            ..dao(OWNER)?
+        };
+
+        assert!(closure_to_note(&dao).is_err());
+        Ok(())
+    }
+
+    #[test]
+    async fn test_load_closed_dao() -> Result<()> {
+        let dao = dao(OWNER)?;
+        let note = BASE64.encode(&closure_to_note(&dao)?);
+        let indexer = FakeIndexer::new()
+            .with_transactions_json(&format!("[{}]", delete_app_json(OWNER, Some(note))))?;
+
+        let closed = load_closed_dao(&indexer, dao.id()).await?;
+
+        assert_eq!(Some(DaoStatus::Closed), closed.map(|d| d.status));
+        Ok(())
+    }
+
+    #[test]
+    async fn test_load_closed_dao_not_closed() -> Result<()> {
+        // deleted without the dao's data, e.g. another app with the same id on another network
+        let indexer = FakeIndexer::new()
+            .with_transactions_json(&format!("[{}]", delete_app_json(OWNER, None)))?;
+
+        assert_eq!(
+            None,
+            load_closed_dao(&indexer, DaoId(DaoAppId(FIXTURE_APP_ID))).await?
+        );
+        Ok(())
+    }
+
+    #[test]
+    async fn test_load_closed_dao_sent_by_other() -> Result<()> {
+        let dao = dao(OWNER)?;
+        let note = BASE64.encode(&closure_to_note(&dao)?);
+        let indexer = FakeIndexer::new()
+            .with_transactions_json(&format!("[{}]", delete_app_json(OTHER, Some(note))))?;
+
+        // the app accepted the deletion: the sender was the owner
+        let closed = load_closed_dao(&indexer, dao.id()).await?;
+
+        assert_eq!(
+            Some(OTHER.parse().map_err(Error::msg)?),
+            closed.map(|d| d.owner)
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    async fn test_close_dao() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let algod = &td.algod;
+        let owner = &td.creator;
+        let investor = &td.investor1;
+
+        let dao = create_dao_flow(td).await?;
+        assert_eq!(DaoStatus::Active, dao.status);
+
+        invests_optins_flow(algod, investor, &dao).await?;
+        invests_flow(td, investor, ShareAmount::new(10), &dao).await?;
+
+        // stop investments
+
+        let to_sign = stop_investments(algod, &owner.address(), &dao).await?;
+        // the closing is recorded in the update's note
+        assert_eq!(Some(closing_to_note(dao.id())), to_sign.update.note);
+        submit_update_data(algod, to_sign.sign(owner)?)
+            .await?
+            .wait(algod)
+            .await?;
+
+        let dao = load_dao(algod, dao.id()).await?;
+        assert_eq!(ShareAmount::new(0), dao.max_invest_amount);
+
+        // the investor still has locked shares
+        let readiness = closing_readiness(algod, &dao, &td.dao_deps()).await?;
+        assert!(!readiness.can_close());
+        assert_eq!(ShareAmount::new(10), readiness.locked_shares);
+        assert!(close_dao(algod, &owner.address(), &dao, &td.dao_deps())
+            .await
+            .is_err());
+
+        let unlock_tx_id = unlock_flow(algod, &dao, investor).await?;
+        wait_for_pending_transaction(algod, &unlock_tx_id).await?;
+
+        // a payment that wasn't drained yet
+        let to_sign = pay_dao_app(
+            algod,
+            &td.customer.address(),
+            dao.app_id,
+            td.funds_asset_id,
+            FundsAmount::new(10 * 1_000_000),
+        )
+        .await?;
+        submit_pay_dao(algod, to_sign.sign(&td.customer)?)
+            .await?
+            .wait(algod)
+            .await?;
+
+        let readiness = closing_readiness(algod, &dao, &td.dao_deps()).await?;
+        assert!(readiness.can_close());
+        assert_eq!(FundsAmount::new(10 * 1_000_000), readiness.not_yet_drained);
+
+        let owner_funds_before = funds_holdings(algod, &owner.address(), td.funds_asset_id).await?;
+        let owner_algos_before = algod.account_information(&owner.address()).await?.amount;
+
+        // close
+
+        let to_sign = close_dao(algod, &owner.address(), &dao, &td.dao_deps()).await?;
+        let fees = to_sign.drain_tx.as_ref().map(|tx| tx.fee.0).unwrap_or(0)
+            + to_sign.withdraw_tx.as_ref().map(|tx| tx.fee.0).unwrap_or(0)
+            + to_sign.delete_tx.fee.0;
+        submit_close_dao(algod, &to_sign.sign(owner)?)
+            .await?
+            .wait(algod)
+            .await?;
+
+        // the app doesn't exist anymore
+        assert!(algod.application_information(dao.app_id.0).await.is_err());
+
+        // the owner got the funds (investment and payment, minus capi's fee) and the app's ALGO
+        let owner_funds = funds_holdings(algod, &owner.address(), td.funds_asset_id).await?;
+        assert_eq!(
+            owner_funds_before.val() + readiness.to_withdraw.val(),
+            owner_funds.val()
+        );
+        let owner_algos = algod.account_information(&owner.address()).await?.amount;
+        assert!(owner_algos.0 > owner_algos_before.0 - fees);
+
+        assert!(load_dao(algod, dao.id()).await.is_err());
+
+        log::debug!("Waiting for indexing..");
+        std::thread::sleep(std::time::Duration::from_secs(120));
+
+        // the closed dao can still be loaded
+        let closed = load_dao_or_closed(algod, &td.indexer, dao.id()).await?;
+        assert_eq!(DaoStatus::Closed, closed.status);
+        assert_eq!(dao.name, closed.name);
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    async fn test_close_dao_without_stopping_investments_fails() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let algod = &td.algod;
+
+        let dao = create_dao_flow(td).await?;
+
+        let readiness = closing_readiness(algod, &dao, &td.dao_deps()).await?;
+        assert!(!readiness.investments_stopped);
+        assert!(
+            close_dao(algod, &td.creator.address(), &dao, &td.dao_deps())
+                .await
+                .is_err()
+        );
+
+        Ok(())
+    }
+}
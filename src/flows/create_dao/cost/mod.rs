@@ -0,0 +1,128 @@
+use super::setup_dao::SETUP_INNER_TXS;
+use crate::{
+    dao_algod::DaoAlgod,
+    min_balance::{
+        account_min_balance, dao_app_creation_min_balance, dao_app_escrow_min_balance,
+        ASSET_MIN_BALANCE,
+    },
+};
+use algonaut::core::{Address, MicroAlgos, SuggestedTransactionParams};
+use anyhow::Result;
+
+mod tests;
+
+/// ALGO paid by the creator to create a DAO, itemized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaoCreationCost {
+    pub items: Vec<CostItem>,
+    pub total: MicroAlgos,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostItem {
+    pub kind: CostKind,
+    pub amount: MicroAlgos,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CostKind {
+    /// The creator's min balance increases by an asset, for the created shares asset
+    SharesAssetMinBalance,
+    /// The creator's min balance increases by the app's program pages (incl. extra pages) and global state schema
+    AppMinBalance,
+    /// Sent to the app: its account's min balance and holding the shares and funds asset
+    AppFunding,
+    /// Part of the app funding reserved to hold the image NFT. The setup funds it also when there's no image.
+    ImageNftMinBalance,
+    CreateSharesFee,
+    CreateAppFee,
+    /// The setup group's fees, incl. the pooled fees of the app's inner txs
+    SetupFee,
+}
+
+impl DaoCreationCost {
+    fn new(items: Vec<CostItem>) -> DaoCreationCost {
+        let total = MicroAlgos(items.iter().map(|i| i.amount.0).sum());
+        DaoCreationCost { items, total }
+    }
+}
+
+/// Estimates the cost of creating a DAO with the current suggested params.
+/// Fees are the params' min fees: if the network is congested they can be higher.
+pub async fn estimate_dao_creation_cost(algod: &dyn DaoAlgod) -> Result<DaoCreationCost> {
+    let params = algod.suggested_transaction_params().await?;
+    Ok(dao_creation_cost(&params))
+}
+
+pub fn dao_creation_cost(params: &SuggestedTransactionParams) -> DaoCreationCost {
+    let fee = params.min_fee.0;
+
+    // setup group: app funding (paying the inner txs), setup app call, shares transfer
+    let setup_fee = fee * (1 + SETUP_INNER_TXS) + fee + fee;
+
+    DaoCreationCost::new(vec![
+        CostItem {
+            kind: CostKind::SharesAssetMinBalance,
+            amount: MicroAlgos(ASSET_MIN_BALANCE),
+        },
+        CostItem {
+            kind: CostKind::AppMinBalance,
+            amount: dao_app_creation_min_balance(),
+        },
+        CostItem {
+            kind: CostKind::AppFunding,
+            amount: MicroAlgos(dao_app_escrow_min_balance().0 - ASSET_MIN_BALANCE),
+        },
+        CostItem {
+            kind: CostKind::ImageNftMinBalance,
+            amount: MicroAlgos(ASSET_MIN_BALANCE),
+        },
+        CostItem {
+            kind: CostKind::CreateSharesFee,
+            amount: MicroAlgos(fee),
+        },
+        CostItem {
+            kind: CostKind::CreateAppFee,
+            amount: MicroAlgos(fee),
+        },
+        CostItem {
+            kind: CostKind::SetupFee,
+            amount: MicroAlgos(setup_fee),
+        },
+    ])
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreatorBalanceCheck {
+    /// Balance that can be spent: above the creator's current min balance
+    pub available: MicroAlgos,
+    pub required: MicroAlgos,
+}
+
+impl CreatorBalanceCheck {
+    pub fn is_enough(&self) -> bool {
+        self.available.0 >= self.required.0
+    }
+
+    /// What has to be added to the creator's account to create the DAO
+    pub fn missing(&self) -> MicroAlgos {
+        MicroAlgos(self.required.0.saturating_sub(self.available.0))
+    }
+}
+
+pub async fn check_creator_balance(
+    algod: &dyn DaoAlgod,
+    creator: &Address,
+    cost: &DaoCreationCost,
+) -> Result<CreatorBalanceCheck> {
+    let account = algod.account_information(creator).await?;
+    Ok(CreatorBalanceCheck {
+        available: MicroAlgos(
+            account
+                .amount
+                .0
+                .saturating_sub(account_min_balance(&account).0),
+        ),
+        required: cost.total,
+    })
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dao_algod::fake::FakeAlgod,
+        flows::create_dao::cost::{estimate_dao_creation_cost, CostKind, CreatorBalanceCheck},
+    };
+    use algonaut::core::MicroAlgos;
+    use anyhow::Result;
+    use tokio::test;
+
+    #[test]
+    async fn test_estimate_is_itemized() -> Result<()> {
+        let algod = FakeAlgod::new();
+        let min_fee = algod.params().min_fee;
+
+        let cost = estimate_dao_creation_cost(&algod).await?;
+
+        assert_eq!(
+            MicroAlgos(cost.items.iter().map(|i| i.amount.0).sum()),
+            cost.total
+        );
+        let amount = |kind: CostKind| cost.items.iter().find(|i| i.kind == kind).map(|i| i.amount);
+        assert_eq!(Some(min_fee), amount(CostKind::CreateSharesFee));
+        assert_eq!(Some(min_fee), amount(CostKind::CreateAppFee));
+        // app funding (incl. 3 inner txs), setup call, shares transfer
+        assert_eq!(Some(min_fee * 6), amount(CostKind::SetupFee));
+        // the funding sent to the app during setup
+        assert_eq!(
+            Some(MicroAlgos(400_000)),
+            amount(CostKind::AppFunding)
+                .zip(amount(CostKind::ImageNftMinBalance))
+                .map(|(a, b)| MicroAlgos(a.0 + b.0))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_balance_check_missing() {
+        let check = CreatorBalanceCheck {
+            available: MicroAlgos(300_000),
+            required: MicroAlgos(1_000_000),
+        };
+        assert!(!check.is_enough());
+        assert_eq!(MicroAlgos(700_000), check.missing());
+
+        let check = CreatorBalanceCheck {
+            available: MicroAlgos(1_000_000),
+            required: MicroAlgos(1_000_000),
+        };
+        assert!(check.is_enough());
+        assert_eq!(MicroAlgos(0), check.missing());
+    }
+}
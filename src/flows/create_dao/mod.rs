@@ -1,6 +1,10 @@
+pub mod cost;
 pub mod model;
+pub mod orchestrator;
 pub mod setup;
 #[allow(clippy::module_inception)]
 pub mod setup_dao;
+pub mod shares_config;
 pub mod storage;
 mod tests;
+pub mod validate_specs;
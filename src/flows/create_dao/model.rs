@@ -1,7 +1,9 @@
+use crate::signer::{Sign, SignedTxs, TxSigner};
 use algonaut::{
     core::Address,
     transaction::{contract_account::ContractAccount, SignedTransaction, Transaction},
 };
+use anyhow::Result;
 use mbase::{
     api::version::VersionedContractAccount,
     models::{
@@ -46,8 +48,14 @@ pub struct SetupDaoToSign {
 
     pub specs: SetupDaoSpecs,
     pub creator: Address,
+    pub shares_asset_id: u64,
+    pub app_id: DaoAppId,
+    pub funds_asset_id: FundsAssetId,
+    pub image_url: Option<String>,
 
     pub setup_date: Timestamp,
+
+    pub prospectus: Option<Prospectus>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -69,6 +77,36 @@ pub struct SetupDaoSigned {
     pub prospectus: Option<Prospectus>,
 }
 
+impl Sign for SetupDaoToSign {
+    type Signed = SetupDaoSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<SetupDaoSigned> {
+        Ok(SetupDaoSigned {
+            app_funding_tx: signer.sign_tx(self.fund_app_tx)?,
+            setup_app_tx: signer.sign_tx(self.setup_app_tx)?,
+            transfer_shares_to_app_tx: signer.sign_tx(self.transfer_shares_to_app_tx)?,
+            specs: self.specs,
+            creator: self.creator,
+            shares_asset_id: self.shares_asset_id,
+            app_id: self.app_id,
+            funds_asset_id: self.funds_asset_id,
+            image_url: self.image_url,
+            setup_date: self.setup_date,
+            prospectus: self.prospectus,
+        })
+    }
+}
+
+impl SignedTxs for SetupDaoSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![
+            self.app_funding_tx.clone(),
+            self.setup_app_tx.clone(),
+            self.transfer_shares_to_app_tx.clone(),
+        ]
+    }
+}
+
 /// Note that dao doesn't know its id (DaoId), because it's generated after it's stored (it's the id of the storage tx),
 /// TODO it probably makes sense to name the id "StoredDaoId" to be more accurate.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -82,6 +120,10 @@ pub struct Dao {
     pub descr_url: Option<String>,
     pub token_name: String,
     pub token_supply: ShareAmount,
+    /// Decimals of the shares asset: share amounts are in base units, a share is `10^token_decimals` of them
+    /// Defaults to 0, the decimals of the DAOs serialized before they were configurable
+    #[serde(default)]
+    pub token_decimals: u32,
     pub investors_share: SharesPercentage,
     pub share_price: FundsAmount,
     pub image_nft: Option<Nft>,
@@ -103,6 +145,25 @@ pub struct Dao {
     pub max_invest_amount: ShareAmount,
 
     pub team_url: Option<String>,
+
+    /// Valid only if the DAO was loaded with [crate::flows::create_dao::storage::load_dao::load_dao_or_closed],
+    /// the only place that looks up the closing (in the indexer).
+    /// The other ways to get the DAO (e.g. [crate::flows::create_dao::storage::load_dao::load_dao]) set [DaoStatus::Active] while the app exists.
+    #[serde(default)]
+    pub status: DaoStatus,
+}
+
+/// Lifecycle of the DAO, see [crate::flows::close_dao]
+/// Defaults to active, the status of the DAOs serialized before it existed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DaoStatus {
+    #[default]
+    Active,
+    /// The owner stopped the investments to close the DAO (see [crate::flows::close_dao::close_dao::stop_investments]):
+    /// it can still be drained, and investors can unlock
+    Closing,
+    /// The app was deleted: the data is the one saved when closing (see [crate::flows::close_dao::close_dao::load_closed_dao])
+    Closed,
 }
 
 impl Dao {
@@ -129,6 +190,7 @@ impl Debug for Dao {
             .field("descr_url", &self.descr_url)
             .field("token_name", &self.token_name)
             .field("token_supply", &self.token_supply)
+            .field("token_decimals", &self.token_decimals)
             .field("investors_share", &self.investors_share)
             .field("share_price", &self.share_price)
             .field("image_nft", &self.image_nft)
@@ -137,6 +199,7 @@ impl Debug for Dao {
             .field("raise_min_target", &self.raise_min_target)
             .field("raised", &self.raised)
             .field("prospectus", &self.prospectus)
+            .field("status", &self.status)
             .finish()
     }
 }
@@ -152,6 +215,33 @@ pub struct CreateAssetsToSign {
     pub create_app_tx: Transaction,
 }
 
+/// Creates only the app, for a DAO with an existing shares asset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateAppToSign {
+    pub create_app_tx: Transaction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateAppSigned {
+    pub create_app: SignedTransaction,
+}
+
+impl Sign for CreateAppToSign {
+    type Signed = CreateAppSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<CreateAppSigned> {
+        Ok(CreateAppSigned {
+            create_app: signer.sign_tx(self.create_app_tx)?,
+        })
+    }
+}
+
+impl SignedTxs for CreateAppSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.create_app.clone()]
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CreateImageNftToSign {
     pub tx: Transaction,
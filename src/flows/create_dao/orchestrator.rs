@@ -0,0 +1,337 @@
+use super::{
+    model::Dao,
+    setup::{
+        create_app::{create_app_tx, render_and_compile_app_approval},
+        create_shares::{create_shares_tx, send_and_retrieve_app_id, send_and_retrieve_asset_id},
+        existing_shares::verify_existing_shares_asset,
+    },
+    setup_dao::{setup_dao_txs, submit_setup_dao, Programs},
+    shares_config::SharesAssetConfig,
+};
+use crate::{
+    compile_cache::CompileCache,
+    dao_algod::DaoAlgod,
+    image_metadata::ImageNftMetadata,
+    signer::{Sign, TxSigner},
+};
+use algonaut::core::{to_app_address, Address};
+use anyhow::{anyhow, Result};
+use mbase::models::{
+    capi_deps::CapiAssetDaoDeps,
+    dao_app_id::DaoAppId,
+    funds::{FundsAmount, FundsAssetId},
+    setup_dao_specs::SetupDaoSpecs,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Everything needed to create a DAO, independent of the progress.
+#[derive(Debug)]
+pub struct CreateDaoInputs {
+    pub creator: Address,
+    pub specs: SetupDaoSpecs,
+    pub shares_config: SharesAssetConfig,
+    /// Use this asset as shares asset instead of creating one (`shares_config` is then ignored).
+    /// See [verify_existing_shares_asset] for the requirements.
+    pub existing_shares_asset_id: Option<u64>,
+    pub funds_asset_id: FundsAssetId,
+    pub programs: Programs,
+    pub precision: u64,
+    pub capi_deps: CapiAssetDaoDeps,
+    pub max_raisable_amount: FundsAmount,
+    /// Metadata of the image NFT, needed only at the setup step: it links the app (see [crate::image_metadata::dao_image_metadata]),
+    /// so it can be set after the app was created, continuing with the checkpoint.
+    pub image_nft_metadata: Option<ImageNftMetadata>,
+}
+
+/// What has been created so far.
+/// Meant to be persisted after each step, so a failed creation can continue where it stopped,
+/// instead of leaving an orphan shares asset or app behind.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateDaoCheckpoint {
+    pub shares_asset_id: Option<u64>,
+    pub app_id: Option<DaoAppId>,
+    /// Set when the app was set up: the creation is complete
+    pub dao: Option<Dao>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreateDaoStep {
+    CreateShares,
+    CreateApp,
+    /// Funds the app, initializes its state, transfers the shares to it and (if there's an image url) creates the image NFT
+    SetupDao,
+    Done,
+}
+
+impl CreateDaoCheckpoint {
+    /// Continue with a shares asset and/or app created previously (e.g. found with [find_partial_daos])
+    pub fn resume(shares_asset_id: Option<u64>, app_id: Option<DaoAppId>) -> CreateDaoCheckpoint {
+        CreateDaoCheckpoint {
+            shares_asset_id,
+            app_id,
+            dao: None,
+        }
+    }
+
+    pub fn next_step(&self) -> CreateDaoStep {
+        if self.dao.is_some() {
+            CreateDaoStep::Done
+        } else if self.shares_asset_id.is_none() {
+            CreateDaoStep::CreateShares
+        } else if self.app_id.is_none() {
+            CreateDaoStep::CreateApp
+        } else {
+            CreateDaoStep::SetupDao
+        }
+    }
+}
+
+/// Runs the remaining steps, starting at `checkpoint` (the default checkpoint for a new DAO).
+/// `on_checkpoint` is called with the new checkpoint after each step, before continuing with the next one:
+/// if it fails (e.g. the checkpoint couldn't be saved), the creation stops.
+/// The app's programs are compiled once per `compile_cache`: e.g. resuming a creation doesn't compile them again.
+pub async fn create_dao<F>(
+    algod: &dyn DaoAlgod,
+    signer: &dyn TxSigner,
+    inputs: &CreateDaoInputs,
+    compile_cache: &CompileCache,
+    checkpoint: CreateDaoCheckpoint,
+    mut on_checkpoint: F,
+) -> Result<Dao>
+where
+    F: FnMut(&CreateDaoCheckpoint) -> Result<()>,
+{
+    verify_checkpoint(algod, inputs, &checkpoint).await?;
+
+    let mut checkpoint = checkpoint;
+    loop {
+        if let Some(dao) = &checkpoint.dao {
+            return Ok(dao.clone());
+        }
+        checkpoint = create_dao_step(algod, signer, inputs, compile_cache, &checkpoint).await?;
+        on_checkpoint(&checkpoint)?;
+    }
+}
+
+/// Executes the next step of the creation and returns the resulting checkpoint
+pub async fn create_dao_step(
+    algod: &dyn DaoAlgod,
+    signer: &dyn TxSigner,
+    inputs: &CreateDaoInputs,
+    compile_cache: &CompileCache,
+    checkpoint: &CreateDaoCheckpoint,
+) -> Result<CreateDaoCheckpoint> {
+    let step = checkpoint.next_step();
+    log::debug!("Create dao step: {step:?}, checkpoint: {checkpoint:?}");
+
+    match (step, checkpoint.shares_asset_id, checkpoint.app_id) {
+        (CreateDaoStep::CreateShares, _, _) => {
+            if let Some(shares_asset_id) = inputs.existing_shares_asset_id {
+                verify_existing_shares_asset(
+                    algod,
+                    &inputs.creator,
+                    shares_asset_id,
+                    &inputs.specs,
+                )
+                .await?;
+                return Ok(CreateDaoCheckpoint {
+                    shares_asset_id: Some(shares_asset_id),
+                    ..checkpoint.clone()
+                });
+            }
+            let params = algod.suggested_transaction_params().await?;
+            let tx = create_shares_tx(
+                &params,
+                &inputs.specs.shares,
+                &inputs.shares_config,
+                inputs.creator,
+            )
+            .await?;
+            let shares_asset_id = send_and_retrieve_asset_id(algod, &signer.sign_tx(tx)?).await?;
+            Ok(CreateDaoCheckpoint {
+                shares_asset_id: Some(shares_asset_id),
+                ..checkpoint.clone()
+            })
+        }
+        (CreateDaoStep::CreateApp, _, _) => {
+            let params = algod.suggested_transaction_params().await?;
+            let tx = create_app_tx(
+                algod,
+                &inputs.programs.central_app_approval,
+                &inputs.programs.central_app_clear,
+                &inputs.creator,
+                inputs.specs.shares.supply,
+                inputs.precision,
+                inputs.specs.investors_share,
+                &params,
+                &inputs.capi_deps,
+                inputs.specs.share_price,
+                inputs.max_raisable_amount,
+                compile_cache,
+            )
+            .await?;
+            let app_id = send_and_retrieve_app_id(algod, &signer.sign_tx(tx)?).await?;
+            Ok(CreateDaoCheckpoint {
+                app_id: Some(app_id),
+                ..checkpoint.clone()
+            })
+        }
+        (CreateDaoStep::SetupDao, Some(shares_asset_id), Some(app_id)) => {
+            let to_sign = setup_dao_txs(
+                algod,
+                &inputs.specs,
+                inputs.creator,
+                shares_asset_id,
+                inputs.funds_asset_id,
+                &inputs.programs,
+                inputs.precision,
+                app_id,
+                inputs.specs.image_url.clone(),
+                inputs.image_nft_metadata.clone(),
+                inputs.specs.prospectus.clone(),
+            )
+            .await?;
+            let res = submit_setup_dao(algod, to_sign.sign(signer)?)
+                .await?
+                .wait(algod)
+                .await?;
+            Ok(CreateDaoCheckpoint {
+                dao: Some(res.dao),
+                ..checkpoint.clone()
+            })
+        }
+        (CreateDaoStep::Done, _, _) => Ok(checkpoint.clone()),
+        _ => Err(anyhow!("Invalid create dao checkpoint: {checkpoint:?}")),
+    }
+}
+
+/// Ensures that the checkpoint's shares asset (unless it's the existing one of the inputs) and app were created by the creator
+/// and that the app wasn't set up yet (setting it up again would fail).
+async fn verify_checkpoint(
+    algod: &dyn DaoAlgod,
+    inputs: &CreateDaoInputs,
+    checkpoint: &CreateDaoCheckpoint,
+) -> Result<()> {
+    let creator = &inputs.creator;
+    if checkpoint.dao.is_some()
+        || (checkpoint.shares_asset_id.is_none() && checkpoint.app_id.is_none())
+    {
+        return Ok(());
+    }
+
+    let creator_infos = algod.account_information(creator).await?;
+
+    if let Some(shares_asset_id) = checkpoint.shares_asset_id {
+        if inputs.existing_shares_asset_id != Some(shares_asset_id)
+            && !creator_infos
+                .created_assets
+                .iter()
+                .any(|a| a.index == shares_asset_id)
+        {
+            return Err(anyhow!(
+                "Shares asset: {shares_asset_id} wasn't created by: {creator}"
+            ));
+        }
+    }
+
+    if let Some(app_id) = checkpoint.app_id {
+        let app = creator_infos
+            .created_apps
+            .iter()
+            .find(|a| a.id == app_id.0)
+            .ok_or_else(|| anyhow!("App: {app_id:?} wasn't created by: {creator}"))?;
+        if !app.params.global_state.is_empty() {
+            return Err(anyhow!(
+                "App: {app_id:?} was already set up: load the dao instead of resuming its creation"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Shares assets and apps of DAO creations that didn't complete, for the same inputs (see [find_partial_daos])
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialDaos {
+    /// Apps created by the creator whose global state is still empty (set up initializes it), running the approval of the inputs
+    pub apps_not_set_up: Vec<DaoAppId>,
+    /// Assets created by the creator as shares asset with the specs, not held by any of its set up apps
+    pub unused_shares_assets: Vec<u64>,
+}
+
+impl PartialDaos {
+    pub fn is_empty(&self) -> bool {
+        self.apps_not_set_up.is_empty() && self.unused_shares_assets.is_empty()
+    }
+
+    /// Checkpoint to continue the most recent creation (ids increase with time), if there's one.
+    /// The apps and assets were found with the same inputs, so each app fits each asset: the most recent ones are paired.
+    pub fn resume_checkpoint(&self) -> Option<CreateDaoCheckpoint> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(CreateDaoCheckpoint::resume(
+            self.unused_shares_assets.iter().max().cloned(),
+            self.apps_not_set_up.iter().max().cloned(),
+        ))
+    }
+}
+
+/// Inspects the apps and assets created by the inputs' creator, to find the ones of DAO creations with the same inputs that didn't complete.
+/// The apps have to run the approval the inputs render (see create_app_tx), which is compiled once per `compile_cache`.
+pub async fn find_partial_daos(
+    algod: &dyn DaoAlgod,
+    inputs: &CreateDaoInputs,
+    compile_cache: &CompileCache,
+) -> Result<PartialDaos> {
+    let creator_infos = algod.account_information(&inputs.creator).await?;
+    let shares_specs = &inputs.specs.shares;
+    let supply = shares_specs.supply.val();
+
+    let expected_approval = render_and_compile_app_approval(
+        algod,
+        &inputs.programs.central_app_approval,
+        shares_specs.supply,
+        inputs.precision,
+        inputs.specs.investors_share,
+        &inputs.capi_deps.address,
+        inputs.capi_deps.escrow_percentage,
+        inputs.specs.share_price,
+        inputs.max_raisable_amount,
+        compile_cache,
+    )
+    .await?;
+
+    let mut apps_not_set_up = vec![];
+    let mut used_assets = HashSet::new();
+    for app in &creator_infos.created_apps {
+        if app.params.global_state.is_empty() {
+            if app.params.approval_program == expected_approval.0 {
+                apps_not_set_up.push(DaoAppId(app.id));
+            }
+        } else {
+            // set up apps hold (are opted in to) their shares asset
+            let app_infos = algod.account_information(&to_app_address(app.id)).await?;
+            used_assets.extend(app_infos.assets.iter().map(|h| h.asset_id));
+        }
+    }
+
+    let unused_shares_assets = creator_infos
+        .created_assets
+        .iter()
+        // see create_shares_tx (the rest of the params are configurable, see SharesAssetConfig)
+        .filter(|a| {
+            a.params.total == supply
+                && a.params.name.as_ref() == Some(&shares_specs.token_name)
+                && a.params.unit_name.as_ref() == Some(&shares_specs.token_name)
+        })
+        .map(|a| a.index)
+        .filter(|id| !used_assets.contains(id))
+        .collect();
+
+    Ok(PartialDaos {
+        apps_not_set_up,
+        unused_shares_assets,
+    })
+}
@@ -1,5 +1,13 @@
+use crate::{
+    compile_cache::CompileCache,
+    dao_algod::DaoAlgod,
+    min_balance::DAO_APP_EXTRA_PAGES,
+    teal_registry::{
+        dao_teal_registry, render_approval_values, ApprovalTemplateParams, TemplateParam,
+        TemplateValue,
+    },
+};
 use algonaut::{
-    algod::v2::Algod,
     core::{Address, CompiledTeal, SuggestedTransactionParams},
     transaction::{transaction::StateSchema, CreateApplication, Transaction, TxnBuilder},
 };
@@ -16,18 +24,15 @@ use mbase::{
         GLOBAL_SCHEMA_NUM_BYTE_SLICES, GLOBAL_SCHEMA_NUM_INTS, LOCAL_SCHEMA_NUM_BYTE_SLICES,
         LOCAL_SCHEMA_NUM_INTS,
     },
-    teal::{render_template_new, TealSource, TealSourceTemplate},
+    teal::{TealSource, TealSourceTemplate},
     util::decimal_util::AsDecimal,
 };
 use rust_decimal::prelude::ToPrimitive;
 use serde::Serialize;
 
-#[cfg(not(target_arch = "wasm32"))]
-use mbase::teal::save_rendered_teal;
-
 #[allow(clippy::too_many_arguments)]
 pub async fn create_app_tx(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     approval_template: &VersionedTealSourceTemplate,
     clear_template: &VersionedTealSourceTemplate,
     creator: &Address,
@@ -40,6 +45,7 @@ pub async fn create_app_tx(
     // checked in teal (it's guaranteed that no more can be raised)
     // expected to be determined by regulations, normally
     max_raisable_amount: FundsAmount,
+    compile_cache: &CompileCache,
 ) -> Result<Transaction> {
     log::debug!("Creating central app with asset supply: {}", share_supply);
 
@@ -53,9 +59,11 @@ pub async fn create_app_tx(
         capi_deps.escrow_percentage,
         share_price,
         max_raisable_amount,
+        compile_cache,
     )
     .await?;
-    let compiled_clear_program = render_and_compile_app_clear(algod, clear_template).await?;
+    let compiled_clear_program =
+        render_and_compile_app_clear(algod, clear_template, compile_cache).await?;
 
     let tx = TxnBuilder::with(
         params,
@@ -72,7 +80,7 @@ pub async fn create_app_tx(
                 number_byteslices: LOCAL_SCHEMA_NUM_BYTE_SLICES,
             },
         )
-        .extra_pages(1)
+        .extra_pages(DAO_APP_EXTRA_PAGES as u32)
         .build(),
     )
     .build()?;
@@ -80,9 +88,10 @@ pub async fn create_app_tx(
     Ok(tx)
 }
 
+/// The programs are compiled once per `compile_cache` (e.g. retrying a creation renders the same approval)
 #[allow(clippy::too_many_arguments)]
 pub async fn render_and_compile_app_approval(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     template: &VersionedTealSourceTemplate,
     share_supply: ShareAmount,
     precision: u64,
@@ -91,25 +100,22 @@ pub async fn render_and_compile_app_approval(
     capi_percentage: SharesPercentage,
     share_price: FundsAmount,
     max_raisable_amount: FundsAmount,
+    compile_cache: &CompileCache,
 ) -> Result<CompiledTeal> {
-    let source = match template.version.0 {
-        1 => render_central_app_approval_v1(
-            &template.template,
+    let source = dao_teal_registry().render_approval(
+        template,
+        &ApprovalTemplateParams {
             share_supply,
             precision,
             investors_share,
-            capi_address,
+            capi_address: CapiAddress(capi_address.0),
             capi_percentage,
             share_price,
             max_raisable_amount,
-        ),
-        _ => Err(anyhow!(
-            "Dao app approval version not supported: {:?}",
-            template.version
-        )),
-    }?;
+        },
+    )?;
 
-    Ok(algod.compile_teal(&source.0).await?)
+    compile_cache.compile(algod, &source.0).await
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -123,58 +129,79 @@ pub fn render_central_app_approval_v1(
     share_price: FundsAmount,
     max_raisable_amount: FundsAmount,
 ) -> Result<TealSource> {
+    let values = approval_v1_values(&ApprovalTemplateParams {
+        share_supply,
+        precision,
+        investors_share,
+        capi_address: CapiAddress(capi_address.0),
+        capi_percentage,
+        share_price,
+        max_raisable_amount,
+    })?;
+    render_approval_values(source, &values)
+}
+
+/// The values of the approval v1 template's placeholders
+pub fn approval_v1_values(
+    params: &ApprovalTemplateParams,
+) -> Result<Vec<(TemplateParam, TemplateValue)>> {
+    let precision = params.precision;
     let precision_square = precision
         .checked_pow(2)
         .ok_or_else(|| anyhow!("Precision squared overflow: {}", precision))?;
 
-    // TODO write tests that catch incorrect/variable supply - previously it was hardcoded to 100 and everything was passing
-    let investors_share_percentage = (investors_share.value() * precision.as_decimal().floor())
-        .to_u64()
-        .ok_or_else(|| anyhow!("Unexpected: couldn't convert decimal to u64"))?;
+    let investors_share_percentage = (params.investors_share.value()
+        * precision.as_decimal().floor())
+    .to_u64()
+    .ok_or_else(|| anyhow!("Unexpected: couldn't convert decimal to u64"))?;
 
-    let capi_share = (capi_percentage
+    let capi_share = (params
+        .capi_percentage
         .value()
         .checked_mul(precision.as_decimal())
         .ok_or_else(|| anyhow!("Precision squared overflow: {}", precision))?)
-    .floor();
-
-    let source = render_template_new(
-        source,
-        &[
-            ("TMPL_SHARE_SUPPLY", &share_supply.to_string()),
-            (
-                "TMPL_INVESTORS_SHARE",
-                &investors_share_percentage.to_string(),
-            ),
-            ("TMPL_PRECISION__", &precision.to_string()),
-            ("TMPL_PRECISION_SQUARE", &precision_square.to_string()),
-            ("TMPL_CAPI_ESCROW_ADDRESS", &capi_address.0.to_string()),
-            ("TMPL_CAPI_SHARE", &capi_share.to_string()),
-            ("TMPL_SHARE_PRICE", &share_price.val().to_string()),
-            (
-                "TMPL_MAX_RAISABLE_AMOUNT",
-                &max_raisable_amount.val().to_string(),
-            ),
-        ],
-    )?;
-    #[cfg(not(target_arch = "wasm32"))]
-    save_rendered_teal("dao_app_approval", source.clone())?; // debugging
-    Ok(source)
+    .floor()
+    .to_u64()
+    .ok_or_else(|| anyhow!("Unexpected: couldn't convert decimal to u64"))?;
+
+    Ok(vec![
+        (
+            TemplateParam::ShareSupply,
+            TemplateValue::Int(params.share_supply.val()),
+        ),
+        (
+            TemplateParam::InvestorsShare,
+            TemplateValue::Int(investors_share_percentage),
+        ),
+        (TemplateParam::Precision, TemplateValue::Int(precision)),
+        (
+            TemplateParam::PrecisionSquare,
+            TemplateValue::Int(precision_square),
+        ),
+        (
+            TemplateParam::CapiEscrowAddress,
+            TemplateValue::Address(params.capi_address.0),
+        ),
+        (TemplateParam::CapiShare, TemplateValue::Int(capi_share)),
+        (
+            TemplateParam::SharePrice,
+            TemplateValue::Int(params.share_price.val()),
+        ),
+        (
+            TemplateParam::MaxRaisableAmount,
+            TemplateValue::Int(params.max_raisable_amount.val()),
+        ),
+    ])
 }
 
 pub async fn render_and_compile_app_clear(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     template: &VersionedTealSourceTemplate,
+    compile_cache: &CompileCache,
 ) -> Result<CompiledTeal> {
-    let source = match template.version.0 {
-        1 => render_central_app_clear_v1(&template.template),
-        _ => Err(anyhow!(
-            "Dao app clear version not supported: {:?}",
-            template.version
-        )),
-    }?;
-
-    Ok(algod.compile_teal(&source.0).await?)
+    let source = dao_teal_registry().render_clear(template)?;
+
+    compile_cache.compile(algod, &source.0).await
 }
 
 pub fn render_central_app_clear_v1(template: &TealSourceTemplate) -> Result<TealSource> {
@@ -195,12 +222,17 @@ struct RenderCentralAppContext {
 
 #[cfg(test)]
 mod tests {
-    use crate::testing::TESTS_DEFAULT_PRECISION;
+    use crate::{
+        compile_cache::{CompileCache, CompileStore, MemoryCompileStore},
+        dao_algod::fake::FakeAlgod,
+        testing::TESTS_DEFAULT_PRECISION,
+    };
     use algonaut::{
         model::algod::v2::TealKeyValue,
         transaction::{transaction::StateSchema, Transaction, TransactionType},
     };
     use anyhow::{anyhow, Result};
+    use data_encoding::BASE64;
     use mbase::{
         api::version::{Version, VersionedTealSourceTemplate},
         dependencies::algod_for_tests,
@@ -221,7 +253,23 @@ mod tests {
     use std::{convert::TryInto, str::FromStr};
     use tokio::test;
 
-    use super::create_app_tx;
+    use super::{create_app_tx, render_and_compile_app_clear, render_central_app_clear_v1};
+
+    #[test]
+    async fn test_compiles_through_the_cache() -> Result<()> {
+        let template =
+            VersionedTealSourceTemplate::new(load_teal_template("dao_app_clear")?, Version(1));
+        let source = render_central_app_clear_v1(&template.template)?;
+        let store = MemoryCompileStore::default();
+        store.set(&CompileCache::key(&source.0), &BASE64.encode(b"stored"))?;
+
+        let compiled =
+            render_and_compile_app_clear(&FakeAlgod::new(), &template, &CompileCache::new(store))
+                .await?;
+
+        assert_eq!(b"stored".to_vec(), compiled.0);
+        Ok(())
+    }
 
     #[test]
     #[serial] // reset network (cmd)
@@ -257,6 +305,7 @@ mod tests {
             // Arbitrary - not used
             FundsAmount::new(10),
             FundsAmount::new(5_000_000_000_000),
+            &CompileCache::new(MemoryCompileStore::default()),
         )
         .await?;
 
@@ -1,11 +1,19 @@
-use crate::flows::create_dao::model::CreateAssetsToSign;
+use crate::{
+    compile_cache::CompileCache,
+    dao_algod::DaoAlgod,
+    flows::create_dao::{
+        model::{CreateAssetsToSign, CreateImageNftToSign},
+        shares_config::SharesAssetConfig,
+        validate_specs::ensure_valid_shares_config,
+    },
+    signer::{Sign, SignedTxs, TxSigner},
+    submission::{Confirmation, Submission},
+};
 use algonaut::{
-    algod::v2::Algod,
     core::{to_app_address, Address, SuggestedTransactionParams},
-    model::algod::v2::PendingTransaction,
     transaction::{CreateAsset, SignedTransaction, Transaction, TxnBuilder},
 };
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use futures::join;
 use mbase::{
     api::version::VersionedTealSourceTemplate,
@@ -13,7 +21,6 @@ use mbase::{
         capi_deps::CapiAssetDaoDeps, create_shares_specs::CreateSharesSpecs, dao_app_id::DaoAppId,
         funds::FundsAmount, nft::Cid, setup_dao_specs::SetupDaoSpecs,
     },
-    util::network_util::wait_for_pending_transaction,
 };
 use serde::{Deserialize, Serialize};
 
@@ -21,17 +28,20 @@ use super::create_app::create_app_tx;
 
 #[allow(clippy::too_many_arguments)]
 pub async fn create_assets(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     creator: &Address,
     specs: &SetupDaoSpecs,
+    shares_config: &SharesAssetConfig,
     app_approval: &VersionedTealSourceTemplate,
     app_clear: &VersionedTealSourceTemplate,
     precision: u64,
     capi_deps: &CapiAssetDaoDeps,
     max_raisable_amount: FundsAmount,
+    compile_cache: &CompileCache,
 ) -> Result<CreateAssetsToSign> {
     let params = algod.suggested_transaction_params().await?;
-    let create_shares_tx = &mut create_shares_tx(&params, &specs.shares, *creator).await?;
+    let create_shares_tx =
+        &mut create_shares_tx(&params, &specs.shares, shares_config, *creator).await?;
 
     let create_app_tx = &mut create_app_tx(
         algod,
@@ -45,6 +55,7 @@ pub async fn create_assets(
         capi_deps,
         specs.share_price,
         max_raisable_amount,
+        compile_cache,
     )
     .await?;
 
@@ -54,10 +65,11 @@ pub async fn create_assets(
     })
 }
 
+/// Broadcasts the creation of the shares asset and the app. Their ids are known when they're confirmed, see [CreateAssetsSubmission::wait].
 pub async fn submit_create_assets(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     signed: &CreateDaoAssetsSigned,
-) -> Result<CreateAssetsResult> {
+) -> Result<CreateAssetsSubmission> {
     log::debug!("Submitting create assets..");
 
     // let txs = vec![signed.create_app.clone()];
@@ -69,46 +81,64 @@ pub async fn submit_create_assets(
     // TODO confirm that it's not possible to bundle these 3 in a group. or maybe inner txs somehow?
 
     // see testing::algorand_checks::cannot_create_asset_and_app_in_same_group
-    let shares_asset_id_fut = send_and_retrieve_asset_id(algod, &signed.create_shares);
-    let app_id_fut = send_and_retrieve_app_id(algod, &signed.create_app);
+    let shares_res = algod
+        .broadcast_signed_transaction(&signed.create_shares)
+        .await?;
+    let app_res = algod
+        .broadcast_signed_transaction(&signed.create_app)
+        .await?;
+
+    Ok(CreateAssetsSubmission {
+        create_shares: Submission::from_response(&shares_res)?,
+        create_app: Submission::from_response(&app_res)?,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateAssetsSubmission {
+    pub create_shares: Submission,
+    pub create_app: Submission,
+}
 
-    let (shares_asset_id_res, app_id_res) = join!(shares_asset_id_fut, app_id_fut);
+impl CreateAssetsSubmission {
+    /// Waits for both creations to be confirmed and returns the created ids
+    pub async fn wait(&self, algod: &dyn DaoAlgod) -> Result<CreateAssetsResult> {
+        let (shares_res, app_res) =
+            join!(self.create_shares.wait(algod), self.create_app.wait(algod));
 
-    let shares_asset_id = shares_asset_id_res?;
-    let app_id = app_id_res?;
+        let shares_asset_id = shares_res?.created_asset_id()?;
+        let app_id = DaoAppId(app_res?.created_app_id()?);
 
-    let app_address = to_app_address(app_id.0);
+        let app_address = to_app_address(app_id.0);
 
-    log::debug!("Dao assets created. Shares id: {shares_asset_id}, app id: {app_id:?}, app address: {app_address:?}");
+        log::debug!("Dao assets created. Shares id: {shares_asset_id}, app id: {app_id:?}, app address: {app_address:?}");
 
-    Ok(CreateAssetsResult {
-        shares_asset_id,
-        app_id,
-    })
+        Ok(CreateAssetsResult {
+            shares_asset_id,
+            app_id,
+        })
+    }
 }
 
-async fn send_and_retrieve_asset_id(algod: &Algod, tx: &SignedTransaction) -> Result<u64> {
-    let p_tx = send_and_wait_for_pending_tx(algod, tx).await?;
-    p_tx.asset_index
-        .ok_or_else(|| anyhow!("Shares asset id in pending tx not set"))
+/// Sends the tx and waits for it to be confirmed
+pub(crate) async fn send_and_retrieve_asset_id(
+    algod: &dyn DaoAlgod,
+    tx: &SignedTransaction,
+) -> Result<u64> {
+    send_and_wait(algod, tx).await?.created_asset_id()
 }
 
-async fn send_and_retrieve_app_id(algod: &Algod, tx: &SignedTransaction) -> Result<DaoAppId> {
-    let p_tx = send_and_wait_for_pending_tx(algod, tx).await?;
-    Ok(DaoAppId(
-        p_tx.application_index
-            .ok_or_else(|| anyhow!("App id in pending tx not set"))?,
-    ))
+/// Sends the tx and waits for it to be confirmed
+pub(crate) async fn send_and_retrieve_app_id(
+    algod: &dyn DaoAlgod,
+    tx: &SignedTransaction,
+) -> Result<DaoAppId> {
+    Ok(DaoAppId(send_and_wait(algod, tx).await?.created_app_id()?))
 }
 
-async fn send_and_wait_for_pending_tx(
-    algod: &Algod,
-    tx: &SignedTransaction,
-) -> Result<PendingTransaction> {
+async fn send_and_wait(algod: &dyn DaoAlgod, tx: &SignedTransaction) -> Result<Confirmation> {
     let res = algod.broadcast_signed_transaction(tx).await?;
-    wait_for_pending_transaction(algod, &res.tx_id.parse()?)
-        .await?
-        .ok_or_else(|| anyhow!("No pending tx to retrieve asset_od"))
+    Submission::from_response(&res)?.wait(algod).await
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -123,24 +153,73 @@ pub struct CreateImageNftSigned {
     pub cid: Cid,
 }
 
+impl Sign for CreateAssetsToSign {
+    type Signed = CreateDaoAssetsSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<CreateDaoAssetsSigned> {
+        Ok(CreateDaoAssetsSigned {
+            create_shares: signer.sign_tx(self.create_shares_tx)?,
+            create_app: signer.sign_tx(self.create_app_tx)?,
+        })
+    }
+}
+
+impl SignedTxs for CreateDaoAssetsSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.create_shares.clone(), self.create_app.clone()]
+    }
+}
+
+impl Sign for CreateImageNftToSign {
+    type Signed = CreateImageNftSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<CreateImageNftSigned> {
+        Ok(CreateImageNftSigned {
+            tx: signer.sign_tx(self.tx)?,
+            cid: self.cid,
+        })
+    }
+}
+
+impl SignedTxs for CreateImageNftSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.tx.clone()]
+    }
+}
+
 #[derive(Debug)]
 pub struct CreateAssetsResult {
     pub shares_asset_id: u64,
     pub app_id: DaoAppId,
 }
 
-async fn create_shares_tx(
+/// Fails if the config isn't valid (see [ensure_valid_shares_config])
+pub async fn create_shares_tx(
     params: &SuggestedTransactionParams,
     shares_specs: &CreateSharesSpecs,
+    config: &SharesAssetConfig,
     creator: Address,
 ) -> Result<Transaction> {
+    ensure_valid_shares_config(config)?;
+
     let unit_and_asset_name = shares_specs.token_name.to_owned();
-    Ok(TxnBuilder::with(
-        params,
-        CreateAsset::new(creator, shares_specs.supply.val(), 0, false)
+    let mut create_asset =
+        CreateAsset::new(creator, shares_specs.supply.val(), config.decimals, false)
             .unit_name(unit_and_asset_name.clone())
-            .asset_name(unit_and_asset_name)
-            .build(),
-    )
-    .build()?)
+            .asset_name(unit_and_asset_name);
+
+    if let Some(url) = &config.url {
+        create_asset = create_asset.url(url.clone());
+    }
+    if let Some(hash) = config.metadata_hash {
+        create_asset = create_asset.meta_data_hash(hash.to_vec());
+    }
+    if let Some(manager) = config.manager {
+        create_asset = create_asset.manager(manager);
+    }
+    if let Some(reserve) = config.reserve {
+        create_asset = create_asset.reserve(reserve);
+    }
+
+    Ok(TxnBuilder::with(params, create_asset.build()).build()?)
 }
@@ -0,0 +1,115 @@
+use super::create_app::create_app_tx;
+use crate::{
+    compile_cache::CompileCache,
+    dao_algod::DaoAlgod,
+    flows::create_dao::model::{CreateAppSigned, CreateAppToSign},
+    state::account_state::asset_holdings_from_account_if_opted_in,
+    submission::Submission,
+};
+use algonaut::core::Address;
+use anyhow::{anyhow, Result};
+use mbase::{
+    api::version::VersionedTealSourceTemplate,
+    models::{
+        capi_deps::CapiAssetDaoDeps, funds::FundsAmount, setup_dao_specs::SetupDaoSpecs,
+        share_amount::ShareAmount,
+    },
+};
+
+/// Checks that an asset created outside of Capi can be used as the DAO's shares asset:
+/// its supply has to be the specs' supply (the app is rendered with it)
+/// and the creator has to hold the shares for investors (they're transferred to the app during setup).
+/// Assets with a freeze or clawback address, or frozen by default, are rejected:
+/// their issuer could block or take the shares of the app and the investors.
+pub async fn verify_existing_shares_asset(
+    algod: &dyn DaoAlgod,
+    creator: &Address,
+    shares_asset_id: u64,
+    specs: &SetupDaoSpecs,
+) -> Result<()> {
+    let asset = algod.asset_information(shares_asset_id).await?;
+    if let Some(freeze) = &asset.params.freeze {
+        return Err(anyhow!(
+            "Shares asset: {shares_asset_id} has a freeze address: {freeze}"
+        ));
+    }
+    if let Some(clawback) = &asset.params.clawback {
+        return Err(anyhow!(
+            "Shares asset: {shares_asset_id} has a clawback address: {clawback}"
+        ));
+    }
+    if asset.params.default_frozen.unwrap_or(false) {
+        return Err(anyhow!(
+            "Shares asset: {shares_asset_id} is frozen by default"
+        ));
+    }
+    if asset.params.total != specs.shares.supply.val() {
+        return Err(anyhow!(
+            "Shares asset: {shares_asset_id} supply: {} doesn't match the specs supply: {}",
+            asset.params.total,
+            specs.shares.supply
+        ));
+    }
+
+    let creator_infos = algod.account_information(creator).await?;
+    let holdings = ShareAmount::new(
+        asset_holdings_from_account_if_opted_in(&creator_infos, shares_asset_id)?.0,
+    );
+    let for_investors = specs.shares_for_investors();
+    if holdings.val() < for_investors.val() {
+        return Err(anyhow!(
+            "Creator: {creator} holds: {holdings} of shares asset: {shares_asset_id}, needs: {for_investors} (the shares for investors)"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Like [super::create_shares::create_assets], but only creates the app: the DAO uses an existing asset as shares asset.
+/// Pass `shares_asset_id` to the setup (see [crate::flows::create_dao::setup_dao::setup_dao_txs]).
+#[allow(clippy::too_many_arguments)]
+pub async fn create_app_for_existing_shares(
+    algod: &dyn DaoAlgod,
+    creator: &Address,
+    specs: &SetupDaoSpecs,
+    shares_asset_id: u64,
+    app_approval: &VersionedTealSourceTemplate,
+    app_clear: &VersionedTealSourceTemplate,
+    precision: u64,
+    capi_deps: &CapiAssetDaoDeps,
+    max_raisable_amount: FundsAmount,
+    compile_cache: &CompileCache,
+) -> Result<CreateAppToSign> {
+    verify_existing_shares_asset(algod, creator, shares_asset_id, specs).await?;
+
+    let params = algod.suggested_transaction_params().await?;
+    let create_app_tx = create_app_tx(
+        algod,
+        app_approval,
+        app_clear,
+        creator,
+        specs.shares.supply,
+        precision,
+        specs.investors_share,
+        &params,
+        capi_deps,
+        specs.share_price,
+        max_raisable_amount,
+        compile_cache,
+    )
+    .await?;
+
+    Ok(CreateAppToSign { create_app_tx })
+}
+
+/// Broadcasts the app creation. The app id is known when it's confirmed, see [crate::submission::Confirmation::created_app_id].
+pub async fn submit_create_app(
+    algod: &dyn DaoAlgod,
+    signed: &CreateAppSigned,
+) -> Result<Submission> {
+    log::debug!("Submitting create app..");
+    let res = algod
+        .broadcast_signed_transaction(&signed.create_app)
+        .await?;
+    Submission::from_response(&res)
+}
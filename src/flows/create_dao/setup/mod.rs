@@ -1,3 +1,4 @@
 pub mod create_app;
 pub mod create_shares;
+pub mod existing_shares;
 pub mod setup_app;
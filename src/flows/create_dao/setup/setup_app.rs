@@ -1,9 +1,12 @@
-use crate::note::dao_setup_prefix;
+use crate::{
+    image_metadata::{image_nft_arg, ImageNftMetadata},
+    note::dao_setup_prefix,
+};
 use algonaut::{
     core::{Address, SuggestedTransactionParams},
     transaction::{builder::CallApplication, Transaction, TxnBuilder},
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use mbase::{
     api::version::{versions_to_bytes, Version, Versions},
     models::{
@@ -32,7 +35,10 @@ pub struct DaoInitData {
     pub share_price: FundsAmount,
     pub investors_share: SharesPercentage,
 
+    /// The DAO's image
     pub image_nft_url: Option<String>,
+    /// The NFT is created with it, if set. Requires the image url.
+    pub image_nft_metadata: Option<ImageNftMetadata>,
     pub social_media_url: String,
 
     pub min_raise_target: FundsAmount,
@@ -63,6 +69,24 @@ pub async fn setup_app_tx(
 ) -> Result<Transaction> {
     log::debug!("Setting up app: {app_id:?}");
 
+    let tx = TxnBuilder::with(
+        params,
+        CallApplication::new(*creator, app_id.0)
+            .app_arguments(setup_app_args(data)?)
+            .foreign_assets(vec![data.funds_asset_id.0, data.shares_asset_id])
+            .build(),
+    )
+    // TODO: consider enforcing in TEAL that this note is being set
+    // for now it's used only as a helper to filter "daos created by me" (via indexer)
+    // so it doesn't need to be secure (it's in the interest of the user / they don't gain anything by omitting it)
+    // but maybe this usage changes
+    .note(dao_setup_prefix().to_vec())
+    .build()?;
+    Ok(tx)
+}
+
+/// The setup app call's arguments, in the order expected by TEAL
+pub fn setup_app_args(data: &DaoInitData) -> Result<Vec<Vec<u8>>> {
     let mut args = vec![
         data.shares_asset_id.to_be_bytes().to_vec(),
         data.funds_asset_id.0.to_be_bytes().to_vec(),
@@ -84,24 +108,15 @@ pub async fn setup_app_tx(
         data.max_invest_shares.val().to_be_bytes().to_vec(),
     ];
 
-    if let Some(image_nft_url) = &data.image_nft_url {
-        args.push(image_nft_url.as_bytes().to_vec());
+    match (&data.image_nft_url, &data.image_nft_metadata) {
+        (Some(image_nft_url), metadata) => {
+            args.push(image_nft_arg(image_nft_url, metadata.as_ref())?);
+        }
+        (None, Some(_)) => return Err(anyhow!("Image nft metadata without image url")),
+        (None, None) => {}
     }
 
-    let tx = TxnBuilder::with(
-        params,
-        CallApplication::new(*creator, app_id.0)
-            .app_arguments(args)
-            .foreign_assets(vec![data.funds_asset_id.0, data.shares_asset_id])
-            .build(),
-    )
-    // TODO: consider enforcing in TEAL that this note is being set
-    // for now it's used only as a helper to filter "daos created by me" (via indexer)
-    // so it doesn't need to be secure (it's in the interest of the user / they don't gain anything by omitting it)
-    // but maybe this usage changes
-    .note(dao_setup_prefix().to_vec())
-    .build()?;
-    Ok(tx)
+    Ok(args)
 }
 
 /// if none, defaults to empty string, and converts to bytes
@@ -1,15 +1,18 @@
 use super::model::{SetupDaoSigned, SetupDaoToSign, SubmitSetupDaoResult};
 use crate::{
-    algo_helpers::wait_for_p_tx_with_id,
     common_txs::pay,
+    dao_algod::DaoAlgod,
     flows::create_dao::{
-        model::Dao,
+        model::{Dao, DaoStatus},
         setup::setup_app::{setup_app_tx, DaoInitData},
     },
+    image_metadata::ImageNftMetadata,
+    min_balance::dao_app_escrow_min_balance,
+    signer::SignedTxs,
+    submission::Submission,
 };
 use algonaut::{
-    algod::v2::Algod,
-    core::{to_app_address, Address, MicroAlgos},
+    core::{to_app_address, Address},
     model::algod::v2::PendingTransaction,
     transaction::{tx_group::TxGroup, TransferAsset, TxnBuilder},
 };
@@ -25,11 +28,14 @@ use mbase::{
     },
     state::dao_app_state::Prospectus,
 };
-use std::fmt::Debug;
+use std::{convert::TryInto, fmt::Debug};
+
+/// Inner txs of the setup app call: opt-ins to the shares and funds asset and the optional image nft creation
+pub const SETUP_INNER_TXS: u64 = 3;
 
 #[allow(clippy::too_many_arguments)]
 pub async fn setup_dao_txs(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     specs: &SetupDaoSpecs,
     creator: Address,
     shares_asset_id: u64,
@@ -39,6 +45,8 @@ pub async fn setup_dao_txs(
     app_id: DaoAppId,
     // TODO remove? it's in the specs already?
     image_nft_url: Option<String>,
+    // the image nft is created with it, if set (see [crate::image_metadata])
+    image_nft_metadata: Option<ImageNftMetadata>,
     prospectus: Option<Prospectus>,
 ) -> Result<SetupDaoToSign> {
     log::debug!(
@@ -78,12 +86,13 @@ pub async fn setup_dao_txs(
             descr_url: specs.descr_url.clone(),
             share_price: specs.share_price,
             investors_share: specs.investors_share,
-            image_nft_url,
+            image_nft_url: image_nft_url.clone(),
+            image_nft_metadata,
             social_media_url: specs.social_media_url.clone(),
             min_raise_target: specs.raise_min_target,
             min_raise_target_end_date: specs.raise_end_date,
             setup_date,
-            prospectus,
+            prospectus: prospectus.clone(),
             min_invest_shares: specs.min_invest_amount,
             max_invest_shares: specs.max_invest_amount,
         },
@@ -91,10 +100,14 @@ pub async fn setup_dao_txs(
     .await?;
 
     let app_address = to_app_address(app_id.0);
-    // min balance to hold 3 assets (shares, funds asset, optional image nft)
-    let mut fund_app_tx = pay(&params, &creator, &app_address, MicroAlgos(400_000))?;
-    // pay the opt-in inner tx fees (shares, funds asset and optional create image nft) (arbitrarily with this tx - could be any other in this group)
-    fund_app_tx.fee = fund_app_tx.fee * 4;
+    let mut fund_app_tx = pay(
+        &params,
+        &creator,
+        &app_address,
+        dao_app_escrow_min_balance(),
+    )?;
+    // pay the inner tx fees (arbitrarily with this tx - could be any other in this group)
+    fund_app_tx.fee = fund_app_tx.fee * (1 + SETUP_INNER_TXS);
 
     TxGroup::assign_group_id(&mut [
         &mut fund_app_tx,
@@ -105,6 +118,10 @@ pub async fn setup_dao_txs(
     Ok(SetupDaoToSign {
         specs: specs.to_owned(),
         creator,
+        shares_asset_id,
+        app_id,
+        funds_asset_id,
+        image_url: image_nft_url,
 
         fund_app_tx,
         setup_app_tx,
@@ -112,13 +129,16 @@ pub async fn setup_dao_txs(
         transfer_shares_to_app_tx,
 
         setup_date,
+
+        prospectus,
     })
 }
 
+/// Broadcasts the setup. The DAO is known when the setup is confirmed, see [SetupDaoSubmission::wait].
 pub async fn submit_setup_dao(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     signed: SetupDaoSigned,
-) -> Result<SubmitSetupDaoResult> {
+) -> Result<SetupDaoSubmission> {
     // crate::debug_msg_pack_submit_par::log_to_msg_pack(&signed);
     log::debug!(
         "Submitting dao setup, specs: {:?}, creator: {:?}",
@@ -128,13 +148,8 @@ pub async fn submit_setup_dao(
 
     let app_call_tx_id = signed.setup_app_tx.transaction.id()?;
 
-    let signed_txs = vec![
-        signed.app_funding_tx,
-        signed.setup_app_tx,
-        signed.transfer_shares_to_app_tx,
-    ];
+    let signed_txs = signed.signed_txs();
 
-    // crate::dryrun_util::dryrun_all(algod, &signed_txs).await?;
     // mbase::teal::debug_teal_rendered(&signed_txs, "dao_app_approval").unwrap();
 
     let _ = algod
@@ -142,37 +157,62 @@ pub async fn submit_setup_dao(
         .await?
         .tx_id;
 
-    let p_tx = wait_for_p_tx_with_id(algod, &app_call_tx_id.parse()?).await?;
-    let image_nft = to_nft(&p_tx, signed.image_url)?;
-
-    Ok(SubmitSetupDaoResult {
-        dao: Dao {
-            shares_asset_id: signed.shares_asset_id,
-            funds_asset_id: signed.funds_asset_id,
-            app_id: signed.app_id,
-            owner: signed.creator,
-            name: signed.specs.name,
-            descr_url: signed.specs.descr_url,
-            token_name: signed.specs.shares.token_name,
-            token_supply: signed.specs.shares.supply,
-            investors_share: signed.specs.investors_share,
-            share_price: signed.specs.share_price,
-            image_nft,
-            social_media_url: signed.specs.social_media_url,
-            raise_end_date: signed.specs.raise_end_date,
-            raise_min_target: signed.specs.raise_min_target,
-            raised: FundsAmount::new(0), // dao is just being setup - nothing raised yet
-            setup_date: signed.setup_date,
-            prospectus: signed.prospectus,
-            min_invest_amount: signed.specs.min_invest_amount,
-            max_invest_amount: signed.specs.max_invest_amount,
-            // we don't create team during dao creation process (too many steps (ux) + it would require a new tx as current tx arg limit is reached)
-            // owner can go to team view after and create it there
-            team_url: None,
-        },
+    Ok(SetupDaoSubmission {
+        // the setup app call creates the image nft
+        submission: Submission::new(app_call_tx_id.parse()?),
+        signed,
     })
 }
 
+#[derive(Debug, Clone)]
+pub struct SetupDaoSubmission {
+    pub submission: Submission,
+    signed: SetupDaoSigned,
+}
+
+impl SetupDaoSubmission {
+    /// Waits for the setup's confirmation and returns the created DAO
+    pub async fn wait(self, algod: &dyn DaoAlgod) -> Result<SubmitSetupDaoResult> {
+        let signed = self.signed;
+        let confirmation = self.submission.wait(algod).await?;
+        let image_nft = to_nft(&confirmation.pending_tx, signed.image_url)?;
+        // the decimals aren't in the specs, and the shares asset may have been created outside of Capi (with another name)
+        let shares_asset = algod.asset_information(signed.shares_asset_id).await?;
+
+        Ok(SubmitSetupDaoResult {
+            dao: Dao {
+                shares_asset_id: signed.shares_asset_id,
+                funds_asset_id: signed.funds_asset_id,
+                app_id: signed.app_id,
+                owner: signed.creator,
+                name: signed.specs.name,
+                descr_url: signed.specs.descr_url,
+                token_name: shares_asset
+                    .params
+                    .name
+                    .unwrap_or(signed.specs.shares.token_name),
+                token_supply: signed.specs.shares.supply,
+                token_decimals: shares_asset.params.decimals.try_into()?,
+                investors_share: signed.specs.investors_share,
+                share_price: signed.specs.share_price,
+                image_nft,
+                social_media_url: signed.specs.social_media_url,
+                raise_end_date: signed.specs.raise_end_date,
+                raise_min_target: signed.specs.raise_min_target,
+                raised: FundsAmount::new(0), // dao is just being setup - nothing raised yet
+                setup_date: signed.setup_date,
+                prospectus: signed.prospectus,
+                min_invest_amount: signed.specs.min_invest_amount,
+                max_invest_amount: signed.specs.max_invest_amount,
+                // we don't create team during dao creation process (too many steps (ux) + it would require a new tx as current tx arg limit is reached)
+                // owner can go to team view after and create it there
+                team_url: None,
+                status: DaoStatus::Active,
+            },
+        })
+    }
+}
+
 /// creates nft (optional) instance with the created asset (in teal) from inner txs and optional url
 /// if the state is inconsistent (e.g. there's no url but there's a created asset or vice versa) returns an error
 /// assumes p_tx to be setup dao tx (which creates the nft asset via inner tx, if the optional nft url arg is set)
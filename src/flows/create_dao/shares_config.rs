@@ -0,0 +1,53 @@
+use algonaut::core::Address;
+use anyhow::{anyhow, Result};
+use mbase::models::share_amount::ShareAmount;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use serde::{Deserialize, Serialize};
+
+/// Parameters of the shares asset besides its name and supply (see `CreateSharesSpecs`).
+/// The default creates the asset as before: no decimals, url, metadata or roles.
+///
+/// Note that share amounts (`ShareAmount`) are in base units everywhere in the crate, also the supply, prices and invest limits:
+/// with decimals, a share is `10^decimals` base units. Use [shares_to_base_units] / [base_units_to_shares] to convert.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharesAssetConfig {
+    pub decimals: u32,
+    /// E.g. an ARC-3 ("<url>#arc3") or ARC-19 ("template-ipfs://...") metadata url
+    pub url: Option<String>,
+    /// Hash of the metadata the url points to (for ARC-3, the SHA-256 of the metadata JSON)
+    pub metadata_hash: Option<[u8; 32]>,
+    /// Can reconfigure or destroy the asset. Without it the asset can't be changed anymore.
+    pub manager: Option<Address>,
+    /// Informative: holds the supply that's not in circulation. ARC-19 stores the metadata CID here.
+    pub reserve: Option<Address>,
+    /// Not allowed, like [clawback](Self::clawback) (see validate_shares_config):
+    /// the address could block the shares of the app and the investors.
+    pub freeze: Option<Address>,
+    /// Not allowed: the address could take the shares of the app and the investors.
+    pub clawback: Option<Address>,
+}
+
+pub fn shares_to_base_units(shares: Decimal, decimals: u32) -> Result<ShareAmount> {
+    let base_units = shares
+        .checked_mul(Decimal::from(unit(decimals)?))
+        .ok_or_else(|| anyhow!("Overflow converting: {shares} shares to base units"))?;
+    if !base_units.fract().is_zero() {
+        return Err(anyhow!(
+            "Shares: {shares} have more than: {decimals} decimals"
+        ));
+    }
+    Ok(ShareAmount::new(base_units.to_u64().ok_or_else(|| {
+        anyhow!("Shares: {shares} can't be represented in base units")
+    })?))
+}
+
+pub fn base_units_to_shares(amount: ShareAmount, decimals: u32) -> Result<Decimal> {
+    Ok(Decimal::from(amount.val()) / Decimal::from(unit(decimals)?))
+}
+
+/// Base units per share
+fn unit(decimals: u32) -> Result<u64> {
+    10u64
+        .checked_pow(decimals)
+        .ok_or_else(|| anyhow!("Invalid decimals: {decimals}"))
+}
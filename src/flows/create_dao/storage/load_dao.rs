@@ -1,10 +1,15 @@
-use crate::flows::create_dao::model::Dao;
-use algonaut::algod::v2::Algod;
-use anyhow::Result;
-use mbase::{
-    models::{dao_id::DaoId, share_amount::ShareAmount},
+use crate::{
+    dao_algod::{is_app_not_found, DaoAlgod},
+    dao_indexer::DaoIndexer,
+    flows::{
+        close_dao::close_dao::{is_closing, load_closed_dao},
+        create_dao::model::{Dao, DaoStatus},
+    },
     state::dao_app_state::dao_global_state,
 };
+use anyhow::Result;
+use mbase::models::{dao_id::DaoId, share_amount::ShareAmount};
+use std::convert::TryInto;
 
 /// NOTE: this is an expensive function:
 /// - Call to load dao app state
@@ -12,7 +17,11 @@ use mbase::{
 /// - Call to retrieve asset information (supply etc, using the asset id stored in the app state)
 /// - Calls to render and compile ALL the escrows (parallelized - 2 batches)
 /// TODO parallelize more (and outside of this function, try to cache the dao, etc. to not have to call this often)
-pub async fn load_dao(algod: &Algod, dao_id: DaoId) -> Result<Dao> {
+///
+/// Closed DAOs don't have an app anymore, so this fails for them: see [load_dao_or_closed].
+/// The status is always [DaoStatus::Active], also for closing DAOs: the closing is recorded in the indexer,
+/// so the status is valid only from [load_dao_or_closed].
+pub async fn load_dao(algod: &dyn DaoAlgod, dao_id: DaoId) -> Result<Dao> {
     let app_id = dao_id.0;
 
     log::debug!("Fetching dao with id: {:?}", app_id);
@@ -32,6 +41,7 @@ pub async fn load_dao(algod: &Algod, dao_id: DaoId) -> Result<Dao> {
         descr_url: dao_state.project_desc_url.clone(),
         token_name: asset_infos.params.name.unwrap_or_else(|| "".to_owned()),
         token_supply: ShareAmount::new(asset_infos.params.total),
+        token_decimals: asset_infos.params.decimals.try_into()?,
         investors_share: dao_state.investors_share,
         share_price: dao_state.share_price,
         image_nft: dao_state.image_nft.clone(),
@@ -44,7 +54,33 @@ pub async fn load_dao(algod: &Algod, dao_id: DaoId) -> Result<Dao> {
         min_invest_amount: dao_state.min_invest_amount,
         max_invest_amount: dao_state.max_invest_amount,
         team_url: dao_state.team_url,
+        status: DaoStatus::Active,
     };
 
     Ok(dao)
 }
+
+/// Loads the DAO with its status: closing DAOs with [DaoStatus::Closing],
+/// closed DAOs (their app was deleted) from the data saved when closing, with [DaoStatus::Closed].
+pub async fn load_dao_or_closed(
+    algod: &dyn DaoAlgod,
+    indexer: &dyn DaoIndexer,
+    dao_id: DaoId,
+) -> Result<Dao> {
+    match load_dao(algod, dao_id).await {
+        Ok(dao) => {
+            let status = if is_closing(indexer, &dao).await? {
+                DaoStatus::Closing
+            } else {
+                DaoStatus::Active
+            };
+            Ok(Dao { status, ..dao })
+        }
+        // other errors (e.g. network) don't mean that the app was deleted
+        Err(e) if is_app_not_found(&e) => match load_closed_dao(indexer, dao_id).await? {
+            Some(dao) => Ok(dao),
+            None => Err(e),
+        },
+        Err(e) => Err(e),
+    }
+}
@@ -1,26 +1,66 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        flows::create_dao::model::Dao,
+        compile_cache::{CompileCache, MemoryCompileStore},
+        dao_algod::fake::FakeAlgod,
+        flows::create_dao::{
+            model::Dao,
+            orchestrator::{
+                create_dao, create_dao_step, find_partial_daos, CreateDaoCheckpoint,
+                CreateDaoInputs, CreateDaoStep, PartialDaos,
+            },
+            setup::{
+                create_shares::send_and_retrieve_asset_id,
+                existing_shares::verify_existing_shares_asset,
+            },
+            setup_dao::Programs,
+            shares_config::{base_units_to_shares, shares_to_base_units, SharesAssetConfig},
+            storage::load_dao::load_dao,
+        },
+        signer::TxSigner,
         state::{
             account_state::{asset_holdings, find_asset_holding_or_err},
             dao_shares::dao_shares,
         },
+        teal_registry::{dao_teal_registry, ApprovalTemplateParams},
         testing::{
-            flow::create_dao_flow::create_dao_flow,
+            fixtures::FIXTURE_FUNDS_ASSET_ID,
+            flow::create_dao_flow::{
+                create_dao_flow,
+                test::{create_dao_flow_with_shares_config, test_programs},
+            },
             network_test_util::{test_dao_init, TestDeps},
         },
     };
-    use algonaut::algod::v2::Algod;
+    use algonaut::{
+        algod::v2::Algod,
+        core::Address,
+        transaction::{account::Account, CreateAsset, TxnBuilder},
+    };
     use anyhow::Result;
+    use chrono::{Duration, Utc};
+    use data_encoding::BASE64;
     use mbase::{
-        models::{asset_amount::AssetAmount, funds::FundsAmount, share_amount::ShareAmount},
+        api::version::{Version, VersionedTealSourceTemplate},
+        models::{
+            asset_amount::AssetAmount,
+            capi_deps::{CapiAddress, CapiAssetDaoDeps},
+            create_shares_specs::CreateSharesSpecs,
+            dao_app_id::DaoAppId,
+            funds::{FundsAmount, FundsAssetId},
+            setup_dao_specs::SetupDaoSpecs,
+            share_amount::ShareAmount,
+        },
         state::{
             app_state::ApplicationLocalStateError,
             dao_app_state::{dao_global_state, dao_investor_state},
         },
+        teal::TealSourceTemplate,
     };
+    use network_test_util::test_data::{investor1, investor2};
+    use rust_decimal::Decimal;
     use serial_test::serial;
+    use std::{convert::TryInto, str::FromStr};
     use tokio::test;
 
     #[test]
@@ -99,4 +139,521 @@ mod tests {
         assert_eq!(FundsAmount::new(0), state.received);
         Ok(())
     }
+
+    #[test]
+    #[serial] // reset network (cmd)
+    async fn test_create_dao_resumes_from_checkpoint() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let algod = &td.algod;
+        let inputs = create_dao_inputs(td)?;
+        let compile_cache = CompileCache::new(MemoryCompileStore::default());
+
+        // only the first step: as if the creation had failed after creating the shares
+        let checkpoint = create_dao_step(
+            algod,
+            &td.creator,
+            &inputs,
+            &compile_cache,
+            &CreateDaoCheckpoint::default(),
+        )
+        .await?;
+        assert_eq!(CreateDaoStep::CreateApp, checkpoint.next_step());
+
+        // the shares asset is detected as partial creation
+        let partial_daos = find_partial_daos(algod, &inputs, &compile_cache).await?;
+        assert_eq!(
+            checkpoint.shares_asset_id.into_iter().collect::<Vec<_>>(),
+            partial_daos.unused_shares_assets
+        );
+        assert!(partial_daos.apps_not_set_up.is_empty());
+        assert_eq!(Some(checkpoint.clone()), partial_daos.resume_checkpoint());
+
+        let mut saved_checkpoints = vec![];
+        let dao = create_dao(
+            algod,
+            &td.creator,
+            &inputs,
+            &compile_cache,
+            checkpoint.clone(),
+            |c| {
+                saved_checkpoints.push(c.clone());
+                Ok(())
+            },
+        )
+        .await?;
+
+        // the shares asset created before was used
+        assert_eq!(checkpoint.shares_asset_id, Some(dao.shares_asset_id));
+        // app created, dao set up
+        assert_eq!(2, saved_checkpoints.len());
+        assert_eq!(Some(dao.app_id), saved_checkpoints[0].app_id);
+        assert_eq!(Some(dao.clone()), saved_checkpoints[1].dao);
+
+        let creator_infos = algod.account_information(&td.creator.address()).await?;
+        assert_eq!(1, creator_infos.created_assets.len());
+        assert_eq!(1, creator_infos.created_apps.len());
+
+        test_global_app_state_setup_correctly(algod, &dao, td).await?;
+
+        // nothing partial anymore
+        assert!(find_partial_daos(algod, &inputs, &compile_cache)
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial] // reset network (cmd)
+    async fn test_create_dao_rejects_checkpoint_of_set_up_app() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let algod = &td.algod;
+        let inputs = create_dao_inputs(td)?;
+
+        let dao = create_dao(
+            algod,
+            &td.creator,
+            &inputs,
+            &CompileCache::new(MemoryCompileStore::default()),
+            CreateDaoCheckpoint::default(),
+            |_| Ok(()),
+        )
+        .await?;
+
+        // e.g. the setup was submitted, but the checkpoint wasn't saved
+        let res = create_dao(
+            algod,
+            &td.creator,
+            &inputs,
+            &CompileCache::new(MemoryCompileStore::default()),
+            CreateDaoCheckpoint::resume(Some(dao.shares_asset_id), Some(dao.app_id)),
+            |_| Ok(()),
+        )
+        .await;
+        assert!(res.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_checkpoint_next_step() {
+        let mut checkpoint = CreateDaoCheckpoint::default();
+        assert_eq!(CreateDaoStep::CreateShares, checkpoint.next_step());
+
+        checkpoint.shares_asset_id = Some(123);
+        assert_eq!(CreateDaoStep::CreateApp, checkpoint.next_step());
+
+        checkpoint.app_id = Some(DaoAppId(456));
+        assert_eq!(CreateDaoStep::SetupDao, checkpoint.next_step());
+
+        // an existing app can be resumed without shares asset: the shares are created first
+        assert_eq!(
+            CreateDaoStep::CreateShares,
+            CreateDaoCheckpoint::resume(None, Some(DaoAppId(456))).next_step()
+        );
+    }
+
+    #[test]
+    async fn test_checkpoint_serialization() -> Result<()> {
+        let checkpoint = CreateDaoCheckpoint::resume(Some(123), Some(DaoAppId(456)));
+
+        let serialized = serde_json::to_string(&checkpoint)?;
+        let deserialized: CreateDaoCheckpoint = serde_json::from_str(&serialized)?;
+
+        assert_eq!(checkpoint, deserialized);
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_resume_checkpoint_uses_most_recent_partial_creation() {
+        let partial_daos = PartialDaos {
+            apps_not_set_up: vec![DaoAppId(10), DaoAppId(20)],
+            unused_shares_assets: vec![15],
+        };
+
+        assert_eq!(
+            Some(CreateDaoCheckpoint::resume(Some(15), Some(DaoAppId(20)))),
+            partial_daos.resume_checkpoint()
+        );
+
+        let no_partial_daos = PartialDaos {
+            apps_not_set_up: vec![],
+            unused_shares_assets: vec![],
+        };
+        assert_eq!(None, no_partial_daos.resume_checkpoint());
+    }
+
+    /// Inputs with a template that renders all the approval's parameters
+    fn offline_create_dao_inputs(creator: Address) -> Result<CreateDaoInputs> {
+        let template = |source: &str| {
+            VersionedTealSourceTemplate::new(
+                TealSourceTemplate(source.as_bytes().to_vec()),
+                Version(1),
+            )
+        };
+        Ok(CreateDaoInputs {
+            creator,
+            specs: SetupDaoSpecs::new(
+                "Pancakes ltd".to_owned(),
+                None,
+                CreateSharesSpecs {
+                    token_name: "PCK".to_owned(),
+                    supply: ShareAmount::new(100),
+                },
+                Decimal::from_str("0.4")?.try_into()?,
+                FundsAmount::new(5_000_000),
+                None,
+                "https://twitter.com/capi_fin".to_owned(),
+                ShareAmount::new(80),
+                FundsAmount::new(0),
+                (Utc::now() + Duration::weeks(4)).into(),
+                None,
+                ShareAmount::new(1),
+                ShareAmount::new(100),
+            )?,
+            funds_asset_id: FundsAssetId(FIXTURE_FUNDS_ASSET_ID),
+            programs: Programs {
+                central_app_approval: template(
+                    r#"#pragma version 6
+int TMPL_SHARE_SUPPLY
+int TMPL_INVESTORS_SHARE
+int TMPL_PRECISION__
+int TMPL_PRECISION_SQUARE
+addr TMPL_CAPI_ESCROW_ADDRESS
+int TMPL_CAPI_SHARE
+int TMPL_SHARE_PRICE
+int TMPL_MAX_RAISABLE_AMOUNT"#,
+                ),
+                central_app_clear: template("#pragma version 6\nint 1"),
+            },
+            precision: 1_000,
+            capi_deps: CapiAssetDaoDeps {
+                escrow_percentage: Decimal::from_str("0.1")?.try_into()?,
+                address: CapiAddress(investor2().address()),
+            },
+            max_raisable_amount: FundsAmount::new(5_000_000_000),
+            shares_config: SharesAssetConfig::default(),
+            existing_shares_asset_id: None,
+            image_nft_metadata: None,
+        })
+    }
+
+    #[test]
+    async fn test_find_partial_daos_matches_the_specs() -> Result<()> {
+        let creator = investor1().address();
+        let inputs = offline_create_dao_inputs(creator)?;
+        let compile_cache = CompileCache::new(MemoryCompileStore::default());
+        // the fake algod's compiled program is the source
+        let approval = |inputs: &CreateDaoInputs| -> Result<Vec<u8>> {
+            Ok(dao_teal_registry()
+                .render_approval(
+                    &inputs.programs.central_app_approval,
+                    &ApprovalTemplateParams {
+                        share_supply: inputs.specs.shares.supply,
+                        precision: inputs.precision,
+                        investors_share: inputs.specs.investors_share,
+                        capi_address: CapiAddress(inputs.capi_deps.address.0),
+                        capi_percentage: inputs.capi_deps.escrow_percentage,
+                        share_price: inputs.specs.share_price,
+                        max_raisable_amount: inputs.max_raisable_amount,
+                    },
+                )?
+                .0)
+        };
+        let mut other_inputs = offline_create_dao_inputs(creator)?;
+        other_inputs.max_raisable_amount = FundsAmount::new(1_000_000_000);
+
+        let asset_json = |id: u64, name: &str, total: u64| {
+            format!(
+                r#"{{ "index": {id}, "params": {{ "creator": "{creator}", "decimals": 0, "total": {total}, "name": "{name}", "unit-name": "{name}" }} }}"#
+            )
+        };
+        let app_json = |id: u64, approval: &[u8]| {
+            let program = BASE64.encode(approval);
+            format!(
+                r#"{{ "id": {id}, "params": {{ "creator": "{creator}", "approval-program": "{program}", "clear-state-program": "{program}", "global-state-schema": {{ "num-byte-slice": 0, "num-uint": 0 }}, "local-state-schema": {{ "num-byte-slice": 0, "num-uint": 0 }} }} }}"#
+            )
+        };
+        let account = serde_json::from_str(&format!(
+            r#"{{
+                "address": "{creator}",
+                "amount": 10000000,
+                "amount-without-pending-rewards": 10000000,
+                "assets": [],
+                "created-apps": [{}, {}, {}],
+                "created-assets": [{}, {}, {}],
+                "pending-rewards": 0,
+                "reward-base": 0,
+                "rewards": 0,
+                "round": 1000,
+                "status": "Offline"
+            }}"#,
+            app_json(10, &approval(&inputs)?),
+            // same supply, rendered with another max raisable amount
+            app_json(20, &approval(&other_inputs)?),
+            // not a Capi app (an unknown opcode): skipped
+            app_json(40, &[0x06, 0x79]),
+            asset_json(15, "PCK", 100),
+            // name = unit name, but not the specs' name
+            asset_json(25, "OTHER", 100),
+            asset_json(30, "PCK", 1000),
+        ))?;
+        let algod = FakeAlgod::new().with_account(account);
+
+        let partial_daos = find_partial_daos(&algod, &inputs, &compile_cache).await?;
+
+        assert_eq!(vec![DaoAppId(10)], partial_daos.apps_not_set_up);
+        assert_eq!(vec![15], partial_daos.unused_shares_assets);
+        assert_eq!(
+            Some(CreateDaoCheckpoint::resume(Some(15), Some(DaoAppId(10)))),
+            partial_daos.resume_checkpoint()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial] // reset network (cmd)
+    async fn test_create_dao_with_existing_shares_asset() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let algod = &td.algod;
+        let shares_asset_id =
+            create_existing_asset(algod, &td.creator, td.specs.shares.supply.val()).await?;
+        let inputs = CreateDaoInputs {
+            existing_shares_asset_id: Some(shares_asset_id),
+            ..create_dao_inputs(td)?
+        };
+
+        let mut saved_checkpoints = vec![];
+        let dao = create_dao(
+            algod,
+            &td.creator,
+            &inputs,
+            &CompileCache::new(MemoryCompileStore::default()),
+            CreateDaoCheckpoint::default(),
+            |c| {
+                saved_checkpoints.push(c.clone());
+                Ok(())
+            },
+        )
+        .await?;
+
+        assert_eq!(shares_asset_id, dao.shares_asset_id);
+        assert_eq!("Existing token", dao.token_name);
+        // the existing asset is used: no asset created
+        assert_eq!(Some(shares_asset_id), saved_checkpoints[0].shares_asset_id);
+        let creator_infos = algod.account_information(&td.creator.address()).await?;
+        assert_eq!(1, creator_infos.created_assets.len());
+
+        let app_escrow_shares =
+            ShareAmount(asset_holdings(algod, &dao.app_address(), shares_asset_id).await?);
+        assert_eq!(td.specs.shares_for_investors(), app_escrow_shares);
+        test_global_app_state_setup_correctly(algod, &dao, td).await?;
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial] // reset network (cmd)
+    async fn test_create_dao_rejects_existing_asset_with_other_supply() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let algod = &td.algod;
+        let shares_asset_id =
+            create_existing_asset(algod, &td.creator, td.specs.shares.supply.val() + 1).await?;
+        let inputs = CreateDaoInputs {
+            existing_shares_asset_id: Some(shares_asset_id),
+            ..create_dao_inputs(td)?
+        };
+
+        let res = create_dao(
+            algod,
+            &td.creator,
+            &inputs,
+            &CompileCache::new(MemoryCompileStore::default()),
+            CreateDaoCheckpoint::default(),
+            |_| Ok(()),
+        )
+        .await;
+
+        assert!(res.is_err());
+        // nothing created
+        let creator_infos = algod.account_information(&td.creator.address()).await?;
+        assert!(creator_infos.created_apps.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial] // reset network (cmd)
+    async fn test_create_dao_rejects_existing_asset_not_held_by_creator() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let algod = &td.algod;
+        // investor1 holds the supply, the creator isn't opted in
+        let shares_asset_id =
+            create_existing_asset(algod, &investor1(), td.specs.shares.supply.val()).await?;
+        let inputs = CreateDaoInputs {
+            existing_shares_asset_id: Some(shares_asset_id),
+            ..create_dao_inputs(td)?
+        };
+
+        let res = create_dao(
+            algod,
+            &td.creator,
+            &inputs,
+            &CompileCache::new(MemoryCompileStore::default()),
+            CreateDaoCheckpoint::default(),
+            |_| Ok(()),
+        )
+        .await;
+
+        assert!(res.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial] // reset network (cmd)
+    async fn test_rejects_existing_asset_that_can_be_frozen_or_clawed_back() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let algod = &td.algod;
+        let creator = &td.creator;
+        let params = algod.suggested_transaction_params().await?;
+        let specs = CreateSharesSpecs {
+            token_name: "EXT".to_owned(),
+            supply: td.specs.shares.supply,
+        };
+
+        // create_shares_tx doesn't allow these roles
+        let asset = || {
+            CreateAsset::new(creator.address(), specs.supply.val(), 0, false)
+                .unit_name(specs.token_name.clone())
+        };
+        let with_freeze = asset().freeze(investor1().address()).build();
+        let with_clawback = asset().clawback(investor1().address()).build();
+        for create_asset in [with_freeze, with_clawback] {
+            let tx = TxnBuilder::with(&params, create_asset).build()?;
+            let asset_id = send_and_retrieve_asset_id(algod, &creator.sign_tx(tx)?).await?;
+            let res =
+                verify_existing_shares_asset(algod, &creator.address(), asset_id, &td.specs).await;
+            assert!(res.is_err());
+        }
+
+        let tx = TxnBuilder::with(
+            &params,
+            CreateAsset::new(creator.address(), specs.supply.val(), 0, true)
+                .unit_name("EXT".to_owned())
+                .build(),
+        )
+        .build()?;
+        let default_frozen_asset_id =
+            send_and_retrieve_asset_id(algod, &creator.sign_tx(tx)?).await?;
+        let res = verify_existing_shares_asset(
+            algod,
+            &creator.address(),
+            default_frozen_asset_id,
+            &td.specs,
+        )
+        .await;
+        assert!(res.is_err());
+
+        Ok(())
+    }
+
+    fn create_dao_inputs(td: &TestDeps) -> Result<CreateDaoInputs> {
+        Ok(CreateDaoInputs {
+            creator: td.creator.address(),
+            specs: td.specs.clone(),
+            funds_asset_id: td.funds_asset_id,
+            programs: test_programs()?,
+            precision: td.precision,
+            capi_deps: td.dao_deps(),
+            max_raisable_amount: FundsAmount::new(5_000_000_000_000),
+            shares_config: SharesAssetConfig::default(),
+            existing_shares_asset_id: None,
+            image_nft_metadata: None,
+        })
+    }
+
+    /// An asset created outside of Capi (the creator holds the supply)
+    async fn create_existing_asset(algod: &Algod, creator: &Account, supply: u64) -> Result<u64> {
+        let params = algod.suggested_transaction_params().await?;
+        let tx = TxnBuilder::with(
+            &params,
+            CreateAsset::new(creator.address(), supply, 0, false)
+                .unit_name("EXT".to_owned())
+                .asset_name("Existing token".to_owned())
+                .build(),
+        )
+        .build()?;
+        send_and_retrieve_asset_id(algod, &creator.sign_tx(tx)?).await
+    }
+
+    #[test]
+    #[serial] // reset network (cmd)
+    async fn test_create_dao_with_shares_config() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let algod = &td.algod;
+
+        let config = SharesAssetConfig {
+            decimals: 2,
+            url: Some("https://example.com/shares.json#arc3".to_owned()),
+            metadata_hash: Some([1; 32]),
+            manager: Some(investor1().address()),
+            ..SharesAssetConfig::default()
+        };
+        let dao = create_dao_flow_with_shares_config(td, &config).await?;
+
+        let asset = algod.asset_information(dao.shares_asset_id).await?;
+        assert_eq!(2, asset.params.decimals);
+        assert_eq!(config.url, asset.params.url);
+        assert_eq!(
+            Some(investor1().address().to_string()),
+            asset.params.manager.map(|a| a.to_string())
+        );
+        // the supply is in base units
+        assert_eq!(td.specs.shares.supply.val(), asset.params.total);
+
+        assert_eq!(2, dao.token_decimals);
+        assert_eq!(2, load_dao(algod, dao.id()).await?.token_decimals);
+
+        // the shares are distributed in base units, as without decimals
+        let app_escrow_shares =
+            ShareAmount(asset_holdings(algod, &dao.app_address(), dao.shares_asset_id).await?);
+        assert_eq!(td.specs.shares_for_investors(), app_escrow_shares);
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_shares_to_base_units() -> Result<()> {
+        assert_eq!(
+            ShareAmount::new(150),
+            shares_to_base_units(Decimal::from_str("1.5")?, 2)?
+        );
+        assert_eq!(
+            ShareAmount::new(3),
+            shares_to_base_units(Decimal::from(3), 0)?
+        );
+        // more decimals than the asset has
+        assert!(shares_to_base_units(Decimal::from_str("1.555")?, 2).is_err());
+        assert!(shares_to_base_units(Decimal::from_str("0.5")?, 0).is_err());
+        // overflow
+        assert!(shares_to_base_units(Decimal::from(u64::MAX), 2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    async fn test_base_units_to_shares() -> Result<()> {
+        assert_eq!(
+            Decimal::from_str("1.5")?,
+            base_units_to_shares(ShareAmount::new(150), 2)?
+        );
+        assert_eq!(
+            Decimal::from(7),
+            base_units_to_shares(ShareAmount::new(7), 0)?
+        );
+        assert!(base_units_to_shares(ShareAmount::new(1), 20).is_err());
+        Ok(())
+    }
 }
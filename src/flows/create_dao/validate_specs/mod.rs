@@ -0,0 +1,335 @@
+use super::{
+    setup::setup_app::{setup_app_args, DaoInitData},
+    shares_config::SharesAssetConfig,
+};
+use crate::image_metadata::ImageNftMetadata;
+use anyhow::{anyhow, Result};
+use mbase::{
+    api::version::Version,
+    models::{
+        funds::FundsAssetId, setup_dao_specs::SetupDaoSpecs, share_amount::ShareAmount,
+        timestamp::Timestamp,
+    },
+};
+use std::fmt;
+
+mod tests;
+
+// Protocol limits, see https://developer.algorand.org/docs/get-details/parameter_tables/
+pub const MAX_APP_ARGS: usize = 16;
+pub const MAX_APP_TOTAL_ARG_LEN: usize = 2048;
+/// Key and value of a global state entry share this limit: the args saved in the state can't be longer than it minus the key.
+pub const MAX_APP_KEY_VALUE_LEN: usize = 128;
+pub const MAX_NOTE_LEN: usize = 1024;
+pub const MAX_ASSET_NAME_LEN: usize = 32;
+pub const MAX_ASSET_UNIT_NAME_LEN: usize = 8;
+pub const MAX_ASSET_URL_LEN: usize = 96;
+pub const MAX_ASSET_DECIMALS: u32 = 19;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecsField {
+    Name,
+    DescrUrl,
+    /// Used as asset and unit name of the shares asset
+    TokenName,
+    ShareSupply,
+    SharePrice,
+    /// Also the url of the image NFT, if it's created without metadata
+    ImageUrl,
+    /// The url of the image NFT, see [ImageNftMetadata]
+    ImageNftMetadataUrl,
+    SocialMediaUrl,
+    ProspectusUrl,
+    ProspectusHash,
+    MinInvestAmount,
+    MaxInvestAmount,
+    RaiseEndDate,
+    SharesDecimals,
+    SharesUrl,
+    SharesFreeze,
+    SharesClawback,
+    /// The setup app call's arguments as a whole
+    AppArgs,
+}
+
+impl SpecsField {
+    /// Key of the global state entry where the app saves the field, if it's saved as bytes.
+    /// These are the approval template's keys (read with mbase's dao_app_state).
+    pub fn global_state_key(&self) -> Option<&'static str> {
+        match self {
+            SpecsField::Name => Some("DaoName"),
+            SpecsField::DescrUrl => Some("DaoDesc"),
+            SpecsField::ImageUrl => Some("ImageUrl"),
+            SpecsField::SocialMediaUrl => Some("SocialMediaUrl"),
+            SpecsField::ProspectusUrl => Some("ProspectusUrl"),
+            SpecsField::ProspectusHash => Some("ProspectusHash"),
+            _ => None,
+        }
+    }
+
+    /// Max length of the field's value in the global state, if it's saved there
+    pub fn max_global_state_len(&self) -> Option<usize> {
+        self.global_state_key()
+            .map(|key| MAX_APP_KEY_VALUE_LEN - key.len())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecsError {
+    TooLong {
+        field: SpecsField,
+        max: usize,
+        actual: usize,
+    },
+    TooManyAppArgs {
+        max: usize,
+        actual: usize,
+    },
+    MinInvestGreaterThanMax {
+        min: ShareAmount,
+        max: ShareAmount,
+    },
+    MinInvestGreaterThanSharesForSale {
+        min: ShareAmount,
+        for_sale: ShareAmount,
+    },
+    /// The app accepts it (it means that there's no funds raising period), but it's not expected when creating a DAO
+    RaiseEndDateNotInFuture {
+        end_date: Timestamp,
+    },
+    /// The price of all the shares can't be represented
+    SupplyPriceOverflow,
+    TooManyDecimals {
+        max: u32,
+        actual: u32,
+    },
+    /// A shares asset role that would let its address block or take the shares (like for existing shares assets)
+    NotAllowed {
+        field: SpecsField,
+    },
+}
+
+impl SpecsError {
+    pub fn field(&self) -> SpecsField {
+        match self {
+            SpecsError::TooLong { field, .. } | SpecsError::NotAllowed { field } => *field,
+            SpecsError::TooManyAppArgs { .. } => SpecsField::AppArgs,
+            SpecsError::MinInvestGreaterThanMax { .. }
+            | SpecsError::MinInvestGreaterThanSharesForSale { .. } => SpecsField::MinInvestAmount,
+            SpecsError::RaiseEndDateNotInFuture { .. } => SpecsField::RaiseEndDate,
+            SpecsError::SupplyPriceOverflow => SpecsField::SharePrice,
+            SpecsError::TooManyDecimals { .. } => SpecsField::SharesDecimals,
+        }
+    }
+}
+
+impl fmt::Display for SpecsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecsError::TooLong { field, max, actual } => {
+                write!(f, "{field:?} is too long: {actual} bytes (max: {max})")
+            }
+            SpecsError::TooManyAppArgs { max, actual } => {
+                write!(f, "Too many app arguments: {actual} (max: {max})")
+            }
+            SpecsError::MinInvestGreaterThanMax { min, max } => write!(
+                f,
+                "Min invest amount: {min} is greater than max invest amount: {max}"
+            ),
+            SpecsError::MinInvestGreaterThanSharesForSale { min, for_sale } => write!(
+                f,
+                "Min invest amount: {min} is greater than the shares for sale: {for_sale}"
+            ),
+            SpecsError::RaiseEndDateNotInFuture { end_date } => {
+                write!(f, "Raise end date: {end_date:?} is not in the future")
+            }
+            SpecsError::SupplyPriceOverflow => {
+                write!(f, "Share price multiplied by the supply is too large")
+            }
+            SpecsError::TooManyDecimals { max, actual } => {
+                write!(f, "Too many decimals: {actual} (max: {max})")
+            }
+            SpecsError::NotAllowed { field } => write!(f, "{field:?} is not allowed"),
+        }
+    }
+}
+
+impl std::error::Error for SpecsError {}
+
+/// Checks the specs (and the image NFT metadata, if it will be passed to the setup) against the protocol limits and their consistency,
+/// before creating any transaction.
+/// Returns all the failures, an empty list means that the specs are valid.
+pub fn validate_setup_dao_specs(
+    specs: &SetupDaoSpecs,
+    image_nft_metadata: Option<&ImageNftMetadata>,
+) -> Result<Vec<SpecsError>> {
+    let mut errors = vec![];
+
+    let token_name = &specs.shares.token_name;
+    // create_shares_tx uses the token name for both, so the shorter limit applies
+    check_len(
+        &mut errors,
+        SpecsField::TokenName,
+        token_name.len(),
+        MAX_ASSET_UNIT_NAME_LEN.min(MAX_ASSET_NAME_LEN),
+    );
+    if let Some(image_url) = &specs.image_url {
+        check_len(
+            &mut errors,
+            SpecsField::ImageUrl,
+            image_url.len(),
+            MAX_ASSET_URL_LEN,
+        );
+    }
+
+    if specs.min_invest_amount.val() > specs.max_invest_amount.val() {
+        errors.push(SpecsError::MinInvestGreaterThanMax {
+            min: specs.min_invest_amount,
+            max: specs.max_invest_amount,
+        });
+    }
+    if specs.min_invest_amount.val() > specs.shares_for_investors().val() {
+        errors.push(SpecsError::MinInvestGreaterThanSharesForSale {
+            min: specs.min_invest_amount,
+            for_sale: specs.shares_for_investors(),
+        });
+    }
+
+    if specs.raise_end_date.0 <= Timestamp::now().0 {
+        errors.push(SpecsError::RaiseEndDateNotInFuture {
+            end_date: specs.raise_end_date,
+        });
+    }
+
+    if specs
+        .share_price
+        .val()
+        .checked_mul(specs.shares.supply.val())
+        .is_none()
+    {
+        errors.push(SpecsError::SupplyPriceOverflow);
+    }
+
+    // the ids, versions and dates are fixed size, so placeholders result in the same arg sizes
+    errors.extend(validate_dao_init_data(&DaoInitData {
+        app_approval_version: Version(1),
+        app_clear_version: Version(1),
+        shares_asset_id: 0,
+        funds_asset_id: FundsAssetId(0),
+        project_name: specs.name.clone(),
+        descr_url: specs.descr_url.clone(),
+        share_price: specs.share_price,
+        investors_share: specs.investors_share,
+        image_nft_url: specs.image_url.clone(),
+        image_nft_metadata: image_nft_metadata.cloned(),
+        social_media_url: specs.social_media_url.clone(),
+        min_raise_target: specs.raise_min_target,
+        min_raise_target_end_date: specs.raise_end_date,
+        setup_date: Timestamp::now(),
+        prospectus: specs.prospectus.clone(),
+        min_invest_shares: specs.min_invest_amount,
+        max_invest_shares: specs.max_invest_amount,
+    })?);
+
+    Ok(errors)
+}
+
+/// Checks the shares asset config against the protocol limits, and that it has no freeze or clawback address.
+/// Returns all the failures, an empty list means that the config is valid.
+pub fn validate_shares_config(config: &SharesAssetConfig) -> Vec<SpecsError> {
+    let mut errors = vec![];
+
+    if config.decimals > MAX_ASSET_DECIMALS {
+        errors.push(SpecsError::TooManyDecimals {
+            max: MAX_ASSET_DECIMALS,
+            actual: config.decimals,
+        });
+    }
+    if let Some(url) = &config.url {
+        check_len(
+            &mut errors,
+            SpecsField::SharesUrl,
+            url.len(),
+            MAX_ASSET_URL_LEN,
+        );
+    }
+    if config.freeze.is_some() {
+        errors.push(SpecsError::NotAllowed {
+            field: SpecsField::SharesFreeze,
+        });
+    }
+    if config.clawback.is_some() {
+        errors.push(SpecsError::NotAllowed {
+            field: SpecsField::SharesClawback,
+        });
+    }
+
+    errors
+}
+
+/// [validate_shares_config] as a result, to stop before creating an invalid shares asset
+pub fn ensure_valid_shares_config(config: &SharesAssetConfig) -> Result<()> {
+    let errors = validate_shares_config(config);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        let descrs: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        Err(anyhow!("Invalid shares config: {}", descrs.join(", ")))
+    }
+}
+
+/// Checks the setup app call's arguments against the protocol limits (its note is a fixed prefix).
+/// Returns all the failures, an empty list means that the data fits in the app call.
+pub fn validate_dao_init_data(data: &DaoInitData) -> Result<Vec<SpecsError>> {
+    let mut errors = vec![];
+
+    let strings = [
+        (SpecsField::Name, Some(&data.project_name)),
+        (SpecsField::DescrUrl, data.descr_url.as_ref()),
+        (SpecsField::SocialMediaUrl, Some(&data.social_media_url)),
+        (SpecsField::ImageUrl, data.image_nft_url.as_ref()),
+        (
+            SpecsField::ProspectusUrl,
+            data.prospectus.as_ref().map(|p| &p.url),
+        ),
+        (
+            SpecsField::ProspectusHash,
+            data.prospectus.as_ref().map(|p| &p.hash),
+        ),
+    ];
+    for (field, value) in strings.iter() {
+        if let (Some(value), Some(max)) = (value, field.max_global_state_len()) {
+            check_len(&mut errors, *field, value.len(), max);
+        }
+    }
+    if let Some(metadata) = &data.image_nft_metadata {
+        check_len(
+            &mut errors,
+            SpecsField::ImageNftMetadataUrl,
+            metadata.url.len(),
+            MAX_ASSET_URL_LEN,
+        );
+    }
+
+    let args = setup_app_args(data)?;
+    if args.len() > MAX_APP_ARGS {
+        errors.push(SpecsError::TooManyAppArgs {
+            max: MAX_APP_ARGS,
+            actual: args.len(),
+        });
+    }
+    check_len(
+        &mut errors,
+        SpecsField::AppArgs,
+        args.iter().map(|a| a.len()).sum(),
+        MAX_APP_TOTAL_ARG_LEN,
+    );
+
+    Ok(errors)
+}
+
+fn check_len(errors: &mut Vec<SpecsError>, field: SpecsField, actual: usize, max: usize) {
+    if actual > max {
+        errors.push(SpecsError::TooLong { field, max, actual });
+    }
+}
@@ -0,0 +1,250 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        flows::create_dao::{
+            shares_config::SharesAssetConfig,
+            validate_specs::{
+                ensure_valid_shares_config, validate_setup_dao_specs, validate_shares_config,
+                SpecsError, SpecsField, MAX_APP_KEY_VALUE_LEN, MAX_APP_TOTAL_ARG_LEN,
+                MAX_ASSET_DECIMALS, MAX_ASSET_UNIT_NAME_LEN, MAX_ASSET_URL_LEN,
+            },
+        },
+        image_metadata::ImageNftMetadata,
+    };
+    use anyhow::Result;
+    use chrono::{Duration, Utc};
+    use mbase::models::{
+        create_shares_specs::CreateSharesSpecs, funds::FundsAmount, setup_dao_specs::SetupDaoSpecs,
+        share_amount::ShareAmount,
+    };
+    use network_test_util::test_data::investor1;
+    use rust_decimal::Decimal;
+    use std::{convert::TryInto, str::FromStr};
+
+    fn specs(token_name: &str, share_price: FundsAmount) -> Result<SetupDaoSpecs> {
+        SetupDaoSpecs::new(
+            "Pancakes ltd".to_owned(),
+            None,
+            CreateSharesSpecs {
+                token_name: token_name.to_owned(),
+                supply: ShareAmount::new(300),
+            },
+            Decimal::from_str("0.4")?.try_into()?,
+            share_price,
+            Some("https://placekitten.com/1033/360".to_owned()),
+            "https://twitter.com/capi_fin".to_owned(),
+            ShareAmount::new(250),
+            FundsAmount::new(0),
+            (Utc::now() + Duration::weeks(4)).into(),
+            None,
+            ShareAmount::new(1),
+            ShareAmount::new(100),
+        )
+    }
+
+    #[test]
+    fn test_valid_specs() -> Result<()> {
+        let errors = validate_setup_dao_specs(&specs("PCK", FundsAmount::new(5_000_000))?, None)?;
+        assert_eq!(Vec::<SpecsError>::new(), errors);
+        Ok(())
+    }
+
+    #[test]
+    fn test_token_name_too_long() -> Result<()> {
+        let errors =
+            validate_setup_dao_specs(&specs("PANCAKES1", FundsAmount::new(5_000_000))?, None)?;
+
+        assert_eq!(
+            vec![SpecsError::TooLong {
+                field: SpecsField::TokenName,
+                max: MAX_ASSET_UNIT_NAME_LEN,
+                actual: 9
+            }],
+            errors
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_image_nft_metadata_url_too_long() -> Result<()> {
+        let specs = specs("PCK", FundsAmount::new(5_000_000))?;
+        let mut metadata = ImageNftMetadata {
+            url: "a".repeat(MAX_ASSET_URL_LEN),
+            hash: [0; 32],
+        };
+        assert_eq!(
+            Vec::<SpecsError>::new(),
+            validate_setup_dao_specs(&specs, Some(&metadata))?
+        );
+
+        metadata.url = "a".repeat(MAX_ASSET_URL_LEN + 1);
+        assert_eq!(
+            vec![SpecsError::TooLong {
+                field: SpecsField::ImageNftMetadataUrl,
+                max: MAX_ASSET_URL_LEN,
+                actual: MAX_ASSET_URL_LEN + 1
+            }],
+            validate_setup_dao_specs(&specs, Some(&metadata))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_name_too_long_for_global_state() -> Result<()> {
+        let mut specs = specs("PCK", FundsAmount::new(5_000_000))?;
+        let key_len = SpecsField::Name.global_state_key().unwrap().len();
+        let max = MAX_APP_KEY_VALUE_LEN - key_len;
+
+        // key + value fit exactly
+        specs.name = "a".repeat(max);
+        assert_eq!(
+            Vec::<SpecsError>::new(),
+            validate_setup_dao_specs(&specs, None)?
+        );
+
+        specs.name = "a".repeat(max + 1);
+        assert_eq!(
+            vec![SpecsError::TooLong {
+                field: SpecsField::Name,
+                max,
+                actual: max + 1
+            }],
+            validate_setup_dao_specs(&specs, None)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_total_app_args_too_long() -> Result<()> {
+        let mut specs = specs("PCK", FundsAmount::new(5_000_000))?;
+        specs.descr_url = Some("a".repeat(MAX_APP_TOTAL_ARG_LEN));
+
+        let errors = validate_setup_dao_specs(&specs, None)?;
+
+        assert_eq!(2, errors.len());
+        assert_eq!(SpecsField::DescrUrl, errors[0].field());
+        assert_eq!(SpecsField::AppArgs, errors[1].field());
+        Ok(())
+    }
+
+    #[test]
+    fn test_inconsistent_invest_amounts() -> Result<()> {
+        let mut specs = specs("PCK", FundsAmount::new(5_000_000))?;
+        specs.min_invest_amount = ShareAmount::new(260);
+        specs.max_invest_amount = ShareAmount::new(200);
+
+        let errors = validate_setup_dao_specs(&specs, None)?;
+
+        assert_eq!(
+            vec![
+                SpecsError::MinInvestGreaterThanMax {
+                    min: ShareAmount::new(260),
+                    max: ShareAmount::new(200),
+                },
+                SpecsError::MinInvestGreaterThanSharesForSale {
+                    min: ShareAmount::new(260),
+                    for_sale: ShareAmount::new(250),
+                }
+            ],
+            errors
+        );
+        assert!(errors
+            .iter()
+            .all(|e| e.field() == SpecsField::MinInvestAmount));
+        Ok(())
+    }
+
+    #[test]
+    fn test_raise_end_date_in_past() -> Result<()> {
+        let mut specs = specs("PCK", FundsAmount::new(5_000_000))?;
+        specs.raise_end_date = (Utc::now() - Duration::minutes(1)).into();
+
+        let errors = validate_setup_dao_specs(&specs, None)?;
+
+        assert_eq!(1, errors.len());
+        assert_eq!(SpecsField::RaiseEndDate, errors[0].field());
+        Ok(())
+    }
+
+    #[test]
+    fn test_supply_price_overflow() -> Result<()> {
+        let errors =
+            validate_setup_dao_specs(&specs("PCK", FundsAmount::new(u64::MAX / 2))?, None)?;
+
+        assert_eq!(vec![SpecsError::SupplyPriceOverflow], errors);
+        Ok(())
+    }
+
+    #[test]
+    fn test_valid_shares_config() -> Result<()> {
+        let config = SharesAssetConfig {
+            decimals: 6,
+            url: Some(
+                "ipfs://bafkreidfpvmwiu3gmc3lfhkw6g2aou2rlqcp4srbl4l2v4vdcv4czflr5i#arc3"
+                    .to_owned(),
+            ),
+            metadata_hash: Some([0; 32]),
+            ..SharesAssetConfig::default()
+        };
+        assert_eq!(Vec::<SpecsError>::new(), validate_shares_config(&config));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_shares_config() -> Result<()> {
+        let config = SharesAssetConfig {
+            decimals: 20,
+            url: Some("a".repeat(97)),
+            ..SharesAssetConfig::default()
+        };
+
+        let errors = validate_shares_config(&config);
+
+        assert_eq!(
+            vec![
+                SpecsError::TooManyDecimals {
+                    max: MAX_ASSET_DECIMALS,
+                    actual: 20
+                },
+                SpecsError::TooLong {
+                    field: SpecsField::SharesUrl,
+                    max: MAX_ASSET_URL_LEN,
+                    actual: 97
+                }
+            ],
+            errors
+        );
+        assert_eq!(SpecsField::SharesDecimals, errors[0].field());
+        // creating the asset stops with all the errors
+        let res = ensure_valid_shares_config(&config);
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("SharesUrl is too long"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_shares_config_with_freeze_or_clawback() -> Result<()> {
+        let address = investor1().address();
+        let config = SharesAssetConfig {
+            freeze: Some(address),
+            clawback: Some(address),
+            ..SharesAssetConfig::default()
+        };
+
+        assert_eq!(
+            vec![
+                SpecsError::NotAllowed {
+                    field: SpecsField::SharesFreeze
+                },
+                SpecsError::NotAllowed {
+                    field: SpecsField::SharesClawback
+                }
+            ],
+            validate_shares_config(&config)
+        );
+        Ok(())
+    }
+}
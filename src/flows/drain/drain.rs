@@ -1,24 +1,26 @@
-use crate::state::account_state::funds_holdings;
+use crate::{
+    dao_algod::DaoAlgod,
+    signer::{Sign, SignedTxs, TxSigner},
+    state::{account_state::funds_holdings, dao_app_state::dao_global_state},
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
     core::{Address, SuggestedTransactionParams},
     transaction::{builder::CallApplication, SignedTransaction, Transaction, TxnBuilder},
 };
 use anyhow::{anyhow, Result};
-use mbase::{
-    models::{
-        dao_app_id::DaoAppId,
-        funds::{FundsAmount, FundsAssetId},
-        shares_percentage::SharesPercentage, capi_deps::{CapiAssetDaoDeps, CapiAddress}, tx_id::TxId,
-    },
-    state::dao_app_state::dao_global_state,
+use mbase::models::{
+    capi_deps::{CapiAddress, CapiAssetDaoDeps},
+    dao_app_id::DaoAppId,
+    funds::{FundsAmount, FundsAssetId},
+    shares_percentage::SharesPercentage,
 };
 use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn drain(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     drainer: &Address,
     app_id: DaoAppId,
     funds_asset_id: FundsAssetId,
@@ -55,7 +57,7 @@ impl DaoAndCapiDrainAmounts {
 }
 
 pub async fn to_drain_amounts(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     capi_percentage: SharesPercentage,
     funds_asset_id: FundsAssetId,
     app_id: DaoAppId,
@@ -80,7 +82,7 @@ pub async fn to_drain_amounts(
 }
 
 pub async fn fetch_drain_amount_and_drain(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     drainer: &Address,
     app_id: DaoAppId,
     funds_asset_id: FundsAssetId,
@@ -129,7 +131,7 @@ pub fn drain_app_call_tx(
     Ok(tx)
 }
 
-pub async fn submit_drain(algod: &Algod, signed: &DrainSigned) -> Result<TxId> {
+pub async fn submit_drain(algod: &dyn DaoAlgod, signed: &DrainSigned) -> Result<Submission> {
     log::debug!("calling submit drain..");
 
     // mbase::teal::debug_teal_rendered(&[signed.app_call_tx_signed.clone()], "dao_app_approval")
@@ -138,7 +140,7 @@ pub async fn submit_drain(algod: &Algod, signed: &DrainSigned) -> Result<TxId> {
     let res = algod
         .broadcast_signed_transactions(&[signed.app_call_tx_signed.clone()])
         .await?;
-    res.tx_id.parse()
+    Submission::from_response(&res)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -150,3 +152,19 @@ pub struct DrainToSign {
 pub struct DrainSigned {
     pub app_call_tx_signed: SignedTransaction,
 }
+
+impl Sign for DrainToSign {
+    type Signed = DrainSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<DrainSigned> {
+        Ok(DrainSigned {
+            app_call_tx_signed: signer.sign_tx(self.app_call_tx)?,
+        })
+    }
+}
+
+impl SignedTxs for DrainSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.app_call_tx_signed.clone()]
+    }
+}
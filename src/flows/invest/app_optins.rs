@@ -1,14 +1,16 @@
-use crate::flows::{create_dao::model::Dao, shared::app::optin_to_dao_app};
+use crate::{
+    dao_algod::DaoAlgod,
+    flows::{create_dao::model::Dao, shared::app::optin_to_dao_app},
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
     core::Address,
     transaction::{SignedTransaction, Transaction},
 };
 use anyhow::Result;
-use mbase::models::tx_id::TxId;
 
 pub async fn invest_or_locking_app_optin_tx(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     dao: &Dao,
     investor: &Address,
 ) -> Result<Transaction> {
@@ -18,11 +20,11 @@ pub async fn invest_or_locking_app_optin_tx(
 }
 
 pub async fn submit_invest_or_locking_app_optin(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     signed: SignedTransaction,
-) -> Result<TxId> {
+) -> Result<Submission> {
     // mbase::teal::debug_teal_rendered(&signed, "dao_app_approval").unwrap();
     let res = algod.broadcast_signed_transaction(&signed).await?;
     log::debug!("Investor app optins tx id: {}", res.tx_id);
-    res.tx_id.parse()
+    Submission::from_response(&res)
 }
@@ -1,9 +1,11 @@
-use crate::flows::create_dao::model::Dao;
+use crate::{
+    capi_error::broadcast_app_txs, dao_algod::DaoAlgod, flows::create_dao::model::Dao,
+    min_balance::RequiresAlgos, signer::SignedTxs,
+};
 
 use super::model::{InvestResult, InvestSigned, InvestToSign};
 use algonaut::{
-    algod::v2::Algod,
-    core::{Address, SuggestedTransactionParams},
+    core::{Address, MicroAlgos, SuggestedTransactionParams},
     transaction::{
         builder::CallApplication, tx_group::TxGroup, AcceptAsset, Transaction, TransferAsset,
         TxnBuilder,
@@ -15,6 +17,7 @@ use mbase::{
         dao_app_id::DaoAppId,
         funds::{FundsAmount, FundsAssetId},
         share_amount::ShareAmount,
+        timestamp::Timestamp,
     },
     state::dao_app_state::SignedProspectus,
 };
@@ -23,7 +26,7 @@ use mbase::{
 /// we can't do it here: setting local state errors if during opt-in
 #[allow(clippy::too_many_arguments)]
 pub async fn invest_txs(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     dao: &Dao,
     investor: &Address,
     app_id: DaoAppId,
@@ -93,6 +96,28 @@ pub async fn invest_txs(
     })
 }
 
+/// Fee of the invest txs, incl. the pooled fee of the shares xfer (not the app opt-in, which is submitted before).
+/// Built with [invest_txs]: the investor, share amount and prospectus don't change it.
+pub async fn invest_fee(algod: &dyn DaoAlgod, dao: &Dao) -> Result<MicroAlgos> {
+    let to_sign = invest_txs(
+        algod,
+        dao,
+        &dao.owner,
+        dao.app_id,
+        dao.shares_asset_id,
+        ShareAmount::new(0),
+        dao.funds_asset_id,
+        dao.share_price,
+        SignedProspectus {
+            url: String::new(),
+            hash: String::new(),
+            timestamp: Timestamp::now(),
+        },
+    )
+    .await?;
+    Ok(MicroAlgos(to_sign.txs().iter().map(|tx| tx.fee.0).sum()))
+}
+
 pub fn dao_app_investor_setup_tx(
     params: &SuggestedTransactionParams,
     app_id: DaoAppId,
@@ -118,19 +143,15 @@ pub fn dao_app_investor_setup_tx(
     Ok(tx)
 }
 
-pub async fn submit_invest(algod: &Algod, signed: &InvestSigned) -> Result<InvestResult> {
+pub async fn submit_invest(algod: &dyn DaoAlgod, signed: &InvestSigned) -> Result<InvestResult> {
     log::debug!("Submitting investing txs..");
     // crate::debug_msg_pack_submit_par::log_to_msg_pack(&signed);
 
-    let txs = vec![
-        signed.shares_asset_optin_tx.clone(),
-        signed.central_app_setup_tx.clone(),
-        signed.payment_tx.clone(),
-    ];
+    let txs = signed.signed_txs();
 
     // mbase::teal::debug_teal_rendered(&txs, "dao_app_approval").unwrap();
 
-    let res = algod.broadcast_signed_transactions(&txs).await?;
+    let res = broadcast_app_txs(algod, &txs).await?;
     Ok(InvestResult {
         tx_id: res.tx_id.parse()?,
         dao: signed.dao.clone(),
@@ -2,4 +2,5 @@ pub mod app_optins;
 #[allow(clippy::module_inception)]
 pub mod invest;
 pub mod model;
+pub mod quote;
 mod tests;
@@ -1,5 +1,9 @@
-use crate::flows::create_dao::model::Dao;
+use crate::{
+    flows::create_dao::model::Dao,
+    signer::{Sign, SignedTxs, TxSigner},
+};
 use algonaut::transaction::{SignedTransaction, Transaction};
+use anyhow::Result;
 use mbase::models::{funds::FundsAmount, tx_id::TxId};
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +26,29 @@ pub struct InvestSigned {
     pub payment_tx: SignedTransaction,
 }
 
+impl Sign for InvestToSign {
+    type Signed = InvestSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<InvestSigned> {
+        Ok(InvestSigned {
+            central_app_setup_tx: signer.sign_tx(self.app_call)?,
+            shares_asset_optin_tx: signer.sign_tx(self.shares_asset_optin_tx)?,
+            payment_tx: signer.sign_tx(self.payment_tx)?,
+            dao: self.dao,
+        })
+    }
+}
+
+impl SignedTxs for InvestSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![
+            self.shares_asset_optin_tx.clone(),
+            self.central_app_setup_tx.clone(),
+            self.payment_tx.clone(),
+        ]
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct InvestResult {
     // TODO id of what tx? do we need this?
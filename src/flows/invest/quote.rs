@@ -0,0 +1,155 @@
+use crate::{
+    dao_algod::DaoAlgod,
+    flows::{create_dao::model::Dao, invest::invest::invest_fee},
+    state::{dao_app_state::dao_global_state, dao_shares::dao_shares_with_dao_state},
+};
+use algonaut::core::MicroAlgos;
+use anyhow::{anyhow, Result};
+use mbase::models::{funds::FundsAmount, share_amount::ShareAmount};
+use rust_decimal::Decimal;
+
+/// What the investor wants to invest: a share count or a funds amount to spend on shares
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvestAmount {
+    Shares(ShareAmount),
+    Funds(FundsAmount),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvestQuote {
+    pub share_amount: ShareAmount,
+    pub total_price: FundsAmount,
+    /// Part of the funds amount that isn't spent, as shares can't be fractional (0 for share amounts)
+    pub change: FundsAmount,
+    pub shares_available: ShareAmount,
+    /// What can still be raised until reaching the max raisable amount
+    pub remaining_raisable: FundsAmount,
+    /// Fee of the invest txs, incl. the pooled fee of the shares xfer (not the app opt-in, see readiness)
+    pub fee: MicroAlgos,
+    /// Part of the share supply bought, between 0 and 1
+    pub supply_percentage: Decimal,
+    /// Part of the DAO's income the bought shares are entitled to, between 0 and 1
+    pub income_percentage: Decimal,
+    /// Why the investment would be rejected. Empty if it's allowed.
+    pub violations: Vec<InvestViolation>,
+}
+
+impl InvestQuote {
+    pub fn is_allowed(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvestViolation {
+    /// E.g. the funds amount doesn't buy a single share
+    NoShares,
+    BelowMinInvest {
+        min: ShareAmount,
+    },
+    AboveMaxInvest {
+        max: ShareAmount,
+    },
+    NotEnoughSharesAvailable {
+        available: ShareAmount,
+    },
+    AboveMaxRaisable {
+        remaining: FundsAmount,
+    },
+}
+
+/// Quotes an investment in the DAO, with its current state.
+/// `max_raisable_amount` is the one the DAO was created with (see create_app_tx).
+pub async fn invest_quote(
+    algod: &dyn DaoAlgod,
+    dao: &Dao,
+    amount: InvestAmount,
+    max_raisable_amount: FundsAmount,
+) -> Result<InvestQuote> {
+    let dao_state = dao_global_state(algod, dao.app_id).await?;
+    let dao_shares =
+        dao_shares_with_dao_state(algod, dao.app_id, dao.shares_asset_id, &dao_state).await?;
+    quote(
+        dao,
+        amount,
+        dao_shares.available,
+        FundsAmount::new(
+            max_raisable_amount
+                .val()
+                .saturating_sub(dao_state.raised.val()),
+        ),
+        invest_fee(algod, dao).await?,
+    )
+}
+
+fn quote(
+    dao: &Dao,
+    amount: InvestAmount,
+    shares_available: ShareAmount,
+    remaining_raisable: FundsAmount,
+    fee: MicroAlgos,
+) -> Result<InvestQuote> {
+    let price = dao.share_price.val();
+    let (share_amount, change) = match amount {
+        InvestAmount::Shares(shares) => (shares, FundsAmount::new(0)),
+        InvestAmount::Funds(funds) => {
+            if price == 0 {
+                return Err(anyhow!("Invalid share price: 0"));
+            }
+            (
+                ShareAmount::new(funds.val() / price),
+                FundsAmount::new(funds.val() % price),
+            )
+        }
+    };
+
+    let total_price = FundsAmount::new(price.checked_mul(share_amount.val()).ok_or_else(|| {
+        anyhow!(
+            "Share price: {price} multiplied by share amount: {share_amount} caused an overflow."
+        )
+    })?);
+
+    let supply_percentage = Decimal::from(share_amount.val())
+        .checked_div(Decimal::from(dao.token_supply.val()))
+        .ok_or_else(|| anyhow!("Invalid share supply: {}", dao.token_supply))?;
+    let income_percentage = supply_percentage
+        .checked_mul(dao.investors_share.value())
+        .ok_or_else(|| anyhow!("Overflow calculating the income percentage"))?;
+
+    let mut violations = vec![];
+    if share_amount.val() == 0 {
+        violations.push(InvestViolation::NoShares);
+    }
+    if share_amount.val() < dao.min_invest_amount.val() {
+        violations.push(InvestViolation::BelowMinInvest {
+            min: dao.min_invest_amount,
+        });
+    }
+    if share_amount.val() > dao.max_invest_amount.val() {
+        violations.push(InvestViolation::AboveMaxInvest {
+            max: dao.max_invest_amount,
+        });
+    }
+    if share_amount.val() > shares_available.val() {
+        violations.push(InvestViolation::NotEnoughSharesAvailable {
+            available: shares_available,
+        });
+    }
+    if total_price.val() > remaining_raisable.val() {
+        violations.push(InvestViolation::AboveMaxRaisable {
+            remaining: remaining_raisable,
+        });
+    }
+
+    Ok(InvestQuote {
+        share_amount,
+        total_price,
+        change,
+        shares_available,
+        remaining_raisable,
+        fee,
+        supply_percentage,
+        income_percentage,
+        violations,
+    })
+}
@@ -1,14 +1,21 @@
 #[cfg(test)]
 mod tests {
+    use crate::capi_error::CapiError;
+    use crate::explain::Explain;
     use crate::flows::claim::claim::claimable_dividend;
     use crate::flows::create_dao::model::Dao;
+    use crate::flows::create_dao::shares_config::{shares_to_base_units, SharesAssetConfig};
     use crate::flows::create_dao::storage::load_dao::load_dao;
+    use crate::flows::invest::invest::invest_txs;
+    use crate::flows::invest::quote::{invest_quote, InvestAmount, InvestViolation};
     use crate::queries::my_daos::my_current_invested_daos;
     use crate::state::account_state::{
         asset_holdings, find_asset_holding_or_err, funds_holdings, funds_holdings_from_account,
     };
     use crate::state::dao_shares::dao_shares;
-    use crate::testing::flow::create_dao_flow::create_dao_flow;
+    use crate::testing::flow::create_dao_flow::{
+        create_dao_flow, test::create_dao_flow_with_shares_config,
+    };
     use crate::testing::flow::customer_payment_and_drain_flow::customer_payment_and_drain_flow;
     use crate::testing::flow::invest_in_dao_flow::{
         invests_flow, invests_optins_flow, InvestInDaoTestFlowRes,
@@ -21,12 +28,15 @@ mod tests {
     use mbase::checked::{CheckedAdd, CheckedSub};
     use mbase::models::funds::FundsAmount;
     use mbase::models::share_amount::ShareAmount;
+    use mbase::models::timestamp::Timestamp;
     use mbase::state::dao_app_state::{
-        central_investor_state_from_acc, dao_global_state, dao_investor_state,
+        central_investor_state_from_acc, dao_global_state, dao_investor_state, SignedProspectus,
     };
     use mbase::util::network_util::wait_for_pending_transaction;
     use network_test_util::test_data::investor2;
+    use rust_decimal::Decimal;
     use serial_test::serial;
+    use std::str::FromStr;
     use tokio::test;
 
     #[test]
@@ -59,6 +69,36 @@ mod tests {
         test_simple_investment_res(&td, &dao, investor, buy_share_amount, flow_res).await
     }
 
+    #[test]
+    #[serial] // reset network (cmd)
+    async fn test_invests_flow_with_shares_decimals() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let algod = &td.algod;
+        let investor = &td.investor1;
+
+        let config = SharesAssetConfig {
+            decimals: 2,
+            ..SharesAssetConfig::default()
+        };
+        // the amounts (and the price) are in base units: 0.1 shares are 10 base units
+        let buy_share_amount = shares_to_base_units(Decimal::from_str("0.1")?, config.decimals)?;
+        assert_eq!(ShareAmount::new(10), buy_share_amount);
+
+        let dao = create_dao_flow_with_shares_config(td, &config).await?;
+
+        // precs
+
+        invests_optins_flow(algod, investor, &dao).await?;
+
+        // flow
+
+        let flow_res = invests_flow(td, investor, buy_share_amount, &dao).await?;
+
+        // tests
+
+        test_simple_investment_res(td, &dao, investor, buy_share_amount, flow_res).await
+    }
+
     #[test]
     #[serial] // reset network (cmd)
     async fn test_increments_shares_when_investing_twice() -> Result<()> {
@@ -500,7 +540,10 @@ mod tests {
 
         // tests
 
-        assert!(flow_res.is_err());
+        assert_eq!(
+            Some(&CapiError::NotEnoughSharesForSale),
+            flow_res.unwrap_err().downcast_ref::<CapiError>()
+        );
 
         Ok(())
     }
@@ -529,7 +572,10 @@ mod tests {
 
         // tests
 
-        assert!(flow_res.is_err());
+        assert_eq!(
+            Some(&CapiError::NotEnoughSharesForSale),
+            flow_res.unwrap_err().downcast_ref::<CapiError>()
+        );
 
         Ok(())
     }
@@ -563,7 +609,10 @@ mod tests {
 
         // tests
 
-        assert!(flow_res.is_err());
+        assert_eq!(
+            Some(&CapiError::NotEnoughSharesForSale),
+            flow_res.unwrap_err().downcast_ref::<CapiError>()
+        );
 
         Ok(())
     }
@@ -642,4 +691,82 @@ mod tests {
         wait_for_pending_transaction(algod, &unlock_tx_id).await?;
         Ok(())
     }
+
+    #[test]
+    #[serial] // reset network (cmd)
+    async fn test_invest_quote() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let algod = &td.algod;
+        let investor = &td.investor1;
+        let max_raisable_amount = FundsAmount::new(5_000_000_000_000);
+
+        let dao = create_dao_flow(td).await?;
+
+        // a funds amount buying 10 shares, with some change
+        let funds_amount = FundsAmount::new(dao.share_price.val() * 10 + 1);
+        let quote = invest_quote(
+            algod,
+            &dao,
+            InvestAmount::Funds(funds_amount),
+            max_raisable_amount,
+        )
+        .await?;
+
+        assert!(quote.is_allowed());
+        assert_eq!(ShareAmount::new(10), quote.share_amount);
+        assert_eq!(
+            FundsAmount::new(dao.share_price.val() * 10),
+            quote.total_price
+        );
+        assert_eq!(FundsAmount::new(1), quote.change);
+        assert_eq!(td.specs.shares_for_investors(), quote.shares_available);
+        assert_eq!(max_raisable_amount, quote.remaining_raisable);
+
+        // the quoted fee is the one of the invest txs, as explained to the investor
+        let to_sign = invest_txs(
+            algod,
+            &dao,
+            &investor.address(),
+            dao.app_id,
+            dao.shares_asset_id,
+            quote.share_amount,
+            dao.funds_asset_id,
+            dao.share_price,
+            SignedProspectus {
+                hash: "...".to_owned(),
+                url: "...".to_owned(),
+                timestamp: Timestamp::now(),
+            },
+        )
+        .await?;
+        assert_eq!(to_sign.explain().total_fee, quote.fee);
+
+        invests_optins_flow(algod, investor, &dao).await?;
+        invests_flow(td, investor, quote.share_amount, &dao).await?;
+
+        // the investment reduced the available shares and the remaining raisable amount
+        let quote_after_invest = invest_quote(
+            algod,
+            &dao,
+            InvestAmount::Shares(td.specs.shares_for_investors()),
+            max_raisable_amount,
+        )
+        .await?;
+
+        assert_eq!(
+            ShareAmount::new(td.specs.shares_for_investors().val() - 10),
+            quote_after_invest.shares_available
+        );
+        assert_eq!(
+            FundsAmount::new(max_raisable_amount.val() - quote.total_price.val()),
+            quote_after_invest.remaining_raisable
+        );
+        assert!(quote_after_invest.violations.contains(
+            &InvestViolation::NotEnoughSharesAvailable {
+                available: quote_after_invest.shares_available
+            }
+        ));
+
+        Ok(())
+    }
 }
@@ -1,6 +1,11 @@
+use crate::{
+    capi_error::broadcast_app_txs,
+    dao_algod::DaoAlgod,
+    signer::{Sign, SignedTxs, TxSigner},
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
-    core::{Address, MicroAlgos},
+    core::Address,
     transaction::{
         builder::CallApplication, tx_group::TxGroup, SignedTransaction, Transaction, TransferAsset,
         TxnBuilder,
@@ -8,17 +13,14 @@ use algonaut::{
 };
 use anyhow::Result;
 use mbase::{
-    models::{dao_app_id::DaoAppId, share_amount::ShareAmount, tx_id::TxId},
+    models::{dao_app_id::DaoAppId, share_amount::ShareAmount},
     state::dao_app_state::SignedProspectus,
 };
 
-// TODO no constants
-pub const MIN_BALANCE: MicroAlgos = MicroAlgos(100_000);
-
 /// Note that this is only for shares that have been bought in the market
 /// The investing flow doesn't use this: there's an xfer from the investing account to the app escrow in the investing tx group
 pub async fn lock(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     investor: Address,
     share_amount: ShareAmount,
     shares_asset_id: u64,
@@ -77,17 +79,14 @@ pub async fn lock(
     })
 }
 
-pub async fn submit_lock(algod: &Algod, signed: LockSigned) -> Result<TxId> {
+pub async fn submit_lock(algod: &dyn DaoAlgod, signed: LockSigned) -> Result<Submission> {
     log::debug!("calling submit lock..");
 
-    let txs = vec![
-        signed.central_app_call_setup_tx.clone(),
-        signed.shares_xfer_tx_signed.clone(),
-    ];
+    let txs = signed.signed_txs();
     // mbase::teal::debug_teal_rendered(&txs, "dao_app_approval").unwrap();
-    let res = algod.broadcast_signed_transactions(&txs).await?;
+    let res = broadcast_app_txs(algod, &txs).await?;
     log::debug!("Lock tx id: {:?}", res.tx_id);
-    res.tx_id.parse()
+    Submission::from_response(&res)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -101,3 +100,23 @@ pub struct LockSigned {
     pub central_app_call_setup_tx: SignedTransaction,
     pub shares_xfer_tx_signed: SignedTransaction,
 }
+
+impl Sign for LockToSign {
+    type Signed = LockSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<LockSigned> {
+        Ok(LockSigned {
+            central_app_call_setup_tx: signer.sign_tx(self.central_app_call_setup_tx)?,
+            shares_xfer_tx_signed: signer.sign_tx(self.shares_xfer_tx)?,
+        })
+    }
+}
+
+impl SignedTxs for LockSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![
+            self.central_app_call_setup_tx.clone(),
+            self.shares_xfer_tx_signed.clone(),
+        ]
+    }
+}
@@ -1,10 +1,10 @@
 #[cfg(test)]
 mod tests {
-    use algonaut::transaction::{AcceptAsset, TransferAsset, TxnBuilder};
+    use algonaut::transaction::{AcceptAsset, TransactionType, TransferAsset, TxnBuilder};
     use anyhow::Result;
     use mbase::{
         checked::CheckedAdd,
-        models::{funds::FundsAmount, share_amount::ShareAmount},
+        models::{dao_app_id::DaoAppId, funds::FundsAmount, share_amount::ShareAmount},
         state::{
             app_state::ApplicationLocalStateError,
             dao_app_state::{
@@ -13,16 +13,19 @@ mod tests {
         },
         util::network_util::wait_for_pending_transaction,
     };
+    use network_test_util::test_data::investor1;
     use serial_test::serial;
     use tokio::test;
 
     use crate::{
         algo_helpers::send_tx_and_wait,
+        dao_algod::fake::FakeAlgod,
         flows::{
             claim::claim::claimable_dividend,
             invest::app_optins::{
                 invest_or_locking_app_optin_tx, submit_invest_or_locking_app_optin,
             },
+            lock::lock::{lock, submit_lock, LockSigned},
         },
         state::{
             account_state::{
@@ -106,9 +109,10 @@ mod tests {
             invest_or_locking_app_optin_tx(&algod, &dao, &td.investor2.address()).await?;
 
         let app_optin_signed_tx = td.investor2.sign_transaction(app_optin_tx)?;
-        let app_optin_tx_id =
-            submit_invest_or_locking_app_optin(&algod, app_optin_signed_tx).await?;
-        wait_for_pending_transaction(&algod, &app_optin_tx_id).await?;
+        submit_invest_or_locking_app_optin(&algod, app_optin_signed_tx)
+            .await?
+            .wait(algod)
+            .await?;
 
         // flow
 
@@ -249,9 +253,10 @@ mod tests {
         let app_optins_tx =
             invest_or_locking_app_optin_tx(algod, &dao, &investor.address()).await?;
         let app_optin_signed_tx = investor.sign_transaction(app_optins_tx)?;
-        let app_optin_tx_id =
-            submit_invest_or_locking_app_optin(algod, app_optin_signed_tx).await?;
-        wait_for_pending_transaction(algod, &app_optin_tx_id).await?;
+        submit_invest_or_locking_app_optin(algod, app_optin_signed_tx)
+            .await?
+            .wait(algod)
+            .await?;
 
         // lock
         lock_flow(algod, &dao, investor, partial_lock_amount).await?;
@@ -279,4 +284,42 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    async fn test_lock_txs_grouped_and_submitted() -> Result<()> {
+        // no network needed: we only check the generated and broadcasted txs
+        let algod = FakeAlgod::new();
+        let investor = investor1();
+        let app_id = DaoAppId(123);
+        let lock_amount = ShareAmount::new(10);
+
+        let to_sign = lock(&algod, investor.address(), lock_amount, 456, app_id).await?;
+
+        assert!(to_sign.central_app_call_setup_tx.group.is_some());
+        assert_eq!(
+            to_sign.central_app_call_setup_tx.group,
+            to_sign.shares_xfer_tx.group
+        );
+        match &to_sign.shares_xfer_tx.txn_type {
+            TransactionType::AssetTransferTransaction(xfer) => {
+                assert_eq!(app_id.address(), xfer.receiver);
+                assert_eq!(lock_amount.val(), xfer.amount);
+            }
+            _ => panic!("Lock is expected to send shares to the app"),
+        }
+
+        submit_lock(
+            &algod,
+            LockSigned {
+                central_app_call_setup_tx: investor
+                    .sign_transaction(to_sign.central_app_call_setup_tx)?,
+                shares_xfer_tx_signed: investor.sign_transaction(to_sign.shares_xfer_tx)?,
+            },
+        )
+        .await?;
+
+        assert_eq!(2, algod.broadcasted()?.len());
+
+        Ok(())
+    }
 }
@@ -1,4 +1,5 @@
 pub mod claim;
+pub mod close_dao;
 pub mod create_dao;
 pub mod drain;
 pub mod invest;
@@ -7,6 +8,7 @@ pub mod pay_dao;
 pub mod reclaim;
 pub mod rekey;
 pub mod shared;
+pub mod transfer_ownership;
 pub mod unlock;
 pub mod update_app;
 pub mod update_data;
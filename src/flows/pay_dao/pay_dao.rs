@@ -1,19 +1,20 @@
+use crate::{
+    dao_algod::DaoAlgod,
+    signer::{Sign, SignedTxs, TxSigner},
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
-    core::{Address, MicroAlgos},
+    core::Address,
     transaction::{SignedTransaction, Transaction, TransferAsset, TxnBuilder},
 };
 use anyhow::Result;
 use mbase::models::{
     dao_app_id::DaoAppId,
-    funds::{FundsAmount, FundsAssetId}, tx_id::TxId,
+    funds::{FundsAmount, FundsAssetId},
 };
 
-// TODO no constants
-pub const MIN_BALANCE: MicroAlgos = MicroAlgos(100_000);
-
 pub async fn pay_dao_app(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     customer: &Address,
     app_id: DaoAppId,
     funds_asset_id: FundsAssetId,
@@ -30,10 +31,10 @@ pub async fn pay_dao_app(
     Ok(PayDaoToSign { tx })
 }
 
-pub async fn submit_pay_dao(algod: &Algod, signed: PayDaoSigned) -> Result<TxId> {
+pub async fn submit_pay_dao(algod: &dyn DaoAlgod, signed: PayDaoSigned) -> Result<Submission> {
     let res = algod.broadcast_signed_transaction(&signed.tx).await?;
     log::debug!("Pay dao tx id: {:?}", res.tx_id);
-    res.tx_id.parse()
+    Submission::from_response(&res)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -45,3 +46,19 @@ pub struct PayDaoToSign {
 pub struct PayDaoSigned {
     pub tx: SignedTransaction,
 }
+
+impl Sign for PayDaoToSign {
+    type Signed = PayDaoSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<PayDaoSigned> {
+        Ok(PayDaoSigned {
+            tx: signer.sign_tx(self.tx)?,
+        })
+    }
+}
+
+impl SignedTxs for PayDaoSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.tx.clone()]
+    }
+}
@@ -1,20 +1,22 @@
+use crate::{
+    capi_error::broadcast_app_txs,
+    dao_algod::DaoAlgod,
+    signer::{Sign, SignedTxs, TxSigner},
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
-    core::{Address, MicroAlgos},
+    core::Address,
     transaction::{
         builder::CallApplication, tx_group::TxGroup, SignedTransaction, Transaction, TransferAsset,
         TxnBuilder,
     },
 };
 use anyhow::Result;
-use mbase::models::{dao_app_id::DaoAppId, funds::FundsAssetId, share_amount::ShareAmount, tx_id::TxId};
+use mbase::models::{dao_app_id::DaoAppId, funds::FundsAssetId, share_amount::ShareAmount};
 use serde::{Deserialize, Serialize};
 
-// TODO no constants
-pub const MIN_BALANCE: MicroAlgos = MicroAlgos(100_000);
-
 pub async fn reclaim(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     reclaimer: &Address,
     app_id: DaoAppId,
     shares_asset_id: u64,
@@ -57,22 +59,18 @@ pub async fn reclaim(
     })
 }
 
-pub async fn submit_reclaim(algod: &Algod, signed: &ReclaimSigned) -> Result<TxId> {
+pub async fn submit_reclaim(algod: &dyn DaoAlgod, signed: &ReclaimSigned) -> Result<Submission> {
     log::debug!("Submit reclaim..");
     // crate::debug_msg_pack_submit_par::log_to_msg_pack(&signed);
 
-    let txs = vec![
-        signed.app_call_tx_signed.clone(),
-        signed.shares_xfer_tx_signed.clone(),
-    ];
+    let txs = signed.signed_txs();
 
-    // crate::dryrun_util::dryrun_all(algod, &txs).await?;
     // mbase::teal::debug_teal_rendered(&txs, "dao_app_approval").unwrap();
     // mbase::teal::debug_teal_rendered(&txs, "central_escrow").unwrap();
 
-    let res = algod.broadcast_signed_transactions(&txs).await?;
+    let res = broadcast_app_txs(algod, &txs).await?;
     log::debug!("Reclaim tx id: {:?}", res.tx_id);
-    res.tx_id.parse()
+    Submission::from_response(&res)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -86,3 +84,23 @@ pub struct ReclaimSigned {
     pub app_call_tx_signed: SignedTransaction,
     pub shares_xfer_tx_signed: SignedTransaction,
 }
+
+impl Sign for ReclaimToSign {
+    type Signed = ReclaimSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<ReclaimSigned> {
+        Ok(ReclaimSigned {
+            app_call_tx_signed: signer.sign_tx(self.app_call_tx)?,
+            shares_xfer_tx_signed: signer.sign_tx(self.shares_xfer_tx)?,
+        })
+    }
+}
+
+impl SignedTxs for ReclaimSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![
+            self.app_call_tx_signed.clone(),
+            self.shares_xfer_tx_signed.clone(),
+        ]
+    }
+}
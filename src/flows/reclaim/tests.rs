@@ -3,6 +3,7 @@
 // for now not working more on this as we likely have to rewrite unlocking, reclaiming etc (legal reasons)
 mod tests {
     use crate::{
+        capi_error::CapiError,
         state::account_state::{funds_holdings, share_holdings},
         testing::{
             flow::{
@@ -177,7 +178,10 @@ mod tests {
         // test
 
         // println!("res: {reclaim_flow_res:?}");
-        assert!(reclaim_flow_res.is_err());
+        assert_eq!(
+            Some(&CapiError::FundsTargetReached),
+            reclaim_flow_res.unwrap_err().downcast_ref::<CapiError>()
+        );
 
         // new state after reclaiming
         let reclaimer_balance_after_reclaiming =
@@ -354,7 +358,10 @@ mod tests {
 
         // test
 
-        assert!(res.is_err());
+        assert_eq!(
+            Some(&CapiError::NotEnoughShares),
+            res.unwrap_err().downcast_ref::<CapiError>()
+        );
 
         Ok(())
     }
@@ -410,7 +417,10 @@ mod tests {
 
         // test
 
-        assert!(reclaim2_res.is_err());
+        assert_eq!(
+            Some(&CapiError::NotEnoughShares),
+            reclaim2_res.unwrap_err().downcast_ref::<CapiError>()
+        );
 
         // the state is the one we expect for the successful reclaim
 
@@ -526,7 +536,10 @@ mod tests {
 
         // test
 
-        assert!(reclaim2_res.is_err());
+        assert_eq!(
+            Some(&CapiError::NotEnoughShares),
+            reclaim2_res.unwrap_err().downcast_ref::<CapiError>()
+        );
 
         // the state is the one we expect for the successful reclaim
 
@@ -1,16 +1,20 @@
+use crate::{
+    dao_algod::DaoAlgod,
+    signer::{Sign, SignedTxs, TxSigner},
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
     core::{Address, MicroAlgos},
     transaction::{Pay, SignedTransaction, Transaction, TxnBuilder},
 };
 use anyhow::Result;
-use mbase::{models::tx_id::TxId, util::network_util::wait_for_pending_transaction};
 use serde::{Deserialize, Serialize};
 
-// TODO no constants
-pub const MIN_BALANCE: MicroAlgos = MicroAlgos(100_000);
-
-pub async fn rekey(algod: &Algod, to_rekey: &Address, auth: &Address) -> Result<RekeyToSign> {
+pub async fn rekey(
+    algod: &dyn DaoAlgod,
+    to_rekey: &Address,
+    auth: &Address,
+) -> Result<RekeyToSign> {
     let params = algod.suggested_transaction_params().await?;
     log::debug!("Creating rekey txs, from: {to_rekey:?} to: {auth:?}");
 
@@ -26,20 +30,18 @@ pub async fn rekey(algod: &Algod, to_rekey: &Address, auth: &Address) -> Result<
     Ok(RekeyToSign { tx })
 }
 
-pub async fn submit_rekey(algod: &Algod, signed: RekeySigned) -> Result<TxId> {
+pub async fn submit_rekey(algod: &dyn DaoAlgod, signed: RekeySigned) -> Result<Submission> {
     log::debug!("calling submit rekey..");
     log::debug!("submit rekey tx: {signed:?}");
 
     // crate::debug_msg_pack_submit_par::log_to_msg_pack(&signed);
 
-    let txs = vec![signed.tx];
+    let txs = signed.signed_txs();
 
     let res = algod.broadcast_signed_transactions(&txs).await?;
     log::debug!("Rekey tx id: {:?}", res.tx_id);
 
-    let _ = wait_for_pending_transaction(algod, &res.tx_id.parse()?).await?;
-
-    res.tx_id.parse()
+    Submission::from_response(&res)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -51,3 +53,19 @@ pub struct RekeyToSign {
 pub struct RekeySigned {
     pub tx: SignedTransaction,
 }
+
+impl Sign for RekeyToSign {
+    type Signed = RekeySigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<RekeySigned> {
+        Ok(RekeySigned {
+            tx: signer.sign_tx(self.tx)?,
+        })
+    }
+}
+
+impl SignedTxs for RekeySigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.tx.clone()]
+    }
+}
@@ -0,0 +1,209 @@
+use super::note::base64_maybe_ownership_transfer_note_to_dao_id;
+use crate::dao_indexer::DaoIndexer;
+use algonaut::{
+    core::Address,
+    model::indexer::v2::{QueryTransaction, Transaction},
+};
+use anyhow::{anyhow, Error, Result};
+use chrono::{DateTime, Utc};
+use data_encoding::BASE64;
+use mbase::{
+    date_util::timestamp_seconds_to_date,
+    models::{dao_id::DaoId, tx_id::TxId},
+};
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OwnershipTransfer {
+    pub from: Address,
+    pub to: Address,
+    pub round: u64,
+    pub date: DateTime<Utc>,
+    pub tx_id: TxId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OwnershipHistory {
+    pub owner: Address,
+    /// Oldest first
+    pub transfers: Vec<OwnershipTransfer>,
+}
+
+impl OwnershipHistory {
+    pub fn creator(&self) -> Address {
+        self.transfers.first().map(|t| t.from).unwrap_or(self.owner)
+    }
+
+    /// Everyone who owned the DAO, in order and without repetitions
+    pub fn owners(&self) -> Vec<Address> {
+        let mut owners = vec![self.creator()];
+        for transfer in &self.transfers {
+            if !owners.contains(&transfer.to) {
+                owners.push(transfer.to);
+            }
+        }
+        owners
+    }
+
+    /// The owner when the round was confirmed (a transfer's round belongs to the new owner)
+    pub fn owner_at(&self, round: u64) -> Address {
+        self.transfers
+            .iter()
+            .rev()
+            .find(|t| t.round <= round)
+            .map(|t| t.to)
+            .unwrap_or_else(|| self.creator())
+    }
+}
+
+/// Retrieves the transfers of the DAO's ownership, going back from the current `owner`:
+/// each owner received a transfer payment from the previous one (see [super::transfer_ownership::transfer_ownership]).
+pub async fn ownership_history(
+    indexer: &dyn DaoIndexer,
+    dao_id: DaoId,
+    owner: &Address,
+) -> Result<OwnershipHistory> {
+    let mut transfers = vec![];
+
+    let mut current = *owner;
+    // only transfers before the one to the following owner: an address can own the DAO more than once
+    let mut before_round: Option<u64> = None;
+    loop {
+        let mut candidates = vec![];
+        for tx in address_txs(indexer, &current).await? {
+            if let Some(transfer) = to_ownership_transfer(&tx, dao_id)? {
+                if transfer.to == current
+                    && before_round.map(|r| transfer.round < r).unwrap_or(true)
+                {
+                    candidates.push((transfer, tx.group));
+                }
+            }
+        }
+        // most recent first
+        candidates.sort_by(|(t1, _), (t2, _)| t2.round.cmp(&t1.round));
+
+        let mut received = None;
+        for (transfer, group) in candidates {
+            // anyone can send a payment with the note: it's a transfer only if the app accepted the transfer call of its group
+            if has_transfer_app_call(indexer, &transfer, dao_id, &group).await? {
+                received = Some(transfer);
+                break;
+            }
+        }
+
+        match received {
+            Some(transfer) => {
+                current = transfer.from;
+                before_round = Some(transfer.round);
+                transfers.push(transfer);
+            }
+            None => break,
+        }
+    }
+
+    transfers.reverse();
+
+    Ok(OwnershipHistory {
+        owner: *owner,
+        transfers,
+    })
+}
+
+async fn address_txs(indexer: &dyn DaoIndexer, address: &Address) -> Result<Vec<Transaction>> {
+    indexer
+        .transactions(&QueryTransaction {
+            address: Some(address.to_string()),
+            ..QueryTransaction::default()
+        })
+        .await
+}
+
+/// Whether the transfer's sender called the app in the group to transfer the ownership to the transfer's receiver
+/// (only the owner's transfer calls are accepted)
+async fn has_transfer_app_call(
+    indexer: &dyn DaoIndexer,
+    transfer: &OwnershipTransfer,
+    dao_id: DaoId,
+    group: &Option<String>,
+) -> Result<bool> {
+    if group.is_none() {
+        return Ok(false);
+    }
+    let sender_str = transfer.from.to_string();
+    Ok(address_txs(indexer, &transfer.from)
+        .await?
+        .iter()
+        .any(|tx| {
+            &tx.group == group
+                && tx.sender == sender_str
+                && is_transfer_app_call(tx, dao_id, &transfer.to)
+        }))
+}
+
+/// Whether the tx calls the app's transfer with `new_owner` (see [super::transfer_ownership::transfer_ownership])
+fn is_transfer_app_call(tx: &Transaction, dao_id: DaoId, new_owner: &Address) -> bool {
+    let app_call = match &tx.application_transaction {
+        Some(app_call) if app_call.application_id == dao_id.0 .0 => app_call,
+        _ => return false,
+    };
+    let args = app_call
+        .application_args
+        .iter()
+        .map(|arg| BASE64.decode(arg.as_bytes()))
+        .collect::<Result<Vec<_>, _>>();
+    match args {
+        Ok(args) => args.len() >= 2 && args[0] == b"transfer_ownership" && args[1] == new_owner.0,
+        Err(e) => {
+            log::debug!("Ignoring app call with invalid args: {e:?}, tx: {tx:?}");
+            false
+        }
+    }
+}
+
+/// Returns the transfer if the tx is a transfer of the DAO's ownership
+pub(crate) fn to_ownership_transfer(
+    tx: &Transaction,
+    dao_id: DaoId,
+) -> Result<Option<OwnershipTransfer>> {
+    if transferred_dao_id(tx) != Some(dao_id) {
+        return Ok(None);
+    }
+    let payment = tx
+        .payment_transaction
+        .as_ref()
+        .ok_or_else(|| anyhow!("Unexpected: ownership transfer isn't a payment: {tx:?}"))?;
+
+    let round = tx
+        .confirmed_round
+        .ok_or_else(|| anyhow!("Unexpected: tx has no confirmed round: {tx:?}"))?;
+    // Round time is documented as optional (https://developer.algorand.org/docs/rest-apis/indexer/#transaction)
+    // Unclear when it's None. For now we just reject it.
+    let round_time = tx
+        .round_time
+        .ok_or_else(|| anyhow!("Unexpected: tx has no round time: {tx:?}"))?;
+    let id = tx
+        .id
+        .clone()
+        .ok_or_else(|| anyhow!("Unexpected: tx has no id: {tx:?}"))?;
+
+    Ok(Some(OwnershipTransfer {
+        from: tx.sender.parse::<Address>().map_err(Error::msg)?,
+        to: payment.receiver.parse::<Address>().map_err(Error::msg)?,
+        round,
+        date: timestamp_seconds_to_date(round_time)?,
+        tx_id: id.parse()?,
+    }))
+}
+
+/// The DAO whose ownership the tx claims to transfer, if it's an ownership transfer.
+/// Invalid notes are ignored: anyone can send payments with the transfer prefix.
+pub(crate) fn transferred_dao_id(tx: &Transaction) -> Option<DaoId> {
+    tx.payment_transaction.as_ref()?;
+    match base64_maybe_ownership_transfer_note_to_dao_id(tx.note.as_ref()?) {
+        Ok(dao_id) => dao_id,
+        Err(e) => {
+            log::debug!("Ignoring invalid ownership transfer note: {e:?}, tx: {tx:?}");
+            None
+        }
+    }
+}
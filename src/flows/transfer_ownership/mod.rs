@@ -0,0 +1,5 @@
+pub mod history;
+mod note;
+mod tests;
+#[allow(clippy::module_inception)]
+pub mod transfer_ownership;
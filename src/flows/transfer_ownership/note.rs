@@ -0,0 +1,55 @@
+use crate::note::{capi_note_prefix, ensure_note_fits};
+use anyhow::{anyhow, Result};
+use data_encoding::BASE64;
+use mbase::models::dao_id::DaoId;
+use std::convert::TryInto;
+
+fn ownership_transfer_note_identifier() -> [u8; 4] {
+    // utf-8 encoding of "ownr"
+    [111, 119, 110, 114]
+}
+
+/// Note of the payment to the new owner: identifies the payment as transfer of the dao's ownership.
+/// The sender is the previous owner and the receiver the new one, so the note doesn't contain the addresses.
+pub fn ownership_transfer_to_note(dao_id: DaoId) -> Result<Vec<u8>> {
+    let version_bytes = u16::to_be_bytes(1);
+    let note = [
+        capi_note_prefix().as_slice(),
+        &ownership_transfer_note_identifier(),
+        &version_bytes,
+        &dao_id.bytes(),
+    ]
+    .concat();
+    ensure_note_fits(&note, "ownership transfer")?;
+    Ok(note)
+}
+
+/// Parses a possible ownership transfer note.
+/// If the note is not an ownership transfer note, it returns Ok(None).
+pub fn base64_maybe_ownership_transfer_note_to_dao_id(note: &str) -> Result<Option<DaoId>> {
+    let bytes = BASE64.decode(note.as_bytes())?;
+    maybe_ownership_transfer_note_to_dao_id(&bytes)
+}
+
+fn maybe_ownership_transfer_note_to_dao_id(note: &[u8]) -> Result<Option<DaoId>> {
+    if note.get(0..4) != Some(capi_note_prefix().as_slice())
+        || note.get(4..8) != Some(ownership_transfer_note_identifier().as_slice())
+    {
+        return Ok(None);
+    }
+
+    let version_bytes = note
+        .get(8..10)
+        .ok_or_else(|| anyhow!("Not enough bytes in note to get version. Note: {note:?}"))?;
+    let version = u16::from_be_bytes(version_bytes.try_into()?);
+    if version != 1 {
+        return Err(anyhow!(
+            "Not supported ownership transfer version in note: {version}"
+        ));
+    }
+
+    let dao_id_bytes = note
+        .get(10..18)
+        .ok_or_else(|| anyhow!("Not enough bytes in note to get dao id. Note: {note:?}"))?;
+    Ok(Some(dao_id_bytes.try_into()?))
+}
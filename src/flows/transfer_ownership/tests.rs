@@ -0,0 +1,322 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dao_indexer::fake::FakeIndexer,
+        flows::{
+            create_dao::storage::load_dao::load_dao,
+            transfer_ownership::{
+                history::{ownership_history, OwnershipHistory, OwnershipTransfer},
+                note::{
+                    base64_maybe_ownership_transfer_note_to_dao_id, ownership_transfer_to_note,
+                },
+                transfer_ownership::{submit_transfer_ownership, transfer_ownership},
+            },
+        },
+        signer::Sign,
+        testing::{flow::create_dao_flow::create_dao_flow, network_test_util::test_dao_init},
+    };
+    use algonaut::core::Address;
+    use anyhow::Result;
+    use chrono::{TimeZone, Utc};
+    use data_encoding::BASE64;
+    use mbase::models::{dao_app_id::DaoAppId, dao_id::DaoId};
+    use serial_test::serial;
+    use tokio::test;
+
+    const CREATOR: &str = "FTPBN666KYZVB5YYYLRZ6GXWBKWLJJSXQ3N753USSWS2WIAYK7WJTTYRPI";
+    const NEW_OWNER: &str = "Y6O4BH3SUBLIHDU33XTLCGTS7SFBRZWFVBUA5IOKCOO2Z4SBVY6XXT6ZUQ";
+    const OTHER: &str = "2QFRITR4DMZCISHVXLQROMMSY6AE4L3GUEWHURMU7PX4BUGK5JMXBO4RP4";
+    const APP_ID: u64 = 123;
+    const GROUP_1: &str = "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=";
+    const GROUP_2: &str = "AgICAgICAgICAgICAgICAgICAgICAgICAgICAgICAgI=";
+
+    fn address(s: &str) -> Address {
+        s.parse().unwrap()
+    }
+
+    /// The transfer payment (indexer format), in `group`
+    fn transfer_payment_json(
+        id: &str,
+        from: &str,
+        to: &str,
+        round: u64,
+        group: &str,
+        dao_id: DaoId,
+    ) -> String {
+        let note = BASE64.encode(&ownership_transfer_to_note(dao_id).unwrap());
+        format!(
+            r#"{{
+            "payment-transaction": {{ "amount": 0, "close-amount": 0, "receiver": "{to}" }},
+            "close-rewards": 0,
+            "closing-amount": 0,
+            "confirmed-round": {round},
+            "fee": 1000,
+            "first-valid": 1,
+            "genesis-hash": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+            "genesis-id": "sandnet-v1",
+            "group": "{group}",
+            "id": "{id}",
+            "intra-round-offset": 1,
+            "last-valid": 1001,
+            "note": "{note}",
+            "receiver-rewards": 0,
+            "round-time": {time},
+            "sender": "{from}",
+            "sender-rewards": 0,
+            "signature": {{}},
+            "tx-type": "pay"
+        }}"#,
+            time = 1650000000 + round
+        )
+    }
+
+    /// The transfer app call (indexer format), in `group`
+    fn transfer_app_call_json(id: &str, from: &str, to: &str, round: u64, group: &str) -> String {
+        app_call_json(
+            id,
+            from,
+            round,
+            group,
+            &[b"transfer_ownership", &address(to).0],
+        )
+    }
+
+    /// An app call (indexer format), in `group`
+    fn app_call_json(id: &str, from: &str, round: u64, group: &str, args: &[&[u8]]) -> String {
+        let args = args
+            .iter()
+            .map(|arg| format!(r#""{}""#, BASE64.encode(arg)))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            r#"{{
+            "application-transaction": {{
+                "accounts": [],
+                "application-args": [{args}],
+                "application-id": {APP_ID},
+                "foreign-apps": [],
+                "foreign-assets": [],
+                "global-state-schema": {{ "num-byte-slice": 0, "num-uint": 0 }},
+                "local-state-schema": {{ "num-byte-slice": 0, "num-uint": 0 }},
+                "on-completion": "noop"
+            }},
+            "close-rewards": 0,
+            "closing-amount": 0,
+            "confirmed-round": {round},
+            "fee": 1000,
+            "first-valid": 1,
+            "genesis-hash": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+            "genesis-id": "sandnet-v1",
+            "group": "{group}",
+            "id": "{id}",
+            "intra-round-offset": 0,
+            "last-valid": 1001,
+            "receiver-rewards": 0,
+            "round-time": {time},
+            "sender": "{from}",
+            "sender-rewards": 0,
+            "signature": {{}},
+            "tx-type": "appl"
+        }}"#,
+            time = 1650000000 + round
+        )
+    }
+
+    fn dao_id() -> DaoId {
+        DaoId(DaoAppId(APP_ID))
+    }
+
+    #[test]
+    async fn test_ownership_transfer_note() -> Result<()> {
+        let note = BASE64.encode(&ownership_transfer_to_note(dao_id())?);
+
+        assert_eq!(
+            Some(dao_id()),
+            base64_maybe_ownership_transfer_note_to_dao_id(&note)?
+        );
+        // e.g. a withdrawal description
+        assert_eq!(
+            None,
+            base64_maybe_ownership_transfer_note_to_dao_id(&BASE64.encode(b"\x00\x01hello"))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    async fn test_ownership_history() -> Result<()> {
+        // creator -> new owner -> creator
+        let txs = vec![
+            transfer_app_call_json(
+                "ZKLYCEWKDO64V6WCGGZZUI64JWTYN37YCR6E44VZQB3YLL7OJC5Q",
+                CREATOR,
+                NEW_OWNER,
+                10,
+                GROUP_1,
+            ),
+            transfer_payment_json(
+                "HYR6QFQAHFMUUM4JJ5SWJYNRGSF326QARDKCYSWLOPXK5VM4ACOQ",
+                CREATOR,
+                NEW_OWNER,
+                10,
+                GROUP_1,
+                dao_id(),
+            ),
+            transfer_app_call_json(
+                "FZ6SYA5JKB5OEZPM6W2TK2EFUUZZHIQCTUSBHFEZOJS2DIS257DA",
+                NEW_OWNER,
+                CREATOR,
+                20,
+                GROUP_2,
+            ),
+            transfer_payment_json(
+                "MVRMNX3ZJBWNMKEUVQPZOVVCJUW6W2ZGRHXJK5HPZ3WBVHDTSAJA",
+                NEW_OWNER,
+                CREATOR,
+                20,
+                GROUP_2,
+                dao_id(),
+            ),
+        ];
+        let indexer = FakeIndexer::new().with_transactions_json(&format!("[{}]", txs.join(",")))?;
+
+        let history = ownership_history(&indexer, dao_id(), &address(CREATOR)).await?;
+
+        assert_eq!(
+            vec![
+                (address(CREATOR), address(NEW_OWNER), 10),
+                (address(NEW_OWNER), address(CREATOR), 20)
+            ],
+            history
+                .transfers
+                .iter()
+                .map(|t| (t.from, t.to, t.round))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(address(CREATOR), history.creator());
+        assert_eq!(vec![address(CREATOR), address(NEW_OWNER)], history.owners());
+        Ok(())
+    }
+
+    #[test]
+    async fn test_ownership_history_ignores_payments_without_app_call() -> Result<()> {
+        // anyone can send a payment with the transfer note
+        let txs = vec![transfer_payment_json(
+            "HYR6QFQAHFMUUM4JJ5SWJYNRGSF326QARDKCYSWLOPXK5VM4ACOQ",
+            OTHER,
+            NEW_OWNER,
+            10,
+            GROUP_1,
+            dao_id(),
+        )];
+        let indexer = FakeIndexer::new().with_transactions_json(&format!("[{}]", txs.join(",")))?;
+
+        let history = ownership_history(&indexer, dao_id(), &address(NEW_OWNER)).await?;
+
+        assert!(history.transfers.is_empty());
+        assert_eq!(address(NEW_OWNER), history.creator());
+        Ok(())
+    }
+
+    #[test]
+    async fn test_ownership_history_ignores_payments_with_other_app_calls() -> Result<()> {
+        // the owner grouped a payment with the transfer note with app calls that aren't transfers to the receiver
+        let txs = vec![
+            app_call_json(
+                "ZKLYCEWKDO64V6WCGGZZUI64JWTYN37YCR6E44VZQB3YLL7OJC5Q",
+                CREATOR,
+                10,
+                GROUP_1,
+                &[b"withdraw"],
+            ),
+            transfer_app_call_json(
+                "FZ6SYA5JKB5OEZPM6W2TK2EFUUZZHIQCTUSBHFEZOJS2DIS257DA",
+                CREATOR,
+                OTHER,
+                10,
+                GROUP_1,
+            ),
+            transfer_payment_json(
+                "HYR6QFQAHFMUUM4JJ5SWJYNRGSF326QARDKCYSWLOPXK5VM4ACOQ",
+                CREATOR,
+                NEW_OWNER,
+                10,
+                GROUP_1,
+                dao_id(),
+            ),
+        ];
+        let indexer = FakeIndexer::new().with_transactions_json(&format!("[{}]", txs.join(",")))?;
+
+        let history = ownership_history(&indexer, dao_id(), &address(NEW_OWNER)).await?;
+
+        assert!(history.transfers.is_empty());
+        assert_eq!(address(NEW_OWNER), history.creator());
+        Ok(())
+    }
+
+    #[test]
+    async fn test_owner_at() -> Result<()> {
+        let history = OwnershipHistory {
+            owner: address(OTHER),
+            transfers: vec![
+                transfer(CREATOR, NEW_OWNER, 10)?,
+                transfer(NEW_OWNER, OTHER, 20)?,
+            ],
+        };
+
+        assert_eq!(address(CREATOR), history.owner_at(5));
+        assert_eq!(address(NEW_OWNER), history.owner_at(10));
+        assert_eq!(address(NEW_OWNER), history.owner_at(19));
+        assert_eq!(address(OTHER), history.owner_at(20));
+        assert_eq!(
+            vec![address(CREATOR), address(NEW_OWNER), address(OTHER)],
+            history.owners()
+        );
+        Ok(())
+    }
+
+    fn transfer(from: &str, to: &str, round: u64) -> Result<OwnershipTransfer> {
+        Ok(OwnershipTransfer {
+            from: address(from),
+            to: address(to),
+            round,
+            date: Utc.timestamp(1650000000, 0),
+            tx_id: "HYR6QFQAHFMUUM4JJ5SWJYNRGSF326QARDKCYSWLOPXK5VM4ACOQ".parse()?,
+        })
+    }
+
+    #[test]
+    #[serial]
+    async fn test_transfer_ownership() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let algod = &td.algod;
+        let dao = create_dao_flow(td).await?;
+        let new_owner = &td.investor1;
+
+        let to_sign = transfer_ownership(
+            algod,
+            &td.creator.address(),
+            dao.app_id,
+            &new_owner.address(),
+        )
+        .await?;
+        submit_transfer_ownership(algod, to_sign.sign(&td.creator)?)
+            .await?
+            .wait(algod)
+            .await?;
+
+        assert_eq!(new_owner.address(), load_dao(algod, dao.id()).await?.owner);
+
+        // the previous owner can't transfer anymore
+        let to_sign = transfer_ownership(
+            algod,
+            &td.creator.address(),
+            dao.app_id,
+            &td.investor2.address(),
+        )
+        .await?;
+        let res = submit_transfer_ownership(algod, to_sign.sign(&td.creator)?).await;
+        assert!(res.is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use super::note::ownership_transfer_to_note;
+use crate::{
+    dao_algod::DaoAlgod,
+    signer::{Sign, SignedTxs, TxSigner},
+    submission::Submission,
+};
+use algonaut::{
+    core::{Address, MicroAlgos},
+    transaction::{
+        builder::CallApplication, tx_group::TxGroup, Pay, SignedTransaction, Transaction,
+        TxnBuilder,
+    },
+};
+use anyhow::{anyhow, Result};
+use mbase::models::{dao_app_id::DaoAppId, dao_id::DaoId};
+use serde::{Deserialize, Serialize};
+
+/// Sets `new_owner` as owner in the app's global state.
+/// Unlike rekeying the owner account, only the DAO changes hands: the owner keeps their other assets.
+///
+/// The app call is grouped with a 0 payment to the new owner, with a note identifying the transfer:
+/// this is how the ownership history is retrieved (see [super::history::ownership_history]),
+/// and how the new owner finds the DAO (see [crate::queries::my_daos::my_created_daos]).
+pub async fn transfer_ownership(
+    algod: &dyn DaoAlgod,
+    owner: &Address,
+    app_id: DaoAppId,
+    new_owner: &Address,
+) -> Result<TransferOwnershipToSign> {
+    if owner == new_owner {
+        return Err(anyhow!("{new_owner} is already the owner"));
+    }
+
+    let params = algod.suggested_transaction_params().await?;
+
+    let mut app_call_tx = TxnBuilder::with(
+        &params,
+        CallApplication::new(*owner, app_id.0)
+            .app_arguments(vec![
+                "transfer_ownership".as_bytes().to_vec(),
+                new_owner.0.to_vec(),
+            ])
+            .build(),
+    )
+    .build()?;
+
+    let mut notify_new_owner_tx =
+        TxnBuilder::with(&params, Pay::new(*owner, *new_owner, MicroAlgos(0)).build())
+            .note(ownership_transfer_to_note(DaoId(app_id))?)
+            .build()?;
+
+    let txs_for_group = &mut [&mut app_call_tx, &mut notify_new_owner_tx];
+    TxGroup::assign_group_id(txs_for_group)?;
+
+    Ok(TransferOwnershipToSign {
+        app_call_tx,
+        notify_new_owner_tx,
+    })
+}
+
+pub async fn submit_transfer_ownership(
+    algod: &dyn DaoAlgod,
+    signed: TransferOwnershipSigned,
+) -> Result<Submission> {
+    log::debug!("calling submit transfer ownership..");
+
+    let txs = signed.signed_txs();
+
+    let res = algod.broadcast_signed_transactions(&txs).await?;
+    log::debug!("Transfer ownership tx id: {:?}", res.tx_id);
+    Submission::from_response(&res)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferOwnershipToSign {
+    pub app_call_tx: Transaction,
+    pub notify_new_owner_tx: Transaction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferOwnershipSigned {
+    pub app_call_tx: SignedTransaction,
+    pub notify_new_owner_tx: SignedTransaction,
+}
+
+impl Sign for TransferOwnershipToSign {
+    type Signed = TransferOwnershipSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<TransferOwnershipSigned> {
+        Ok(TransferOwnershipSigned {
+            app_call_tx: signer.sign_tx(self.app_call_tx)?,
+            notify_new_owner_tx: signer.sign_tx(self.notify_new_owner_tx)?,
+        })
+    }
+}
+
+impl SignedTxs for TransferOwnershipSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.app_call_tx.clone(), self.notify_new_owner_tx.clone()]
+    }
+}
@@ -14,6 +14,7 @@ mod tests {
     use tokio::test;
 
     use crate::{
+        capi_error::CapiError,
         flows::create_dao::model::Dao,
         state::{account_state::find_asset_holding_or_err, dao_shares::dao_shares},
         testing::{
@@ -149,7 +150,10 @@ mod tests {
         // tests
 
         log::debug!("res error: {res:?}");
-        assert!(res.is_err());
+        assert_eq!(
+            Some(&CapiError::RaiseNotEnded),
+            res.unwrap_err().downcast_ref::<CapiError>()
+        );
 
         Ok(())
     }
@@ -1,17 +1,19 @@
+use crate::{
+    capi_error::broadcast_app_txs,
+    dao_algod::DaoAlgod,
+    signer::{Sign, SignedTxs, TxSigner},
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
-    core::{Address, MicroAlgos},
+    core::Address,
     transaction::{builder::CloseApplication, SignedTransaction, Transaction, TxnBuilder},
 };
 use anyhow::Result;
-use mbase::models::{dao_app_id::DaoAppId, tx_id::TxId};
+use mbase::models::dao_app_id::DaoAppId;
 use serde::{Deserialize, Serialize};
 
-// TODO no constants
-pub const MIN_BALANCE: MicroAlgos = MicroAlgos(100_000);
-
 pub async fn unlock(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     investor: Address,
     app_id: DaoAppId,
     shares_asset_id: u64,
@@ -36,17 +38,17 @@ pub async fn unlock(
     })
 }
 
-pub async fn submit_unlock(algod: &Algod, signed: UnlockSigned) -> Result<TxId> {
+pub async fn submit_unlock(algod: &dyn DaoAlgod, signed: UnlockSigned) -> Result<Submission> {
     log::debug!("calling submit unlock..");
     // crate::debug_msg_pack_submit_par::log_to_msg_pack(&signed);
 
-    let txs = vec![signed.central_app_optout_tx];
+    let txs = signed.signed_txs();
 
     // mbase::teal::debug_teal_rendered(&txs, "dao_app_approval").unwrap();
 
-    let res = algod.broadcast_signed_transactions(&txs).await?;
+    let res = broadcast_app_txs(algod, &txs).await?;
     log::debug!("Unlock tx id: {:?}", res.tx_id);
-    res.tx_id.parse()
+    Submission::from_response(&res)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -58,3 +60,19 @@ pub struct UnlockToSign {
 pub struct UnlockSigned {
     pub central_app_optout_tx: SignedTransaction,
 }
+
+impl Sign for UnlockToSign {
+    type Signed = UnlockSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<UnlockSigned> {
+        Ok(UnlockSigned {
+            central_app_optout_tx: signer.sign_tx(self.central_app_optout_tx)?,
+        })
+    }
+}
+
+impl SignedTxs for UnlockSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.central_app_optout_tx.clone()]
+    }
+}
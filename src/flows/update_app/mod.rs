@@ -1,3 +1,4 @@
 mod tests;
 #[allow(clippy::module_inception)]
 pub mod update;
+pub mod upgrade;
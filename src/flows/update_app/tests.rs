@@ -1,18 +1,33 @@
 #[cfg(test)]
 mod tests {
-    use crate::testing::{
-        flow::{
-            claim_flow::{claim_flow, test::claim_precs_with_dao},
-            create_dao_flow::test::create_dao_flow,
-            update_dao_flow::update_dao_flow,
+    use crate::{
+        flows::{
+            create_dao::setup::create_app::approval_v1_values,
+            update_app::upgrade::{submit_upgrade_dao_app, upgrade_dao_app_with_registry},
+        },
+        signer::Sign,
+        teal::TealApi,
+        teal_registry::{dao_teal_registry, ApprovalVersion},
+        testing::{
+            flow::{
+                claim_flow::{claim_flow, test::claim_precs_with_dao},
+                create_dao_flow::test::create_dao_flow,
+                update_dao_flow::update_dao_flow,
+            },
+            local_teal_api::LocalTealApi,
+            network_test_util::test_dao_init,
         },
-        network_test_util::test_dao_init,
     };
     use anyhow::Result;
+    use async_trait::async_trait;
     use mbase::{
+        api::{
+            contract::Contract,
+            version::{Version, VersionedTealSourceTemplate, Versions},
+        },
         models::{funds::FundsAmount, share_amount::ShareAmount},
-        state::dao_app_state::{dao_global_state, dao_investor_state},
-        teal::TealSource,
+        state::dao_app_state::{dao_global_state, dao_investor_state, CentralAppGlobalState},
+        teal::{TealSource, TealSourceTemplate},
     };
     use serial_test::serial;
     use tokio::test;
@@ -122,6 +137,94 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[serial]
+    async fn test_upgrade_dao_app() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let algod = &td.algod;
+
+        // precs
+
+        let owner = &td.creator;
+        let dao = create_dao_flow(td).await?;
+
+        let global_state_before_upgrade = dao_global_state(algod, dao.app_id).await?;
+
+        // flow
+
+        // v2 is rendered like v1
+        let registry = dao_teal_registry().with_approval(ApprovalVersion {
+            version: Version(2),
+            params: dao_teal_registry().approval_params(Version(1))?,
+            values: approval_v1_values,
+        });
+        let to_sign = upgrade_dao_app_with_registry(
+            algod,
+            &ApprovalV2TealApi,
+            &registry,
+            &dao,
+            &td.dao_deps(),
+            td.precision,
+            FundsAmount::new(5_000_000_000_000),
+        )
+        .await?;
+        let submission = submit_upgrade_dao_app(algod, to_sign.clone().sign(owner)?).await?;
+        submission.wait(algod).await?;
+
+        // test
+
+        assert_eq!(1, to_sign.from.app_approval.0);
+        assert_eq!(2, to_sign.to.app_approval.0);
+        assert_eq!(1, to_sign.to.app_clear.0);
+
+        // the new version is saved, the rest of the state stays as it was
+        let global_state_after_upgrade = dao_global_state(algod, dao.app_id).await?;
+        log::debug!("global_state_after_upgrade: {global_state_after_upgrade:?}");
+        assert_eq!(
+            CentralAppGlobalState {
+                app_approval_version: Version(2),
+                ..global_state_before_upgrade
+            },
+            global_state_after_upgrade
+        );
+
+        Ok(())
+    }
+
+    /// Serves the local templates, with an approval v2: the v1 template with a different program
+    struct ApprovalV2TealApi;
+
+    #[async_trait]
+    impl TealApi for ApprovalV2TealApi {
+        async fn last_versions(&self) -> Result<Versions> {
+            Ok(Versions {
+                app_approval: Version(2),
+                app_clear: Version(1),
+            })
+        }
+
+        async fn template(
+            &self,
+            contract: Contract,
+            version: Version,
+        ) -> Result<VersionedTealSourceTemplate> {
+            match (&contract, version.0) {
+                (Contract::DaoAppApproval, 2) => {
+                    let v1 = LocalTealApi
+                        .template(Contract::DaoAppApproval, Version(1))
+                        .await?;
+                    // a no-op at the end: changes the program, not what it does
+                    let template = [v1.template.0.as_slice(), b"\nint 2\npop\n"].concat();
+                    Ok(VersionedTealSourceTemplate::new(
+                        TealSourceTemplate(template),
+                        version,
+                    ))
+                }
+                _ => LocalTealApi.template(contract, version).await,
+            }
+        }
+    }
+
     fn always_accept_teal() -> TealSource {
         TealSource(
             r#"
@@ -1,14 +1,18 @@
+use crate::{
+    dao_algod::DaoAlgod,
+    signer::{Sign, SignedTxs, TxSigner},
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
     core::{Address, CompiledTeal},
     transaction::{builder::UpdateApplication, SignedTransaction, Transaction, TxnBuilder},
 };
 use anyhow::Result;
-use mbase::models::{dao_app_id::DaoAppId, tx_id::TxId};
+use mbase::models::dao_app_id::DaoAppId;
 use serde::{Deserialize, Serialize};
 
 pub async fn update(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     owner: &Address,
     app_id: DaoAppId,
     approval: CompiledTeal,
@@ -25,17 +29,17 @@ pub async fn update(
     Ok(UpdateAppToSign { update })
 }
 
-pub async fn submit_update(algod: &Algod, signed: UpdateAppSigned) -> Result<TxId> {
+pub async fn submit_update(algod: &dyn DaoAlgod, signed: UpdateAppSigned) -> Result<Submission> {
     log::debug!("calling submit app update..");
     // crate::debug_msg_pack_submit_par::log_to_msg_pack(&signed);
 
-    let txs = vec![signed.update];
+    let txs = signed.signed_txs();
 
     // mbase::teal::debug_teal_rendered(&txs, "dao_app_approval").unwrap();
 
     let res = algod.broadcast_signed_transactions(&txs).await?;
     log::debug!("Unlock tx id: {:?}", res.tx_id);
-    res.tx_id.parse()
+    Submission::from_response(&res)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -47,3 +51,19 @@ pub struct UpdateAppToSign {
 pub struct UpdateAppSigned {
     pub update: SignedTransaction,
 }
+
+impl Sign for UpdateAppToSign {
+    type Signed = UpdateAppSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<UpdateAppSigned> {
+        Ok(UpdateAppSigned {
+            update: signer.sign_tx(self.update)?,
+        })
+    }
+}
+
+impl SignedTxs for UpdateAppSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.update.clone()]
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{
+    dao_algod::DaoAlgod,
+    flows::{
+        create_dao::model::Dao,
+        update_data::update_data::{update_data_app_call, UpdatableDaoData},
+    },
+    signer::{Sign, SignedTxs, TxSigner},
+    state::dao_app_state::dao_global_state,
+    submission::Submission,
+    teal::TealApi,
+    teal_registry::{dao_teal_registry, ApprovalTemplateParams, TealRegistry},
+};
+use algonaut::transaction::{
+    builder::UpdateApplication, tx_group::TxGroup, SignedTransaction, Transaction, TxnBuilder,
+};
+use anyhow::Result;
+use mbase::{
+    api::version::Versions,
+    models::{
+        capi_deps::{CapiAddress, CapiAssetDaoDeps},
+        funds::FundsAmount,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// Updates the DAO app to the last versions of its programs.
+/// The templates are rendered with the DAO's parameters. `precision` and `max_raisable_amount` aren't stored:
+/// they have to be the ones the DAO was created with.
+pub async fn upgrade_dao_app(
+    algod: &dyn DaoAlgod,
+    teal_api: &dyn TealApi,
+    dao: &Dao,
+    capi_deps: &CapiAssetDaoDeps,
+    precision: u64,
+    max_raisable_amount: FundsAmount,
+) -> Result<UpgradeDaoAppToSign> {
+    upgrade_dao_app_with_registry(
+        algod,
+        teal_api,
+        &dao_teal_registry(),
+        dao,
+        capi_deps,
+        precision,
+        max_raisable_amount,
+    )
+    .await
+}
+
+/// [upgrade_dao_app], supporting the versions of `registry`
+pub(crate) async fn upgrade_dao_app_with_registry(
+    algod: &dyn DaoAlgod,
+    teal_api: &dyn TealApi,
+    registry: &TealRegistry,
+    dao: &Dao,
+    capi_deps: &CapiAssetDaoDeps,
+    precision: u64,
+    max_raisable_amount: FundsAmount,
+) -> Result<UpgradeDaoAppToSign> {
+    let params = algod.suggested_transaction_params().await?;
+    let current_state = dao_global_state(algod, dao.app_id).await?;
+
+    let from = Versions {
+        app_approval: current_state.app_approval_version,
+        app_clear: current_state.app_clear_version,
+    };
+    let to = teal_api.last_versions().await?;
+    log::debug!(
+        "Upgrading dao app: {} from: {from:?} to: {to:?}",
+        dao.app_id.0
+    );
+
+    let approval_template = registry.load_approval(teal_api, to.app_approval).await?;
+    let clear_template = registry.load_clear(teal_api, to.app_clear).await?;
+
+    let approval_source = registry.render_approval(
+        &approval_template,
+        &ApprovalTemplateParams {
+            share_supply: dao.token_supply,
+            precision,
+            investors_share: dao.investors_share,
+            capi_address: CapiAddress(capi_deps.address.0),
+            capi_percentage: capi_deps.escrow_percentage,
+            share_price: dao.share_price,
+            max_raisable_amount,
+        },
+    )?;
+    let approval = algod.compile_teal(&approval_source.0).await?;
+    let clear_source = registry.render_clear(&clear_template)?;
+    let clear = algod.compile_teal(&clear_source.0).await?;
+
+    let mut update = TxnBuilder::with(
+        &params,
+        UpdateApplication::new(dao.owner, dao.app_id.0, approval, clear).build(),
+    )
+    .build()?;
+
+    // sets the new versions in global state, leaving the data as it is.
+    // it's executed by the updated programs, after the update.
+    let mut update_versions = update_data_app_call(
+        &params,
+        &dao.owner,
+        dao.app_id,
+        &UpdatableDaoData {
+            project_name: dao.name.clone(),
+            project_desc_url: dao.descr_url.clone(),
+            // no image url: keeps the current image nft
+            image_url: None,
+            image_nft_metadata: None,
+            social_media_url: dao.social_media_url.clone(),
+            prospectus: dao.prospectus.clone(),
+            min_invest_shares: dao.min_invest_amount,
+            max_invest_shares: dao.max_invest_amount,
+        },
+        Versions {
+            app_approval: to.app_approval,
+            app_clear: to.app_clear,
+        },
+    )?;
+
+    TxGroup::assign_group_id(&mut [&mut update, &mut update_versions])?;
+
+    Ok(UpgradeDaoAppToSign {
+        update,
+        update_versions,
+        from,
+        to,
+    })
+}
+
+pub async fn submit_upgrade_dao_app(
+    algod: &dyn DaoAlgod,
+    signed: UpgradeDaoAppSigned,
+) -> Result<Submission> {
+    log::debug!("calling submit app upgrade..");
+
+    let txs = signed.signed_txs();
+
+    let res = algod.broadcast_signed_transactions(&txs).await?;
+    log::debug!("Upgrade tx id: {:?}", res.tx_id);
+    Submission::from_response(&res)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpgradeDaoAppToSign {
+    pub update: Transaction,
+    pub update_versions: Transaction,
+
+    /// Versions currently used by the app
+    pub from: Versions,
+    pub to: Versions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeDaoAppSigned {
+    pub update: SignedTransaction,
+    pub update_versions: SignedTransaction,
+}
+
+impl Sign for UpgradeDaoAppToSign {
+    type Signed = UpgradeDaoAppSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<UpgradeDaoAppSigned> {
+        Ok(UpgradeDaoAppSigned {
+            update: signer.sign_tx(self.update)?,
+            update_versions: signer.sign_tx(self.update_versions)?,
+        })
+    }
+}
+
+impl SignedTxs for UpgradeDaoAppSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.update.clone(), self.update_versions.clone()]
+    }
+}
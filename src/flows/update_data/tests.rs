@@ -126,6 +126,7 @@ mod tests {
             project_name: new_project_name.clone(),
             project_desc_url: new_project_desc.clone(),
             image_url: new_image_url,
+            image_nft_metadata: None,
             social_media_url: new_social_media_url.clone(),
             prospectus: Some(Prospectus {
                 hash: "some_hash".to_owned(),
@@ -1,16 +1,24 @@
-use crate::{common_txs::pay, flows::create_dao::setup::setup_app::str_opt_def_to_bytes};
+use crate::{
+    common_txs::pay,
+    dao_algod::DaoAlgod,
+    flows::create_dao::setup::setup_app::str_opt_def_to_bytes,
+    image_metadata::{image_nft_arg, ImageNftMetadata},
+    min_balance::ASSET_MIN_BALANCE,
+    signer::{Sign, SignedTxs, TxSigner},
+    state::dao_app_state::dao_global_state,
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
-    core::{Address, MicroAlgos},
+    core::{Address, MicroAlgos, SuggestedTransactionParams},
     transaction::{
         builder::CallApplication, tx_group::TxGroup, SignedTransaction, Transaction, TxnBuilder,
     },
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use mbase::{
     api::version::{versions_to_bytes, Versions},
-    models::{dao_app_id::DaoAppId, share_amount::ShareAmount, tx_id::TxId},
-    state::dao_app_state::{dao_global_state, Prospectus},
+    models::{dao_app_id::DaoAppId, share_amount::ShareAmount},
+    state::dao_app_state::Prospectus,
 };
 use serde::{Deserialize, Serialize};
 
@@ -21,6 +29,8 @@ pub struct UpdatableDaoData {
     pub project_desc_url: Option<String>,
 
     pub image_url: Option<String>,
+    /// The new image NFT is created with it, if set. Requires the image url.
+    pub image_nft_metadata: Option<ImageNftMetadata>,
     pub social_media_url: String,
 
     pub prospectus: Option<Prospectus>,
@@ -29,7 +39,7 @@ pub struct UpdatableDaoData {
 }
 
 pub async fn update_data(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     owner: &Address,
     app_id: DaoAppId,
     data: &UpdatableDaoData,
@@ -45,8 +55,43 @@ pub async fn update_data(
         app_clear: current_state.app_clear_version,
     };
 
+    let mut update = update_data_app_call(&params, owner, app_id, data, versions)?;
+
+    // pay for optional image nft create tx
+    update.fee = update.fee * 2;
+
+    let increase_min_balance_tx = if data.image_url.is_some() {
+        let mut pay_tx = pay(
+            &params,
+            owner,
+            &app_id.address(),
+            MicroAlgos(ASSET_MIN_BALANCE),
+        )?;
+        TxGroup::assign_group_id(&mut [&mut pay_tx, &mut update])?;
+        Some(pay_tx)
+    } else {
+        None
+    };
+
+    Ok(UpdateAppToSign {
+        update,
+        increase_min_balance_tx,
+    })
+}
+
+/// First arg of the update data app call
+pub(crate) const UPDATE_DATA_APP_ARG: &str = "update_data";
+
+/// App call that sets the data and the versions in global state
+pub(crate) fn update_data_app_call(
+    params: &SuggestedTransactionParams,
+    owner: &Address,
+    app_id: DaoAppId,
+    data: &UpdatableDaoData,
+    versions: Versions,
+) -> Result<Transaction> {
     let mut args = vec![
-        "update_data".as_bytes().to_vec(),
+        UPDATE_DATA_APP_ARG.as_bytes().to_vec(),
         data.project_name.as_bytes().to_vec(),
         data.project_desc_url
             .as_ref()
@@ -60,51 +105,36 @@ pub async fn update_data(
         data.max_invest_shares.val().to_be_bytes().to_vec(),
     ];
 
-    if let Some(image_url) = &data.image_url {
-        args.push(image_url.as_bytes().to_vec())
+    match (&data.image_url, &data.image_nft_metadata) {
+        (Some(image_url), metadata) => args.push(image_nft_arg(image_url, metadata.as_ref())?),
+        (None, Some(_)) => return Err(anyhow!("Image nft metadata without image url")),
+        (None, None) => {}
     }
 
     // We might make these updates more granular later. For now everything in 1 call.
-    let mut update = TxnBuilder::with(
-        &params,
+    Ok(TxnBuilder::with(
+        params,
         CallApplication::new(*owner, app_id.0)
             .app_arguments(args)
             .build(),
     )
-    .build()?;
-
-    // pay for optional image nft create tx
-    update.fee = update.fee * 2;
-
-    let increase_min_balance_tx = if data.image_url.is_some() {
-        let mut pay_tx = pay(&params, owner, &app_id.address(), MicroAlgos(100_000))?;
-        TxGroup::assign_group_id(&mut [&mut pay_tx, &mut update])?;
-        Some(pay_tx)
-    } else {
-        None
-    };
-
-    Ok(UpdateAppToSign {
-        update,
-        increase_min_balance_tx,
-    })
+    .build()?)
 }
 
-pub async fn submit_update_data(algod: &Algod, signed: UpdateDaoDataSigned) -> Result<TxId> {
+pub async fn submit_update_data(
+    algod: &dyn DaoAlgod,
+    signed: UpdateDaoDataSigned,
+) -> Result<Submission> {
     log::debug!("calling submit app data update..");
     // crate::debug_msg_pack_submit_par::log_to_msg_pack(&signed);
 
-    let mut txs = vec![];
-    if let Some(tx) = signed.increase_min_balance_tx {
-        txs.push(tx)
-    };
-    txs.push(signed.update);
+    let txs = signed.signed_txs();
 
     // mbase::teal::debug_teal_rendered(&txs, "dao_app_approval").unwrap();
 
     let res = algod.broadcast_signed_transactions(&txs).await?;
     log::debug!("Unlock tx id: {:?}", res.tx_id);
-    res.tx_id.parse()
+    Submission::from_response(&res)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -118,3 +148,28 @@ pub struct UpdateDaoDataSigned {
     pub update: SignedTransaction,
     pub increase_min_balance_tx: Option<SignedTransaction>, // for possible image nft being created
 }
+
+impl Sign for UpdateAppToSign {
+    type Signed = UpdateDaoDataSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<UpdateDaoDataSigned> {
+        Ok(UpdateDaoDataSigned {
+            update: signer.sign_tx(self.update)?,
+            increase_min_balance_tx: self
+                .increase_min_balance_tx
+                .map(|tx| signer.sign_tx(tx))
+                .transpose()?,
+        })
+    }
+}
+
+impl SignedTxs for UpdateDaoDataSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        let mut txs = vec![];
+        if let Some(tx) = &self.increase_min_balance_tx {
+            txs.push(tx.clone())
+        };
+        txs.push(self.update.clone());
+        txs
+    }
+}
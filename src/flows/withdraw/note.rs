@@ -1,4 +1,5 @@
 use super::withdraw::WithdrawalInputs;
+use crate::note::ensure_note_fits;
 use anyhow::{anyhow, Result};
 use data_encoding::BASE64;
 use std::convert::TryInto;
@@ -11,7 +12,9 @@ pub fn withdrawal_to_note(item: &WithdrawalInputs) -> Result<Vec<u8>> {
     // in a test it compressed ~40% of regular english text (from random wikipedia article)
     // it increased WASM file size by only ~16kb
     let description_bytes = item.description.as_bytes();
-    Ok([version_bytes.as_slice(), description_bytes].concat())
+    let note = [version_bytes.as_slice(), description_bytes].concat();
+    ensure_note_fits(&note, "withdrawal description")?;
+    Ok(note)
 }
 
 pub fn base64_withdrawal_note_to_withdrawal_description(note: &str) -> Result<String> {
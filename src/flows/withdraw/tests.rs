@@ -1,7 +1,14 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        flows::withdraw::withdraw::{submit_withdraw, withdraw, WithdrawSigned, WithdrawalInputs},
+        capi_error::CapiError,
+        flows::{
+            create_dao::validate_specs::MAX_NOTE_LEN,
+            withdraw::{
+                note::withdrawal_to_note,
+                withdraw::{submit_withdraw, withdraw, WithdrawSigned, WithdrawalInputs},
+            },
+        },
         state::account_state::funds_holdings,
         testing::{
             create_and_submit_txs::transfer_tokens_submit,
@@ -34,6 +41,23 @@ mod tests {
     use serial_test::serial;
     use tokio::test;
 
+    #[test]
+    async fn test_withdrawal_description_has_to_fit_in_the_note() -> Result<()> {
+        let inputs = |description_len: usize| WithdrawalInputs {
+            amount: FundsAmount::new(1_000_000),
+            description: "a".repeat(description_len),
+        };
+
+        // the version takes 2 bytes
+        assert_eq!(
+            MAX_NOTE_LEN,
+            withdrawal_to_note(&inputs(MAX_NOTE_LEN - 2))?.len()
+        );
+        assert!(withdrawal_to_note(&inputs(MAX_NOTE_LEN - 1)).is_err());
+
+        Ok(())
+    }
+
     #[test]
     #[serial]
     async fn test_withdraw_directly_sent_funds_fails() -> Result<()> {
@@ -68,7 +92,10 @@ mod tests {
 
         // test
 
-        assert!(res.is_err());
+        assert_eq!(
+            Some(&CapiError::NotEnoughFunds),
+            res.unwrap_err().downcast_ref::<CapiError>()
+        );
 
         Ok(())
     }
@@ -177,7 +204,10 @@ mod tests {
 
         // test
 
-        assert!(withdraw_res.is_err());
+        assert_eq!(
+            Some(&CapiError::NotEnoughFunds),
+            withdraw_res.unwrap_err().downcast_ref::<CapiError>()
+        );
 
         test_withdrawal_did_not_succeed(
             algod,
@@ -247,7 +277,10 @@ mod tests {
 
         // test
 
-        assert!(withdraw_res.is_err());
+        assert_eq!(
+            Some(&CapiError::NotOwner),
+            withdraw_res.unwrap_err().downcast_ref::<CapiError>()
+        );
 
         test_withdrawal_did_not_succeed(
             algod,
@@ -335,7 +368,10 @@ mod tests {
         // test
 
         println!("res: {res:?}");
-        assert!(res.is_err());
+        assert_eq!(
+            Some(&CapiError::RaiseNotEnded),
+            res.unwrap_err().downcast_ref::<CapiError>()
+        );
 
         Ok(())
     }
@@ -417,7 +453,10 @@ mod tests {
         if can_withdraw {
             assert!(res.is_ok());
         } else {
-            assert!(res.is_err());
+            assert_eq!(
+                Some(&CapiError::FundsTargetNotReached),
+                res.unwrap_err().downcast_ref::<CapiError>()
+            );
         }
 
         Ok(())
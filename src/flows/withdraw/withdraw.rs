@@ -1,22 +1,23 @@
-use crate::flows::withdraw::note::withdrawal_to_note;
+use crate::{
+    capi_error::broadcast_app_txs,
+    dao_algod::DaoAlgod,
+    flows::withdraw::note::withdrawal_to_note,
+    signer::{Sign, SignedTxs, TxSigner},
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
-    core::{Address, MicroAlgos},
+    core::Address,
     transaction::{builder::CallApplication, SignedTransaction, Transaction, TxnBuilder},
 };
 use anyhow::Result;
 use mbase::models::{
     dao_app_id::DaoAppId,
     funds::{FundsAmount, FundsAssetId},
-    tx_id::TxId,
 };
 use serde::{Deserialize, Serialize};
 
-// TODO no constants
-pub const MIN_BALANCE: MicroAlgos = MicroAlgos(100_000);
-
 pub async fn withdraw(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     sender: Address,
     inputs: &WithdrawalInputs,
     app_id: DaoAppId,
@@ -47,19 +48,18 @@ pub async fn withdraw(
     })
 }
 
-pub async fn submit_withdraw(algod: &Algod, signed: &WithdrawSigned) -> Result<TxId> {
+pub async fn submit_withdraw(algod: &dyn DaoAlgod, signed: &WithdrawSigned) -> Result<Submission> {
     // crate::debug_msg_pack_submit_par::log_to_msg_pack(&signed);
     log::debug!("Submit withdrawal txs..");
 
-    let txs = vec![signed.withdraw_tx.clone()];
+    let txs = signed.signed_txs();
 
-    // crate::dryrun_util::dryrun_all(algod, &txs).await?;
     // mbase::teal::debug_teal_rendered(&txs, "dao_app_approval").unwrap();
 
-    let res = algod.broadcast_signed_transactions(&txs).await?;
+    let res = broadcast_app_txs(algod, &txs).await?;
     log::debug!("Withdrawal txs tx id: {}", res.tx_id);
 
-    res.tx_id.parse()
+    Submission::from_response(&res)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -72,6 +72,22 @@ pub struct WithdrawSigned {
     pub withdraw_tx: SignedTransaction,
 }
 
+impl Sign for WithdrawToSign {
+    type Signed = WithdrawSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<WithdrawSigned> {
+        Ok(WithdrawSigned {
+            withdraw_tx: signer.sign_tx(self.withdraw_tx)?,
+        })
+    }
+}
+
+impl SignedTxs for WithdrawSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.withdraw_tx.clone()]
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct WithdrawalInputs {
     pub amount: FundsAmount,
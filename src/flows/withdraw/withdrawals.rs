@@ -1,11 +1,12 @@
-use crate::flows::{
-    create_dao::storage::load_dao::load_dao,
-    withdraw::note::base64_withdrawal_note_to_withdrawal_description,
-};
-use algonaut::{
-    algod::v2::Algod, core::Address, indexer::v2::Indexer,
-    model::indexer::v2::QueryAccountTransaction,
+use crate::{
+    dao_algod::DaoAlgod,
+    dao_indexer::DaoIndexer,
+    flows::{
+        create_dao::storage::load_dao::load_dao, transfer_ownership::history::ownership_history,
+        withdraw::note::base64_withdrawal_note_to_withdrawal_description,
+    },
 };
+use algonaut::{core::Address, model::indexer::v2::QueryAccountTransaction};
 use anyhow::{anyhow, Error, Result};
 use chrono::{DateTime, Utc};
 use mbase::date_util::timestamp_seconds_to_date;
@@ -18,14 +19,35 @@ use serde::{Deserialize, Serialize};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn withdrawals(
-    algod: &Algod,
-    indexer: &Indexer,
+    algod: &dyn DaoAlgod,
+    indexer: &dyn DaoIndexer,
     dao_id: DaoId,
     funds_asset: FundsAssetId,
     before_time: &Option<DateTime<Utc>>,
     after_time: &Option<DateTime<Utc>>,
 ) -> Result<Vec<Withdrawal>> {
     let dao = load_dao(algod, dao_id).await?;
+    owner_withdrawals(
+        indexer,
+        dao_id,
+        &dao.owner,
+        funds_asset,
+        before_time,
+        after_time,
+    )
+    .await
+}
+
+/// Like [withdrawals], for callers that already know the current owner (e.g. having loaded the dao): doesn't need the algod.
+pub async fn owner_withdrawals(
+    indexer: &dyn DaoIndexer,
+    dao_id: DaoId,
+    owner: &Address,
+    funds_asset: FundsAssetId,
+    before_time: &Option<DateTime<Utc>>,
+    after_time: &Option<DateTime<Utc>>,
+) -> Result<Vec<Withdrawal>> {
+    let app_id = dao_id.0;
 
     // let before_time_formatted = before_time.map(|t| t.to_rfc3339());
     // let after_time_formatted = after_time.map(|t| t.to_rfc3339());
@@ -46,11 +68,14 @@ pub async fn withdrawals(
         ..Default::default()
     };
 
-    // TODO filter txs by receiver (creator) - this returns everything associated with creator
-    let txs = indexer
-        .account_transactions(&dao.owner, &query)
-        .await?
-        .transactions;
+    // withdrawals go to whoever owned the dao at the time
+    let owners = ownership_history(indexer, dao_id, owner).await?.owners();
+
+    // TODO filter txs by receiver (owner) - this returns everything associated with the owners
+    let mut txs = vec![];
+    for owner in &owners {
+        txs.extend(indexer.account_transactions(owner, &query).await?);
+    }
 
     // TODO (low prio) compare performance of above vs this (i.e. querying account txs vs txs with receiver field)
     // Note that none is using note prefix currently, see note in query above.
@@ -65,7 +90,7 @@ pub async fn withdrawals(
 
     for tx in &txs {
         if let Some(app_call) = tx.application_transaction.clone() {
-            if app_call.application_id == dao.app_id.0 {
+            if app_call.application_id == app_id.0 {
                 for inner_tx in &tx.inner_txns {
                     // withdrawals are xfers from the app to the owner
                     if let Some(xfer) = inner_tx.asset_transfer_transaction.clone() {
@@ -76,10 +101,10 @@ pub async fn withdrawals(
                             xfer.receiver.parse::<Address>().map_err(Error::msg)?;
 
                         // account_transactions returns all the txs "related" to the account, i.e. can be sender or receiver
-                        // we're interested only in central escrow -> creator
+                        // we're interested only in central escrow -> owner
                         if FundsAssetId(xfer.asset_id) == funds_asset
-                            && sender_address == dao.app_address()
-                            && receiver_address == dao.owner
+                            && sender_address == app_id.address()
+                            && owners.contains(&receiver_address)
                         {
                             // for now the only payload is the description
                             let withdrawal_description = match &tx.note {
@@ -118,7 +143,7 @@ pub async fn withdrawals(
                                 description: withdrawal_description,
                                 date: timestamp_seconds_to_date(round_time)?,
                                 tx_id: id.parse()?,
-                                // this should be always the owner - we return it for the UI, which currently shows addresses for all the activity entries
+                                // the owner at the time - we return it for the UI, which currently shows addresses for all the activity entries
                                 address: receiver_address,
                             })
                         }
@@ -0,0 +1,189 @@
+use crate::{dao_algod::DaoAlgod, flows::create_dao::model::Dao};
+use anyhow::{anyhow, Result};
+use data_encoding::BASE64;
+use mbase::models::dao_app_id::DaoAppId;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::convert::TryInto;
+
+mod tests;
+
+/// Suffix of the asset url that marks the NFT as ARC-3, when the asset name doesn't end with "@arc3"
+pub const ARC3_URL_SUFFIX: &str = "#arc3";
+
+/// ARC-3 metadata of the DAO's image NFT, see https://github.com/algorandfoundation/ARCs/blob/main/ARCs/arc-0003.md
+/// The NFT's url points to the JSON document (see [Arc3Metadata::to_json]), which points to the image.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Arc3Metadata {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub image: String,
+    /// "sha256-" followed by the base64 encoded SHA-256 of the image
+    pub image_integrity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_mimetype: Option<String>,
+    pub properties: DaoImageProperties,
+}
+
+/// Links the NFT with its DAO
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaoImageProperties {
+    pub dao_app_id: u64,
+}
+
+impl Arc3Metadata {
+    /// The document to publish: the metadata hash is calculated with these exact bytes
+    pub fn to_json(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    pub fn from_json(json: &[u8]) -> Result<Arc3Metadata> {
+        Ok(serde_json::from_slice(json)?)
+    }
+}
+
+/// Metadata of the image NFT of the DAO with `app_id`: the NFT is created when setting up the app, so the app exists already.
+pub fn dao_image_metadata(
+    dao_name: &str,
+    description: Option<String>,
+    image_url: &str,
+    image: &[u8],
+    image_mimetype: Option<String>,
+    app_id: DaoAppId,
+) -> Arc3Metadata {
+    Arc3Metadata {
+        name: dao_name.to_owned(),
+        description,
+        image: image_url.to_owned(),
+        image_integrity: image_integrity(image),
+        image_mimetype,
+        properties: DaoImageProperties {
+            dao_app_id: app_id.0,
+        },
+    }
+}
+
+/// Where the NFT's metadata document is published and its hash (see [metadata_hash]).
+/// Passed to the setup and the data update, which create the NFT with them: the url is the NFT's url (normally marked as ARC-3, see [arc3_url]).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageNftMetadata {
+    pub url: String,
+    pub hash: [u8; 32],
+}
+
+/// App argument with the DAO's image url and its NFT's metadata, if passed: a single argument, as the setup is at the app args limit.
+/// Each url is prefixed with its length (u16, big endian) and the hash follows the metadata url.
+/// Without metadata, the metadata url is empty and there's no hash: the NFT is created with the image url.
+pub fn image_nft_arg(image_url: &str, metadata: Option<&ImageNftMetadata>) -> Result<Vec<u8>> {
+    let mut arg = length_prefixed(image_url)?;
+    match metadata {
+        Some(metadata) => {
+            arg.extend(length_prefixed(&metadata.url)?);
+            arg.extend_from_slice(&metadata.hash);
+        }
+        None => arg.extend(length_prefixed("")?),
+    }
+    Ok(arg)
+}
+
+fn length_prefixed(url: &str) -> Result<Vec<u8>> {
+    let len: u16 = url
+        .len()
+        .try_into()
+        .map_err(|_| anyhow!("Url is too long: {} bytes", url.len()))?;
+    Ok([len.to_be_bytes().as_slice(), url.as_bytes()].concat())
+}
+
+/// The metadata hash of the NFT: SHA-256 of the published JSON document
+pub fn metadata_hash(metadata_json: &[u8]) -> [u8; 32] {
+    Sha256::digest(metadata_json).into()
+}
+
+/// Subresource integrity string (as used in `image_integrity`) of the image
+pub fn image_integrity(image: &[u8]) -> String {
+    format!("sha256-{}", BASE64.encode(&Sha256::digest(image)))
+}
+
+/// The url of the metadata document, marked as ARC-3
+pub fn arc3_url(metadata_url: &str) -> String {
+    if metadata_url.ends_with(ARC3_URL_SUFFIX) {
+        metadata_url.to_owned()
+    } else {
+        format!("{metadata_url}{ARC3_URL_SUFFIX}")
+    }
+}
+
+/// Result of checking the DAO's image NFT against the metadata document its url points to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageNftVerification {
+    /// The metadata's image is the image url saved in the DAO
+    pub image_url_matches: bool,
+    /// The NFT's url is marked as ARC-3
+    pub url_is_arc3: bool,
+    /// The NFT carries the hash of the metadata document. An NFT without hash doesn't match:
+    /// it was created without metadata (see [ImageNftMetadata]), so it can't be verified against it.
+    pub metadata_hash_matches: bool,
+    /// The metadata's properties link the DAO's app
+    pub dao_app_id_matches: bool,
+    /// `None` if the image wasn't passed
+    pub image_integrity_matches: Option<bool>,
+}
+
+impl ImageNftVerification {
+    pub fn is_valid(&self) -> bool {
+        self.image_url_matches
+            && self.url_is_arc3
+            && self.metadata_hash_matches
+            && self.dao_app_id_matches
+            && self.image_integrity_matches != Some(false)
+    }
+}
+
+/// Checks the DAO's image NFT against the metadata document (fetched from the NFT's url by the caller)
+/// and optionally the image the metadata points to.
+pub async fn verify_dao_image_nft(
+    algod: &dyn DaoAlgod,
+    dao: &Dao,
+    metadata_json: &[u8],
+    image: Option<&[u8]>,
+) -> Result<ImageNftVerification> {
+    let nft = dao
+        .image_nft
+        .as_ref()
+        .ok_or_else(|| anyhow!("Dao: {:?} has no image nft", dao.app_id))?;
+    let asset = algod.asset_information(nft.asset_id).await?;
+
+    verify_image_nft(
+        dao.app_id,
+        &nft.url,
+        asset.params.url.as_deref(),
+        asset.params.metadata_hash.as_deref(),
+        metadata_json,
+        image,
+    )
+}
+
+fn verify_image_nft(
+    app_id: DaoAppId,
+    dao_image_url: &str,
+    asset_url: Option<&str>,
+    asset_metadata_hash: Option<&[u8]>,
+    metadata_json: &[u8],
+    image: Option<&[u8]>,
+) -> Result<ImageNftVerification> {
+    let metadata = Arc3Metadata::from_json(metadata_json)?;
+
+    Ok(ImageNftVerification {
+        image_url_matches: metadata.image == dao_image_url,
+        url_is_arc3: asset_url
+            .map(|url| url.ends_with(ARC3_URL_SUFFIX))
+            .unwrap_or(false),
+        metadata_hash_matches: asset_metadata_hash
+            .map(|hash| hash == metadata_hash(metadata_json).as_slice())
+            .unwrap_or(false),
+        dao_app_id_matches: metadata.properties.dao_app_id == app_id.0,
+        image_integrity_matches: image
+            .map(|image| metadata.image_integrity == image_integrity(image)),
+    })
+}
@@ -0,0 +1,270 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dao_algod::fake::FakeAlgod,
+        flows::create_dao::setup::setup_app::{setup_app_args, DaoInitData},
+        image_metadata::{
+            arc3_url, dao_image_metadata, image_integrity, metadata_hash, verify_dao_image_nft,
+            verify_image_nft, Arc3Metadata, ImageNftMetadata,
+        },
+        testing::{
+            fixtures::{dao_fixture, FIXTURE_APP_ID},
+            flow::create_dao_flow::create_dao_flow,
+            network_test_util::test_dao_init,
+        },
+    };
+    use anyhow::Result;
+    use data_encoding::BASE64;
+    use mbase::{
+        api::version::Version,
+        models::{dao_app_id::DaoAppId, nft::Nft, timestamp::Timestamp},
+    };
+    use network_test_util::test_data::investor1;
+    use serial_test::serial;
+    use std::convert::TryInto;
+    use tokio::test;
+
+    const IMAGE: &[u8] = b"image bytes";
+    const IMAGE_URL: &str = "ipfs://bafkreiimage";
+    const METADATA_URL: &str =
+        "ipfs://bafkreidfpvmwiu3gmc3lfhkw6g2aou2rlqcp4srbl4l2v4vdcv4czflr5i#arc3";
+
+    fn metadata(app_id: DaoAppId) -> Arc3Metadata {
+        dao_image_metadata(
+            "Pancakes ltd",
+            Some("We sell pancakes".to_owned()),
+            IMAGE_URL,
+            IMAGE,
+            Some("image/png".to_owned()),
+            app_id,
+        )
+    }
+
+    #[test]
+    async fn test_metadata_json() -> Result<()> {
+        let json: serde_json::Value = serde_json::from_slice(&metadata(DaoAppId(123)).to_json()?)?;
+
+        assert_eq!("Pancakes ltd", json["name"]);
+        assert_eq!("We sell pancakes", json["description"]);
+        assert_eq!(IMAGE_URL, json["image"]);
+        assert_eq!("image/png", json["image_mimetype"]);
+        assert_eq!(123, json["properties"]["dao_app_id"]);
+        assert!(json["image_integrity"]
+            .as_str()
+            .unwrap()
+            .starts_with("sha256-"));
+        Ok(())
+    }
+
+    #[test]
+    async fn test_metadata_hash_and_integrity() -> Result<()> {
+        let json = metadata(DaoAppId(123)).to_json()?;
+
+        assert_eq!(metadata_hash(&json), metadata_hash(&json));
+        assert_ne!(
+            metadata_hash(&json),
+            metadata_hash(&metadata(DaoAppId(124)).to_json()?)
+        );
+        // echo -n "image bytes" | openssl dgst -sha256 -binary | base64
+        assert_eq!(
+            "sha256-3nAwI0STqL6oRNvh2GduaKLBpLAUxyHwQloitt9m+uw=",
+            image_integrity(IMAGE)
+        );
+        Ok(())
+    }
+
+    #[test]
+    async fn test_arc3_url() -> Result<()> {
+        assert_eq!("ipfs://cid#arc3", arc3_url("ipfs://cid"));
+        assert_eq!("ipfs://cid#arc3", arc3_url("ipfs://cid#arc3"));
+        Ok(())
+    }
+
+    #[test]
+    async fn test_verify_valid_image_nft() -> Result<()> {
+        let json = metadata(DaoAppId(123)).to_json()?;
+
+        let verification = verify_image_nft(
+            DaoAppId(123),
+            IMAGE_URL,
+            Some(METADATA_URL),
+            Some(&metadata_hash(&json)),
+            &json,
+            Some(IMAGE),
+        )?;
+
+        assert!(verification.is_valid());
+        assert!(verification.metadata_hash_matches);
+        assert_eq!(Some(true), verification.image_integrity_matches);
+        Ok(())
+    }
+
+    #[test]
+    async fn test_verify_image_nft_of_other_dao() -> Result<()> {
+        let json = metadata(DaoAppId(999)).to_json()?;
+
+        let verification = verify_image_nft(
+            DaoAppId(123),
+            IMAGE_URL,
+            Some(METADATA_URL),
+            Some(&metadata_hash(&json)),
+            &json,
+            Some(b"other image"),
+        )?;
+
+        assert!(!verification.is_valid());
+        assert!(!verification.dao_app_id_matches);
+        assert_eq!(Some(false), verification.image_integrity_matches);
+        // the hash matches: the metadata is the published one, but for another dao
+        assert!(verification.metadata_hash_matches);
+        Ok(())
+    }
+
+    #[test]
+    async fn test_verify_image_nft_with_other_metadata_hash() -> Result<()> {
+        let json = metadata(DaoAppId(123)).to_json()?;
+
+        let verification = verify_image_nft(
+            DaoAppId(123),
+            IMAGE_URL,
+            Some(METADATA_URL),
+            Some(&[0; 32]),
+            &json,
+            None,
+        )?;
+
+        assert!(!verification.is_valid());
+        assert!(!verification.metadata_hash_matches);
+        assert_eq!(None, verification.image_integrity_matches);
+        Ok(())
+    }
+
+    #[test]
+    async fn test_verify_image_nft_without_metadata_hash() -> Result<()> {
+        let json = metadata(DaoAppId(123)).to_json()?;
+
+        let verification = verify_image_nft(
+            DaoAppId(123),
+            IMAGE_URL,
+            Some(METADATA_URL),
+            None,
+            &json,
+            Some(IMAGE),
+        )?;
+
+        // everything else matches, but the metadata can't be verified
+        assert!(!verification.metadata_hash_matches);
+        assert!(!verification.is_valid());
+        Ok(())
+    }
+
+    /// The url and metadata hash of the NFT the app creates with the setup's image nft argument
+    fn nft_created_by_app(arg: &[u8]) -> Result<(String, Option<Vec<u8>>)> {
+        let image_len = u16::from_be_bytes(arg[0..2].try_into()?) as usize;
+        let image_url = String::from_utf8(arg[2..2 + image_len].to_vec())?;
+        let metadata_start = 2 + image_len;
+        let metadata_len =
+            u16::from_be_bytes(arg[metadata_start..metadata_start + 2].try_into()?) as usize;
+        if metadata_len == 0 {
+            return Ok((image_url, None));
+        }
+        let hash_start = metadata_start + 2 + metadata_len;
+        Ok((
+            String::from_utf8(arg[metadata_start + 2..hash_start].to_vec())?,
+            Some(arg[hash_start..].to_vec()),
+        ))
+    }
+
+    fn init_data(image_nft_metadata: Option<ImageNftMetadata>) -> Result<DaoInitData> {
+        let dao = dao_fixture(investor1().address())?;
+        Ok(DaoInitData {
+            app_approval_version: Version(1),
+            app_clear_version: Version(1),
+            shares_asset_id: dao.shares_asset_id,
+            funds_asset_id: dao.funds_asset_id,
+            project_name: dao.name,
+            descr_url: dao.descr_url,
+            share_price: dao.share_price,
+            investors_share: dao.investors_share,
+            image_nft_url: Some(IMAGE_URL.to_owned()),
+            image_nft_metadata,
+            social_media_url: dao.social_media_url,
+            min_raise_target: dao.raise_min_target,
+            min_raise_target_end_date: dao.raise_end_date,
+            setup_date: Timestamp::now(),
+            prospectus: dao.prospectus,
+            min_invest_shares: dao.min_invest_amount,
+            max_invest_shares: dao.max_invest_amount,
+        })
+    }
+
+    #[test]
+    async fn test_create_and_verify_image_nft() -> Result<()> {
+        let nft_asset_id = 1000;
+        let mut dao = dao_fixture(investor1().address())?;
+        // published by the creator, after creating the app
+        let json = metadata(DaoAppId(FIXTURE_APP_ID)).to_json()?;
+        let metadata = ImageNftMetadata {
+            url: METADATA_URL.to_owned(),
+            hash: metadata_hash(&json),
+        };
+
+        let args = setup_app_args(&init_data(Some(metadata))?)?;
+        let (nft_url, nft_metadata_hash) = nft_created_by_app(args.last().unwrap())?;
+        let asset = serde_json::from_str(&format!(
+            r#"{{ "index": {nft_asset_id}, "params": {{ "creator": "{}", "decimals": 0, "total": 1, "url": "{nft_url}", "metadata-hash": "{}" }} }}"#,
+            dao.app_id.address(),
+            BASE64.encode(&nft_metadata_hash.unwrap_or_default()),
+        ))?;
+        let algod = FakeAlgod::new().with_asset(asset);
+        // the dao keeps the image url (loaded from the global state)
+        dao.image_nft = Some(Nft {
+            asset_id: nft_asset_id,
+            url: IMAGE_URL.to_owned(),
+        });
+
+        let verification = verify_dao_image_nft(&algod, &dao, &json, Some(IMAGE)).await?;
+
+        assert!(verification.is_valid());
+        Ok(())
+    }
+
+    #[test]
+    async fn test_image_nft_arg_without_metadata() -> Result<()> {
+        let args = setup_app_args(&init_data(None)?)?;
+
+        // the app creates the nft with the image url and without metadata hash
+        assert_eq!(
+            (IMAGE_URL.to_owned(), None),
+            nft_created_by_app(args.last().unwrap())?
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    async fn test_verify_dao_image_nft_created_without_metadata() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let dao = create_dao_flow(td).await?;
+        let json = dao_image_metadata(
+            &dao.name,
+            None,
+            &td.specs.image_url.clone().unwrap(),
+            IMAGE,
+            None,
+            dao.app_id,
+        )
+        .to_json()?;
+
+        let verification = verify_dao_image_nft(&td.algod, &dao, &json, Some(IMAGE)).await?;
+
+        assert!(verification.image_url_matches);
+        assert!(verification.dao_app_id_matches);
+        assert_eq!(Some(true), verification.image_integrity_matches);
+        // the nft's url is the image url and it has no metadata hash
+        assert!(!verification.url_is_arc3);
+        assert!(!verification.metadata_hash_matches);
+        assert!(!verification.is_valid());
+        Ok(())
+    }
+}
@@ -1,20 +1,34 @@
 pub mod algo_helpers;
 pub mod api;
 pub mod capi_deps;
+pub mod capi_error;
 mod common_txs;
+pub mod compile_cache;
+pub mod dao_algod;
+pub mod dao_indexer;
 mod debug_msg_pack_submit_par;
 pub mod dependencies;
 pub mod diagnostics;
-mod dryrun_util;
+pub mod dryrun;
+pub mod explain;
 pub mod flows;
 pub mod image;
+pub mod image_metadata;
+pub mod min_balance;
 pub mod mock_data;
 mod note;
 pub mod queries;
+pub mod readiness;
 pub mod reqwest_ext;
 pub mod roadmap;
+pub mod signer;
 pub mod state;
+pub mod submission;
 pub mod teal;
+pub mod teal_registry;
 mod testing;
 pub mod dev_settings;
 pub mod team;
+pub mod verify_contract;
+pub mod verify_signed;
+pub mod wallet;
@@ -0,0 +1,183 @@
+use super::{dao_app_creation_min_balance, RequiresAlgos, ASSET_MIN_BALANCE};
+use crate::{
+    dev_settings::DevSettingsToSign,
+    flows::{
+        claim::claim::ClaimToSign,
+        close_dao::close_dao::CloseDaoToSign,
+        create_dao::model::{CreateAppToSign, CreateAssetsToSign, SetupDaoToSign},
+        drain::drain::DrainToSign,
+        invest::model::InvestToSign,
+        lock::lock::LockToSign,
+        pay_dao::pay_dao::PayDaoToSign,
+        reclaim::reclaim::ReclaimToSign,
+        rekey::rekey::RekeyToSign,
+        transfer_ownership::transfer_ownership::TransferOwnershipToSign,
+        unlock::unlock::UnlockToSign,
+        update_app::{update::UpdateAppToSign, upgrade::UpgradeDaoAppToSign},
+        update_data::update_data::UpdateAppToSign as UpdateDataToSign,
+        withdraw::withdraw::WithdrawToSign,
+    },
+    roadmap::add_roadmap_item::AddRoadmapItemToSign,
+    team::SetTeamToSign,
+};
+use algonaut::{core::MicroAlgos, transaction::Transaction};
+
+impl RequiresAlgos for CreateAssetsToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.create_shares_tx, &self.create_app_tx]
+    }
+
+    fn min_balance_increase(&self) -> MicroAlgos {
+        // the creator holds the created shares
+        MicroAlgos(ASSET_MIN_BALANCE) + dao_app_creation_min_balance()
+    }
+}
+
+impl RequiresAlgos for CreateAppToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.create_app_tx]
+    }
+
+    fn min_balance_increase(&self) -> MicroAlgos {
+        // the creator already holds the shares
+        dao_app_creation_min_balance()
+    }
+}
+
+impl RequiresAlgos for SetupDaoToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        // the app funding is counted as sent algos
+        vec![
+            &self.fund_app_tx,
+            &self.setup_app_tx,
+            &self.transfer_shares_to_app_tx,
+        ]
+    }
+}
+
+impl RequiresAlgos for InvestToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![
+            &self.shares_asset_optin_tx,
+            &self.app_call,
+            &self.payment_tx,
+        ]
+    }
+
+    fn min_balance_increase(&self) -> MicroAlgos {
+        // the app opt-in is a separate tx, submitted before (see invest_or_locking_app_optin_tx)
+        MicroAlgos(ASSET_MIN_BALANCE)
+    }
+}
+
+impl RequiresAlgos for LockToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.central_app_call_setup_tx, &self.shares_xfer_tx]
+    }
+}
+
+impl RequiresAlgos for UnlockToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        // the opt-out decreases the min balance, but only after it's submitted
+        vec![&self.central_app_optout_tx]
+    }
+}
+
+impl RequiresAlgos for ClaimToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.app_call_tx]
+    }
+}
+
+impl RequiresAlgos for DrainToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.app_call_tx]
+    }
+}
+
+impl RequiresAlgos for WithdrawToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.withdraw_tx]
+    }
+}
+
+impl RequiresAlgos for ReclaimToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.app_call_tx, &self.shares_xfer_tx]
+    }
+}
+
+impl RequiresAlgos for RekeyToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.tx]
+    }
+}
+
+impl RequiresAlgos for PayDaoToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.tx]
+    }
+}
+
+impl RequiresAlgos for CloseDaoToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        // the owner receives the app's ALGO only after it's submitted
+        let mut txs = vec![];
+        if let Some(tx) = &self.drain_tx {
+            txs.push(tx);
+        }
+        if let Some(tx) = &self.withdraw_tx {
+            txs.push(tx);
+        }
+        txs.push(&self.delete_tx);
+        txs
+    }
+}
+
+impl RequiresAlgos for TransferOwnershipToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.app_call_tx, &self.notify_new_owner_tx]
+    }
+}
+
+impl RequiresAlgos for UpgradeDaoAppToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.update, &self.update_versions]
+    }
+}
+
+impl RequiresAlgos for UpdateAppToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.update]
+    }
+}
+
+impl RequiresAlgos for UpdateDataToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        // the payment for a new image nft is counted as sent algos: it increases the app's min balance, not the owner's
+        let mut txs = vec![];
+        if let Some(tx) = &self.increase_min_balance_tx {
+            txs.push(tx);
+        }
+        txs.push(&self.update);
+        txs
+    }
+}
+
+impl RequiresAlgos for SetTeamToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.app_call_tx]
+    }
+}
+
+impl RequiresAlgos for DevSettingsToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.app_call_tx]
+    }
+}
+
+impl RequiresAlgos for AddRoadmapItemToSign {
+    fn txs(&self) -> Vec<&Transaction> {
+        vec![&self.tx]
+    }
+}
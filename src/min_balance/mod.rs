@@ -0,0 +1,146 @@
+use crate::dao_algod::DaoAlgod;
+use algonaut::{
+    core::{Address, MicroAlgos},
+    model::algod::v2::Account,
+    transaction::{transaction::TransactionType, Transaction},
+};
+use anyhow::Result;
+use mbase::state::dao_app_state::{
+    GLOBAL_SCHEMA_NUM_BYTE_SLICES, GLOBAL_SCHEMA_NUM_INTS, LOCAL_SCHEMA_NUM_BYTE_SLICES,
+    LOCAL_SCHEMA_NUM_INTS,
+};
+
+mod flows;
+mod tests;
+
+// Protocol min balance requirements (microalgos), see https://developer.algorand.org/docs/get-details/parameter_tables/
+pub const ACCOUNT_MIN_BALANCE: u64 = 100_000;
+pub const ASSET_MIN_BALANCE: u64 = 100_000;
+/// Per app created (per program page) or opted in
+pub const APP_MIN_BALANCE: u64 = 100_000;
+pub const SCHEMA_INT_MIN_BALANCE: u64 = 28_500;
+pub const SCHEMA_BYTE_SLICE_MIN_BALANCE: u64 = 50_000;
+
+/// Extra program pages of the DAO app, see create_app_tx
+pub const DAO_APP_EXTRA_PAGES: u64 = 1;
+
+pub fn schema_min_balance(num_uint: u64, num_byte_slice: u64) -> MicroAlgos {
+    MicroAlgos(num_uint * SCHEMA_INT_MIN_BALANCE + num_byte_slice * SCHEMA_BYTE_SLICE_MIN_BALANCE)
+}
+
+/// Min balance increase of the creator, when creating the DAO app
+pub fn dao_app_creation_min_balance() -> MicroAlgos {
+    MicroAlgos(APP_MIN_BALANCE * (1 + DAO_APP_EXTRA_PAGES))
+        + schema_min_balance(GLOBAL_SCHEMA_NUM_INTS, GLOBAL_SCHEMA_NUM_BYTE_SLICES)
+}
+
+/// Min balance increase of an investor, when opting in to the DAO app
+pub fn dao_app_optin_min_balance() -> MicroAlgos {
+    MicroAlgos(APP_MIN_BALANCE)
+        + schema_min_balance(LOCAL_SCHEMA_NUM_INTS, LOCAL_SCHEMA_NUM_BYTE_SLICES)
+}
+
+/// Min balance of the DAO app's account: the account and holding the shares, funds asset and (optional) image NFT
+pub fn dao_app_escrow_min_balance() -> MicroAlgos {
+    MicroAlgos(ACCOUNT_MIN_BALANCE + 3 * ASSET_MIN_BALANCE)
+}
+
+/// The account's current min balance
+pub fn account_min_balance(account: &Account) -> MicroAlgos {
+    let assets = ASSET_MIN_BALANCE * account.assets.len() as u64;
+
+    let created_apps: u64 = account
+        .created_apps
+        .iter()
+        .map(|app| {
+            let schema = app
+                .params
+                .global_state_schema
+                .as_ref()
+                .map(|s| schema_min_balance(s.num_uint, s.num_byte_slice))
+                .unwrap_or(MicroAlgos(0));
+            APP_MIN_BALANCE + schema.0
+        })
+        .sum();
+    // the extra pages requested when creating the apps, not derivable from the programs' length
+    let extra_pages = APP_MIN_BALANCE * account.apps_total_extra_pages.unwrap_or(0);
+
+    let opted_in_apps: u64 = account
+        .apps_local_state
+        .iter()
+        .map(|s| APP_MIN_BALANCE + schema_min_balance(s.schema.num_uint, s.schema.num_byte_slice).0)
+        .sum();
+
+    MicroAlgos(ACCOUNT_MIN_BALANCE + assets + created_apps + extra_pages + opted_in_apps)
+}
+
+/// Implemented by the flows' `*ToSign` structs, to know how much ALGO the user needs before signing.
+pub trait RequiresAlgos {
+    /// All the transactions of the flow (only the ones sent by the user are counted)
+    fn txs(&self) -> Vec<&Transaction>;
+
+    /// How much the transactions increase the sender's min balance, e.g. opting in to an asset
+    fn min_balance_increase(&self) -> MicroAlgos {
+        MicroAlgos(0)
+    }
+}
+
+/// ALGO needed by an account to submit a flow's transactions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlgosNeeded {
+    pub balance: MicroAlgos,
+    /// Min balance after submitting the transactions
+    pub min_balance: MicroAlgos,
+    pub fees: MicroAlgos,
+    /// ALGO payments sent by the account
+    pub sent: MicroAlgos,
+}
+
+impl AlgosNeeded {
+    /// The balance needed to submit the transactions
+    pub fn required(&self) -> MicroAlgos {
+        self.min_balance + self.fees + self.sent
+    }
+
+    pub fn is_enough(&self) -> bool {
+        self.balance.0 >= self.required().0
+    }
+
+    /// What has to be added to the account to submit the transactions
+    pub fn missing(&self) -> MicroAlgos {
+        MicroAlgos(self.required().0.saturating_sub(self.balance.0))
+    }
+}
+
+pub async fn algos_needed(
+    algod: &dyn DaoAlgod,
+    address: &Address,
+    to_sign: &dyn RequiresAlgos,
+) -> Result<AlgosNeeded> {
+    let account = algod.account_information(address).await?;
+    Ok(algos_needed_by_account(&account, to_sign))
+}
+
+pub fn algos_needed_by_account(account: &Account, to_sign: &dyn RequiresAlgos) -> AlgosNeeded {
+    let sent_txs: Vec<&Transaction> = to_sign
+        .txs()
+        .into_iter()
+        .filter(|tx| tx.sender() == account.address)
+        .collect();
+
+    let fees = sent_txs.iter().map(|tx| tx.fee.0).sum();
+    let sent = sent_txs
+        .iter()
+        .map(|tx| match &tx.txn_type {
+            TransactionType::Payment(p) => p.amount.0,
+            _ => 0,
+        })
+        .sum();
+
+    AlgosNeeded {
+        balance: account.amount,
+        min_balance: account_min_balance(account) + to_sign.min_balance_increase(),
+        fees: MicroAlgos(fees),
+        sent: MicroAlgos(sent),
+    }
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dao_algod::fake::FakeAlgod,
+        flows::lock::lock::lock,
+        min_balance::{account_min_balance, algos_needed_by_account, AlgosNeeded},
+    };
+    use algonaut::{core::MicroAlgos, model::algod::v2::Account};
+    use anyhow::Result;
+    use mbase::models::{dao_app_id::DaoAppId, share_amount::ShareAmount};
+    use network_test_util::test_data::investor1;
+    use tokio::test;
+
+    /// An account as returned by algod, holding an asset and opted in to an app with 2 ints and 1 byte slice
+    fn account(address: &str, amount: u64) -> Result<Account> {
+        Ok(serde_json::from_str(&format!(
+            r#"{{
+                "address": "{address}",
+                "amount": {amount},
+                "amount-without-pending-rewards": {amount},
+                "apps-local-state": [
+                    {{ "id": 123, "schema": {{ "num-byte-slice": 1, "num-uint": 2 }} }}
+                ],
+                "assets": [
+                    {{ "amount": 10, "asset-id": 456, "is-frozen": false }}
+                ],
+                "pending-rewards": 0,
+                "reward-base": 0,
+                "rewards": 0,
+                "round": 1000,
+                "status": "Offline"
+            }}"#
+        ))?)
+    }
+
+    #[test]
+    async fn test_account_min_balance() -> Result<()> {
+        let account = account(&investor1().address().to_string(), 1_000_000)?;
+
+        // account + asset + app opt-in + 2 ints + 1 byte slice
+        assert_eq!(
+            MicroAlgos(100_000 + 100_000 + 100_000 + 2 * 28_500 + 50_000),
+            account_min_balance(&account)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_app_creator_min_balance() -> Result<()> {
+        let address = investor1().address();
+        // a tiny program, created with an extra page
+        let account: Account = serde_json::from_str(&format!(
+            r#"{{
+                "address": "{address}",
+                "amount": 1000000,
+                "amount-without-pending-rewards": 1000000,
+                "apps-local-state": [],
+                "apps-total-extra-pages": 1,
+                "assets": [],
+                "created-apps": [
+                    {{ "id": 123, "params": {{ "creator": "{address}", "approval-program": "BoEB", "clear-state-program": "BoEB", "global-state-schema": {{ "num-byte-slice": 1, "num-uint": 2 }}, "local-state-schema": {{ "num-byte-slice": 0, "num-uint": 0 }} }} }}
+                ],
+                "pending-rewards": 0,
+                "reward-base": 0,
+                "rewards": 0,
+                "round": 1000,
+                "status": "Offline"
+            }}"#
+        ))?;
+
+        // account + app + extra page + 2 ints + 1 byte slice
+        assert_eq!(
+            MicroAlgos(100_000 + 100_000 + 100_000 + 2 * 28_500 + 50_000),
+            account_min_balance(&account)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_algos_needed_to_lock() -> Result<()> {
+        let algod = FakeAlgod::new();
+        let investor = investor1();
+        let account = account(&investor.address().to_string(), 1_000_000)?;
+
+        let to_sign = lock(
+            &algod,
+            investor.address(),
+            ShareAmount::new(10),
+            456,
+            DaoAppId(123),
+        )
+        .await?;
+
+        let needed = algos_needed_by_account(&account, &to_sign);
+
+        assert_eq!(account_min_balance(&account), needed.min_balance);
+        // app call + shares xfer
+        assert_eq!(algod.params().min_fee * 2, needed.fees);
+        assert_eq!(MicroAlgos(0), needed.sent);
+        assert!(needed.is_enough());
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_missing_algos() {
+        let needed = AlgosNeeded {
+            balance: MicroAlgos(200_000),
+            min_balance: MicroAlgos(300_000),
+            fees: MicroAlgos(2_000),
+            sent: MicroAlgos(0),
+        };
+
+        assert!(!needed.is_enough());
+        assert_eq!(MicroAlgos(102_000), needed.missing());
+    }
+}
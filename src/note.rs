@@ -1,3 +1,5 @@
+use crate::flows::create_dao::validate_specs::MAX_NOTE_LEN;
+use anyhow::{anyhow, Result};
 use data_encoding::BASE64;
 
 /// a general (capi) note prefix
@@ -23,3 +25,14 @@ pub fn dao_setup_prefix_base64() -> String {
     let prefix = dao_setup_prefix();
     BASE64.encode(&prefix)
 }
+
+/// Fails if the note is longer than a transaction allows. `content` describes the note, for the error.
+pub fn ensure_note_fits(note: &[u8], content: &str) -> Result<()> {
+    if note.len() > MAX_NOTE_LEN {
+        return Err(anyhow!(
+            "The {content}: {} bytes doesn't fit in the note: max {MAX_NOTE_LEN} bytes",
+            note.len()
+        ));
+    }
+    Ok(())
+}
@@ -1,6 +1,10 @@
 use super::received_payments::received_payments;
-use crate::flows::withdraw::withdrawals::withdrawals;
-use algonaut::{algod::v2::Algod, core::Address, indexer::v2::Indexer};
+use crate::{
+    dao_algod::DaoAlgod,
+    dao_indexer::DaoIndexer,
+    flows::{create_dao::storage::load_dao::load_dao, withdraw::withdrawals::owner_withdrawals},
+};
+use algonaut::core::Address;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use mbase::models::{
@@ -27,13 +31,25 @@ pub enum FundsActivityEntryType {
 
 #[allow(clippy::too_many_arguments)]
 pub async fn funds_activity(
-    algod: &Algod,
-    indexer: &Indexer,
+    algod: &dyn DaoAlgod,
+    indexer: &dyn DaoIndexer,
+    dao_id: DaoId,
+    capi_deps: &CapiAssetDaoDeps,
+    funds_asset: FundsAssetId,
+) -> Result<Vec<FundsActivityEntry>> {
+    let dao = load_dao(algod, dao_id).await?;
+    owner_funds_activity(indexer, dao_id, &dao.owner, capi_deps, funds_asset).await
+}
+
+/// Like [funds_activity], for callers that already know the current owner (e.g. having loaded the dao): doesn't need the algod.
+pub async fn owner_funds_activity(
+    indexer: &dyn DaoIndexer,
     dao_id: DaoId,
+    owner: &Address,
     capi_deps: &CapiAssetDaoDeps,
     funds_asset: FundsAssetId,
 ) -> Result<Vec<FundsActivityEntry>> {
-    let withdrawals = withdrawals(algod, indexer, dao_id, funds_asset, &None, &None).await?;
+    let withdrawals = owner_withdrawals(indexer, dao_id, owner, funds_asset, &None, &None).await?;
     // payments to the app escrow
     let payments = received_payments(
         indexer,
@@ -1,7 +1,7 @@
 use crate::{
-    flows::withdraw::withdrawals::withdrawals, queries::received_payments::received_payments,
+    dao_algod::DaoAlgod, dao_indexer::DaoIndexer, flows::withdraw::withdrawals::withdrawals,
+    queries::received_payments::received_payments,
 };
-use algonaut::{algod::v2::Algod, indexer::v2::Indexer};
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
 use mbase::models::{
@@ -17,8 +17,8 @@ use mbase::models::{
 /// If date is before the dao was created / had balance, the returned balance will be 0
 #[allow(clippy::too_many_arguments)]
 pub async fn historic_dao_funds_balance(
-    algod: &Algod,
-    indexer: &Indexer,
+    algod: &dyn DaoAlgod,
+    indexer: &dyn DaoIndexer,
     funds_asset: FundsAssetId,
     dao_id: DaoId,
     capi_deps: &CapiAssetDaoDeps,
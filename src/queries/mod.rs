@@ -4,3 +4,4 @@ pub mod historic_balance;
 pub mod my_daos;
 pub mod received_payments;
 pub mod shares_distribution;
+mod tests;
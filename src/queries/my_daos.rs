@@ -1,11 +1,15 @@
 use crate::{
-    flows::create_dao::{model::Dao, storage::load_dao::load_dao},
+    dao_algod::DaoAlgod,
+    dao_indexer::DaoIndexer,
+    flows::{
+        close_dao::close_dao::to_closed_dao,
+        create_dao::{model::Dao, storage::load_dao::load_dao},
+        transfer_ownership::history::transferred_dao_id,
+    },
     note::dao_setup_prefix_base64,
 };
 use algonaut::{
-    algod::v2::Algod,
     core::{Address, MicroAlgos},
-    indexer::v2::Indexer,
     model::indexer::v2::{OnCompletion, QueryTransaction},
 };
 use anyhow::{anyhow, Error, Result};
@@ -19,15 +23,15 @@ use std::collections::HashMap;
 #[derive(Debug, Clone)]
 pub struct MyStoredDao {
     pub dao: Dao,
-    // whether I created this dao
+    // whether I own this dao (created it or it was transferred to me)
     pub created_by_me: bool,
     // whether I'm currently invested (locking shares) in this dao
     pub invested_by_me: bool,
 }
 
 pub async fn my_daos(
-    algod: &Algod,
-    indexer: &Indexer,
+    algod: &dyn DaoAlgod,
+    indexer: &dyn DaoIndexer,
     address: &Address,
 ) -> Result<Vec<MyStoredDao>> {
     let created = my_created_daos(algod, indexer, address).await?;
@@ -66,7 +70,7 @@ pub async fn my_daos(
 /// Returns daos where the user is invested. Meaning: has currently locked shares (more exactly a local state containing the dao id).
 /// (Daos for non-locked shares, where the user opted out, or where the local state was deleted (externally) don't count).
 /// TODO can this be improved, now that we use URL->app id?
-pub async fn my_current_invested_daos(algod: &Algod, address: &Address) -> Result<Vec<Dao>> {
+pub async fn my_current_invested_daos(algod: &dyn DaoAlgod, address: &Address) -> Result<Vec<Dao>> {
     log::debug!("Retrieving my current invested daos from: {:?}", address);
 
     let account = algod.account_information(address).await?;
@@ -92,20 +96,20 @@ pub async fn my_current_invested_daos(algod: &Algod, address: &Address) -> Resul
     Ok(my_daos)
 }
 
-/// Returns daos created by user (this is technically defined as daos where user was the sender of the store dao tx)
-/// Note that this currently doesn't consider the case that the dao might be considered as "deleted"
-/// (this use case hasn't even been considered at all, also not in the UI/UX)
+/// Returns daos owned by user: the ones the user created (this is technically defined as daos where user was the sender of the store dao tx)
+/// and the ones transferred to the user (receiver of the ownership transfer payment), if the user is still the owner.
+/// Daos closed by the user are included too, with the data saved when closing (see [crate::flows::close_dao]).
 // TODO (low prio): review: if for some weird reason one user creates the asset and initializes the contracts and another stores the dao,
 // what are the consequences? any possible security or UX issues?
 // Consider all combinations, e.g. 3 differnet users to these actions respectively
 pub async fn my_created_daos(
-    algod: &Algod,
-    indexer: &Indexer,
+    algod: &dyn DaoAlgod,
+    indexer: &dyn DaoIndexer,
     address: &Address,
 ) -> Result<Vec<Dao>> {
     log::debug!("Retrieving my created daos from: {:?}", address);
 
-    let response = indexer
+    let txs = indexer
         .transactions(&QueryTransaction {
             address: Some(address.to_string()),
             // indexer disabled this, for performance apparently https://github.com/algorand/indexer/commit/1216e7957d5fba7c6a858e244a2aaf7e99412e5d
@@ -116,9 +120,31 @@ pub async fn my_created_daos(
         })
         .await?;
 
-    let mut my_daos = vec![];
+    let mut created_dao_ids = vec![];
+    let mut transferred_dao_ids = vec![];
+    let mut closed_daos: Vec<Dao> = vec![];
+
+    for tx in txs {
+        // the app doesn't exist anymore: the closure tx (sent by the last owner) has the dao's data
+        match to_closed_dao(&tx) {
+            Ok(Some(dao)) => {
+                if &dao.owner == address {
+                    closed_daos.push(dao);
+                }
+                continue;
+            }
+            Ok(None) => {}
+            Err(e) => log::debug!("Ignoring invalid dao closure: {e:?}, tx: {tx:?}"),
+        }
+
+        // the new owner is the receiver of the transfer payment
+        if let (Some(dao_id), Some(payment)) = (transferred_dao_id(&tx), &tx.payment_transaction) {
+            if payment.receiver == address.to_string() && !transferred_dao_ids.contains(&dao_id) {
+                transferred_dao_ids.push(dao_id);
+            }
+            continue;
+        }
 
-    for tx in response.transactions {
         let sender_address = tx.sender.parse::<Address>().map_err(Error::msg)?;
         if &sender_address == address {
             if let Some(app_tx) = &tx.application_transaction {
@@ -133,15 +159,42 @@ pub async fn my_created_daos(
                             ));
                         }
                         let dao_id = DaoId(DaoAppId(app_id));
-
-                        let dao = load_dao(algod, dao_id).await?;
-                        my_daos.push(dao);
+                        created_dao_ids.push(dao_id);
                     }
                 }
             }
         }
     }
 
+    let is_closed = |dao_id: DaoId| closed_daos.iter().any(|d| d.id() == dao_id);
+
+    let mut my_daos = vec![];
+    for dao_id in created_dao_ids {
+        if is_closed(dao_id) {
+            continue;
+        }
+        let dao = load_dao(algod, dao_id).await?;
+        // not transferred to someone else
+        if &dao.owner == address {
+            my_daos.push(dao);
+        }
+    }
+    for dao_id in transferred_dao_ids {
+        if is_closed(dao_id) {
+            continue;
+        }
+        // anyone can send a payment with a transfer note: only the dao's state is reliable
+        match load_dao(algod, dao_id).await {
+            Ok(dao) => {
+                if &dao.owner == address && !my_daos.iter().any(|d| d.id() == dao_id) {
+                    my_daos.push(dao);
+                }
+            }
+            Err(e) => log::debug!("Ignoring transfer of not loadable dao: {dao_id:?}: {e:?}"),
+        }
+    }
+    my_daos.extend(closed_daos);
+
     Ok(my_daos)
 }
 
@@ -1,5 +1,7 @@
-use crate::flows::drain::drain::calculate_dao_and_capi_escrow_xfer_amounts;
-use algonaut::{core::Address, indexer::v2::Indexer, model::indexer::v2::QueryTransaction};
+use crate::{
+    dao_indexer::DaoIndexer, flows::drain::drain::calculate_dao_and_capi_escrow_xfer_amounts,
+};
+use algonaut::{core::Address, model::indexer::v2::QueryTransaction};
 use anyhow::{anyhow, Error, Result};
 use chrono::{DateTime, Utc};
 use data_encoding::BASE64;
@@ -11,7 +13,7 @@ use mbase::{
 
 /// Payments (funds xfer) to the Dao escrow
 pub async fn received_payments(
-    indexer: &Indexer,
+    indexer: &dyn DaoIndexer,
     address: &Address,
     funds_asset: FundsAssetId,
     before_time: &Option<DateTime<Utc>>,
@@ -23,7 +25,7 @@ pub async fn received_payments(
     // let before_time_formatted = before_time.map(|t| t.to_rfc3339());
     // let after_time_formatted = after_time.map(|t| t.to_rfc3339());
 
-    let response = indexer
+    let txs = indexer
         .transactions(&QueryTransaction {
             address: Some(address.to_string()),
             // added to disabled_parameters..
@@ -38,7 +40,7 @@ pub async fn received_payments(
         .await?;
 
     let mut payments = vec![];
-    for tx in &response.transactions {
+    for tx in &txs {
         let sender_address = tx.sender.parse::<Address>().map_err(Error::msg)?;
 
         if let Some(xfer_tx) = &tx.asset_transfer_transaction {
@@ -1,16 +1,16 @@
 use std::collections::HashMap;
 
+use crate::{
+    dao_algod::DaoAlgod, dao_indexer::DaoIndexer, state::dao_app_state::dao_investor_state,
+};
 use algonaut::{
-    algod::v2::Algod,
     core::{to_app_address, Address},
-    indexer::v2::Indexer,
     model::indexer::v2::{Account, AssetHolding, QueryAccount},
 };
 use anyhow::{anyhow, Result};
 use mbase::{
     checked::CheckedAdd,
     models::{asset_amount::AssetAmount, dao_app_id::DaoAppId, share_amount::ShareAmount},
-    state::{app_state::ApplicationLocalStateError, dao_app_state::dao_investor_state},
 };
 use rust_decimal::Decimal;
 
@@ -18,8 +18,8 @@ use rust_decimal::Decimal;
 /// See [share_sholders] doc for more details.
 /// This function just "decorates" [share_sholders] with the percentage calculation.
 pub async fn shares_holders_distribution(
-    algod: &Algod,
-    indexer: &Indexer,
+    algod: &dyn DaoAlgod,
+    indexer: &dyn DaoIndexer,
     asset_id: u64,
     app_id: DaoAppId,
     asset_supply: u64,
@@ -56,8 +56,8 @@ pub async fn shares_holders_distribution(
 }
 
 async fn share_sholders(
-    algod: &Algod,
-    indexer: &Indexer,
+    algod: &dyn DaoAlgod,
+    indexer: &dyn DaoIndexer,
     asset_id: u64,
     app_id: DaoAppId,
 ) -> Result<Vec<ShareHolding>> {
@@ -97,24 +97,22 @@ fn merge(
 
 // TODO paginate? but clarify first whether we'll actually use this, it's quite expensive either way
 // we've to fetch the local state for each account to get the share count
-async fn opted_in_to_app(indexer: &Indexer, app_id: DaoAppId) -> Result<Vec<Account>> {
+async fn opted_in_to_app(indexer: &dyn DaoIndexer, app_id: DaoAppId) -> Result<Vec<Account>> {
     // get all the accounts opted in to the app (lockers/investors)
-    let accounts = indexer
+    indexer
         .accounts(&QueryAccount {
             application_id: Some(app_id.0),
             ..QueryAccount::default()
         })
-        .await?;
-
-    Ok(accounts.accounts)
+        .await
 }
 
 // TODO when fetching shares distr sometimes,
 // Msg("Unexpected investor local state length: 0, state: ApplicationLocalState { id: 75, key_value: [], schema: ApplicationStateSchema { num_byte_slice: 0, num_uint: 4 } }")
 // only reason for no local state should be not opted in, but here we're fetching only opted in accounts - what's going on? also don't remember having opted out the account
 async fn lockers_holdings(
-    algod: &Algod,
-    indexer: &Indexer,
+    algod: &dyn DaoAlgod,
+    indexer: &dyn DaoIndexer,
     app_id: DaoAppId,
 ) -> Result<Vec<ShareHolding>> {
     let opted_in_accounts = opted_in_to_app(indexer, app_id).await?;
@@ -122,20 +120,14 @@ async fn lockers_holdings(
     for opted_in_account in opted_in_accounts {
         // TODO (low prio) small optimization: read only the shares amount
         // TODO consider using join to parallelize these requests
-        let state_res = dao_investor_state(algod, &opted_in_account.address, app_id).await;
-        let amount = match state_res {
-            Ok(state) => {
+        let state = dao_investor_state(algod, &opted_in_account.address, app_id).await?;
+        let amount = match state {
+            Some(state) => {
                 log::trace!("Share locker state: {:?}", state);
                 state.shares
             }
-            Err(e) => {
-                if e == ApplicationLocalStateError::NotOptedIn {
-                    // Not opted in -> has no locked shares for statistics
-                    ShareAmount::new(0)
-                } else {
-                    return Err(e.into());
-                }
-            }
+            // Not opted in -> has no locked shares for statistics
+            None => ShareAmount::new(0),
         };
 
         holdings.push(ShareHolding {
@@ -149,7 +141,7 @@ async fn lockers_holdings(
 /// Returns a list all (unique) addresses that hold the asset, with their respective amounts.
 /// Note: amount > 0, i.e. excludes addresses that are opted in but don't hold the asset.
 async fn free_assets_holdings(
-    indexer: &Indexer,
+    indexer: &dyn DaoIndexer,
     asset_id: u64,
     app_escrow: &Address,
 ) -> Result<Vec<ShareHolding>> {
@@ -163,7 +155,7 @@ async fn free_assets_holdings(
     log::debug!("Got free shares holders: {:?}", accounts);
 
     let mut holdings = vec![];
-    for holder in accounts.accounts {
+    for holder in accounts {
         let asset_amount = find_amount(asset_id, &holder.assets)?;
 
         if asset_amount > 0 // if accounts have no assets but are opted in, we get 0 count - filter those out
@@ -180,7 +172,7 @@ async fn free_assets_holdings(
 
 // TODO how is this used? it seems awkward to count only free asset holders as general holders?
 pub async fn holders_count(
-    indexer: &Indexer,
+    indexer: &dyn DaoIndexer,
     asset_id: u64,
     app_escrow: &Address,
 ) -> Result<usize> {
@@ -0,0 +1,585 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dao_algod::fake::FakeAlgod,
+        dao_indexer::fake::FakeIndexer,
+        flows::{
+            close_dao::note::closure_to_note,
+            create_dao::{model::DaoStatus, shares_config::shares_to_base_units},
+            withdraw::{
+                note::withdrawal_to_note, withdraw::WithdrawalInputs,
+                withdrawals::owner_withdrawals,
+            },
+        },
+        note::dao_setup_prefix,
+        queries::{
+            funds_activity::{owner_funds_activity, FundsActivityEntryType},
+            my_daos::my_created_daos,
+            received_payments::received_payments,
+            shares_distribution::shares_holders_distribution,
+        },
+        testing::fixtures::{
+            algod_account, dao_fixture, FIXTURE_APP_ID, FIXTURE_FUNDS_ASSET_ID,
+            FIXTURE_SHARES_ASSET_ID,
+        },
+    };
+    use algonaut::core::Address;
+    use anyhow::Result;
+    use chrono::{TimeZone, Utc};
+    use data_encoding::BASE64;
+    use mbase::models::{
+        capi_deps::{CapiAddress, CapiAssetDaoDeps},
+        dao_app_id::DaoAppId,
+        dao_id::DaoId,
+        funds::{FundsAmount, FundsAssetId},
+        share_amount::ShareAmount,
+    };
+    use network_test_util::test_data::{creator, investor1, investor2};
+    use rust_decimal::Decimal;
+    use std::{convert::TryInto, str::FromStr};
+    use tokio::test;
+
+    const DAO_ADDRESS: &str = "NT4TNO4NXGI46MBS6T5HDI25XKO5GESRSMRAATNTAYE6YKUE7N34TONJBA";
+    const INVESTOR_ADDRESS: &str = "FTPBN666KYZVB5YYYLRZ6GXWBKWLJJSXQ3N753USSWS2WIAYK7WJTTYRPI";
+    const CUSTOMER_ADDRESS: &str = "Y6O4BH3SUBLIHDU33XTLCGTS7SFBRZWFVBUA5IOKCOO2Z4SBVY6XXT6ZUQ";
+    const CAPI_ADDRESS: &str = "2QFRITR4DMZCISHVXLQROMMSY6AE4L3GUEWHURMU7PX4BUGK5JMXBO4RP4";
+    const FUNDS_ASSET_ID: u64 = 123;
+
+    // indexer response format (`transactions` of /v2/transactions?address=<DAO_ADDRESS>):
+    // an investment, a customer payment and the app's own opt-in to the funds asset
+    const DAO_TXS_JSON: &str = r#"[
+        {
+            "asset-transfer-transaction": {
+                "amount": 10000,
+                "asset-id": 123,
+                "close-amount": 0,
+                "receiver": "NT4TNO4NXGI46MBS6T5HDI25XKO5GESRSMRAATNTAYE6YKUE7N34TONJBA"
+            },
+            "close-rewards": 0,
+            "closing-amount": 0,
+            "confirmed-round": 10,
+            "fee": 1000,
+            "first-valid": 8,
+            "genesis-hash": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+            "genesis-id": "sandnet-v1",
+            "id": "ZKLYCEWKDO64V6WCGGZZUI64JWTYN37YCR6E44VZQB3YLL7OJC5Q",
+            "intra-round-offset": 0,
+            "last-valid": 1008,
+            "note": "SW52ZXN0",
+            "receiver-rewards": 0,
+            "round-time": 1650000000,
+            "sender": "FTPBN666KYZVB5YYYLRZ6GXWBKWLJJSXQ3N753USSWS2WIAYK7WJTTYRPI",
+            "sender-rewards": 0,
+            "signature": {},
+            "tx-type": "axfer"
+        },
+        {
+            "asset-transfer-transaction": {
+                "amount": 1000,
+                "asset-id": 123,
+                "close-amount": 0,
+                "receiver": "NT4TNO4NXGI46MBS6T5HDI25XKO5GESRSMRAATNTAYE6YKUE7N34TONJBA"
+            },
+            "close-rewards": 0,
+            "closing-amount": 0,
+            "confirmed-round": 12,
+            "fee": 1000,
+            "first-valid": 11,
+            "genesis-hash": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+            "genesis-id": "sandnet-v1",
+            "id": "HYR6QFQAHFMUUM4JJ5SWJYNRGSF326QARDKCYSWLOPXK5VM4ACOQ",
+            "intra-round-offset": 0,
+            "last-valid": 1011,
+            "note": "VGhhbmtzIGZvciB0aGUgY29mZmVl",
+            "receiver-rewards": 0,
+            "round-time": 1650000100,
+            "sender": "Y6O4BH3SUBLIHDU33XTLCGTS7SFBRZWFVBUA5IOKCOO2Z4SBVY6XXT6ZUQ",
+            "sender-rewards": 0,
+            "signature": {},
+            "tx-type": "axfer"
+        },
+        {
+            "asset-transfer-transaction": {
+                "amount": 0,
+                "asset-id": 123,
+                "close-amount": 0,
+                "receiver": "NT4TNO4NXGI46MBS6T5HDI25XKO5GESRSMRAATNTAYE6YKUE7N34TONJBA"
+            },
+            "close-rewards": 0,
+            "closing-amount": 0,
+            "confirmed-round": 5,
+            "fee": 1000,
+            "first-valid": 4,
+            "genesis-hash": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+            "genesis-id": "sandnet-v1",
+            "id": "FZ6SYA5JKB5OEZPM6W2TK2EFUUZZHIQCTUSBHFEZOJS2DIS257DA",
+            "intra-round-offset": 1,
+            "last-valid": 1004,
+            "receiver-rewards": 0,
+            "round-time": 1649999000,
+            "sender": "NT4TNO4NXGI46MBS6T5HDI25XKO5GESRSMRAATNTAYE6YKUE7N34TONJBA",
+            "sender-rewards": 0,
+            "signature": {},
+            "tx-type": "axfer"
+        }
+    ]"#;
+
+    fn capi_deps() -> Result<CapiAssetDaoDeps> {
+        Ok(CapiAssetDaoDeps {
+            escrow_percentage: Decimal::from_str("0.1")?.try_into()?,
+            address: CapiAddress(CAPI_ADDRESS.parse().map_err(anyhow::Error::msg)?),
+        })
+    }
+
+    #[test]
+    async fn test_received_payments_from_indexer_fixture() -> Result<()> {
+        let indexer = FakeIndexer::new().with_transactions_json(DAO_TXS_JSON)?;
+        let dao_address: Address = DAO_ADDRESS.parse().map_err(anyhow::Error::msg)?;
+
+        let payments = received_payments(
+            &indexer,
+            &dao_address,
+            FundsAssetId(FUNDS_ASSET_ID),
+            &None,
+            &None,
+            &capi_deps()?,
+        )
+        .await?;
+
+        // the opt-in isn't a payment
+        assert_eq!(2, payments.len());
+
+        let investment = &payments[0];
+        assert_eq!(INVESTOR_ADDRESS, investment.sender.to_string());
+        assert_eq!(FundsAmount::new(10_000), investment.amount);
+        // investments don't pay the capi fee
+        assert_eq!(FundsAmount::new(0), investment.fee);
+        assert_eq!(Some("Invest".to_owned()), investment.note);
+        assert_eq!(Utc.timestamp(1650000000, 0), investment.date);
+
+        let customer_payment = &payments[1];
+        assert_eq!(CUSTOMER_ADDRESS, customer_payment.sender.to_string());
+        assert_eq!(FundsAmount::new(1_000), customer_payment.amount);
+        assert_eq!(FundsAmount::new(100), customer_payment.fee);
+        assert_eq!(FundsAmount::new(900), customer_payment.received_amount()?);
+        assert_eq!(
+            Some("Thanks for the coffee".to_owned()),
+            customer_payment.note
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_received_payments_filters_by_date() -> Result<()> {
+        let indexer = FakeIndexer::new().with_transactions_json(DAO_TXS_JSON)?;
+        let dao_address: Address = DAO_ADDRESS.parse().map_err(anyhow::Error::msg)?;
+
+        let payments = received_payments(
+            &indexer,
+            &dao_address,
+            FundsAssetId(FUNDS_ASSET_ID),
+            &None,
+            &Some(Utc.timestamp(1650000050, 0)),
+            &capi_deps()?,
+        )
+        .await?;
+
+        assert_eq!(1, payments.len());
+        assert_eq!(CUSTOMER_ADDRESS, payments[0].sender.to_string());
+
+        Ok(())
+    }
+
+    const TX_ID_1: &str = "ZKLYCEWKDO64V6WCGGZZUI64JWTYN37YCR6E44VZQB3YLL7OJC5Q";
+    const TX_ID_2: &str = "HYR6QFQAHFMUUM4JJ5SWJYNRGSF326QARDKCYSWLOPXK5VM4ACOQ";
+    const TX_ID_3: &str = "FZ6SYA5JKB5OEZPM6W2TK2EFUUZZHIQCTUSBHFEZOJS2DIS257DA";
+
+    fn fixture_dao_id() -> DaoId {
+        DaoId(DaoAppId(FIXTURE_APP_ID))
+    }
+
+    /// A funds xfer (indexer format). Inner txs have no id.
+    fn xfer_json(
+        id: Option<&str>,
+        sender: &Address,
+        receiver: &Address,
+        amount: u64,
+        round_time: u64,
+    ) -> String {
+        let id = id.map(|id| format!(r#""id": "{id}","#)).unwrap_or_default();
+        format!(
+            r#"{{
+            "asset-transfer-transaction": {{
+                "amount": {amount},
+                "asset-id": {FIXTURE_FUNDS_ASSET_ID},
+                "close-amount": 0,
+                "receiver": "{receiver}"
+            }},
+            "close-rewards": 0,
+            "closing-amount": 0,
+            "confirmed-round": 10,
+            "fee": 1000,
+            "first-valid": 1,
+            {id}
+            "intra-round-offset": 0,
+            "last-valid": 1001,
+            "receiver-rewards": 0,
+            "round-time": {round_time},
+            "sender": "{sender}",
+            "sender-rewards": 0,
+            "tx-type": "axfer"
+        }}"#
+        )
+    }
+
+    /// An app call (indexer format)
+    fn app_call_json(
+        id: &str,
+        sender: &Address,
+        app_id: u64,
+        on_completion: &str,
+        note: Option<&[u8]>,
+        inner_txns: &[String],
+        round_time: u64,
+    ) -> String {
+        let note = note
+            .map(|n| format!(r#""note": "{}","#, BASE64.encode(n)))
+            .unwrap_or_default();
+        let inner_txns = inner_txns.join(",");
+        format!(
+            r#"{{
+            "application-transaction": {{
+                "accounts": [],
+                "application-args": [],
+                "application-id": {app_id},
+                "foreign-apps": [],
+                "foreign-assets": [],
+                "global-state-schema": {{ "num-byte-slice": 0, "num-uint": 0 }},
+                "local-state-schema": {{ "num-byte-slice": 0, "num-uint": 0 }},
+                "on-completion": "{on_completion}"
+            }},
+            "close-rewards": 0,
+            "closing-amount": 0,
+            "confirmed-round": 10,
+            "fee": 2000,
+            "first-valid": 1,
+            "genesis-hash": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+            "genesis-id": "sandnet-v1",
+            "id": "{id}",
+            "inner-txns": [{inner_txns}],
+            "intra-round-offset": 0,
+            "last-valid": 1001,
+            {note}
+            "receiver-rewards": 0,
+            "round-time": {round_time},
+            "sender": "{sender}",
+            "sender-rewards": 0,
+            "signature": {{}},
+            "tx-type": "appl"
+        }}"#
+        )
+    }
+
+    /// The owner withdrawing `amount` with `description` from the fixture dao
+    fn withdrawal_json(
+        id: &str,
+        owner: &Address,
+        amount: u64,
+        description: &str,
+        round_time: u64,
+    ) -> Result<String> {
+        let note = withdrawal_to_note(&WithdrawalInputs {
+            amount: FundsAmount::new(amount),
+            description: description.to_owned(),
+        })?;
+        let app_address = DaoAppId(FIXTURE_APP_ID).address();
+        Ok(app_call_json(
+            id,
+            owner,
+            FIXTURE_APP_ID,
+            "noop",
+            Some(&note),
+            &[xfer_json(None, &app_address, owner, amount, round_time)],
+            round_time,
+        ))
+    }
+
+    /// An account as returned by the indexer. `assets`: (asset id, amount)
+    fn indexer_account_json(address: &Address, assets: &[(u64, u64)]) -> String {
+        let assets_json = assets
+            .iter()
+            .map(|(id, amount)| {
+                format!(r#"{{ "amount": {amount}, "asset-id": {id}, "is-frozen": false }}"#)
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        format!(
+            r#"{{
+            "address": "{address}",
+            "amount": 1000000,
+            "amount-without-pending-rewards": 1000000,
+            "assets": [{assets_json}],
+            "pending-rewards": 0,
+            "reward-base": 0,
+            "rewards": 0,
+            "round": 1000,
+            "status": "Offline"
+        }}"#
+        )
+    }
+
+    #[test]
+    async fn test_owner_withdrawals() -> Result<()> {
+        let owner = creator().address();
+        let app_address = DaoAppId(FIXTURE_APP_ID).address();
+        let txs = [
+            withdrawal_json(TX_ID_1, &owner, 1_000, "Office supplies", 1650000000)?,
+            // a call to another app, sending the same asset to the owner
+            app_call_json(
+                TX_ID_2,
+                &owner,
+                FIXTURE_APP_ID + 1,
+                "noop",
+                None,
+                &[xfer_json(None, &app_address, &owner, 500, 1650000100)],
+                1650000100,
+            ),
+        ];
+        let indexer = FakeIndexer::new().with_transactions_json(&format!("[{}]", txs.join(",")))?;
+
+        let withdrawals = owner_withdrawals(
+            &indexer,
+            fixture_dao_id(),
+            &owner,
+            FundsAssetId(FIXTURE_FUNDS_ASSET_ID),
+            &None,
+            &None,
+        )
+        .await?;
+
+        assert_eq!(1, withdrawals.len());
+        assert_eq!(FundsAmount::new(1_000), withdrawals[0].amount);
+        assert_eq!("Office supplies", withdrawals[0].description);
+        assert_eq!(owner, withdrawals[0].address);
+        assert_eq!(TX_ID_1, withdrawals[0].tx_id.to_string());
+        assert_eq!(Utc.timestamp(1650000000, 0), withdrawals[0].date);
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_owner_withdrawals_filters_by_date() -> Result<()> {
+        let owner = creator().address();
+        let txs = [
+            withdrawal_json(TX_ID_1, &owner, 1_000, "Office supplies", 1650000000)?,
+            withdrawal_json(TX_ID_2, &owner, 2_000, "Rent", 1650000100)?,
+        ];
+        let indexer = FakeIndexer::new().with_transactions_json(&format!("[{}]", txs.join(",")))?;
+
+        let withdrawals = owner_withdrawals(
+            &indexer,
+            fixture_dao_id(),
+            &owner,
+            FundsAssetId(FIXTURE_FUNDS_ASSET_ID),
+            &Some(Utc.timestamp(1650000050, 0)),
+            &None,
+        )
+        .await?;
+
+        assert_eq!(1, withdrawals.len());
+        assert_eq!("Office supplies", withdrawals[0].description);
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_funds_activity() -> Result<()> {
+        let owner = creator().address();
+        let customer = investor1().address();
+        let app_address = DaoAppId(FIXTURE_APP_ID).address();
+        let txs = [
+            withdrawal_json(TX_ID_1, &owner, 1_000, "Office supplies", 1650000100)?,
+            xfer_json(Some(TX_ID_2), &customer, &app_address, 5_000, 1650000000),
+        ];
+        let indexer = FakeIndexer::new().with_transactions_json(&format!("[{}]", txs.join(",")))?;
+
+        let activity = owner_funds_activity(
+            &indexer,
+            fixture_dao_id(),
+            &owner,
+            &capi_deps()?,
+            FundsAssetId(FIXTURE_FUNDS_ASSET_ID),
+        )
+        .await?;
+
+        // oldest first
+        assert_eq!(2, activity.len());
+        assert!(matches!(activity[0].type_, FundsActivityEntryType::Income));
+        assert_eq!(customer, activity[0].address);
+        assert_eq!(FundsAmount::new(5_000), activity[0].amount);
+        assert_eq!(FundsAmount::new(500), activity[0].fee);
+        assert!(matches!(
+            activity[1].type_,
+            FundsActivityEntryType::Spending
+        ));
+        assert_eq!(owner, activity[1].address);
+        assert_eq!(FundsAmount::new(1_000), activity[1].amount);
+        assert_eq!("Office supplies", activity[1].description);
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_my_created_daos_includes_closed_daos() -> Result<()> {
+        let owner = creator().address();
+        let dao = dao_fixture(owner)?;
+        let txs = [
+            // setup
+            app_call_json(
+                TX_ID_1,
+                &owner,
+                FIXTURE_APP_ID,
+                "noop",
+                Some(&dao_setup_prefix()),
+                &[],
+                1650000000,
+            ),
+            // closure
+            app_call_json(
+                TX_ID_2,
+                &owner,
+                FIXTURE_APP_ID,
+                "delete",
+                Some(&closure_to_note(&dao)?),
+                &[],
+                1650000100,
+            ),
+        ];
+        let indexer = FakeIndexer::new().with_transactions_json(&format!("[{}]", txs.join(",")))?;
+        // the app doesn't exist anymore
+        let algod = FakeAlgod::new();
+
+        let daos = my_created_daos(&algod, &indexer, &owner).await?;
+
+        assert_eq!(1, daos.len());
+        assert_eq!(dao.id(), daos[0].id());
+        assert_eq!(DaoStatus::Closed, daos[0].status);
+        assert_eq!(dao.name, daos[0].name);
+
+        // someone else
+        assert!(my_created_daos(&algod, &indexer, &investor1().address())
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_shares_holders_distribution() -> Result<()> {
+        let holder1 = investor1().address();
+        let holder2 = investor2().address();
+        let opted_in = creator().address();
+        let app_address = DaoAppId(FIXTURE_APP_ID).address();
+        let accounts = [
+            indexer_account_json(&holder1, &[(FIXTURE_SHARES_ASSET_ID, 100)]),
+            indexer_account_json(&holder2, &[(FIXTURE_SHARES_ASSET_ID, 200)]),
+            // opted in, without shares
+            indexer_account_json(&opted_in, &[(FIXTURE_SHARES_ASSET_ID, 0)]),
+            // the locked shares are in the app
+            indexer_account_json(&app_address, &[(FIXTURE_SHARES_ASSET_ID, 50)]),
+        ];
+        let indexer =
+            FakeIndexer::new().with_accounts_json(&format!("[{}]", accounts.join(",")))?;
+        // nobody locking (the app's local state is in the algod account)
+        let algod = FakeAlgod::new();
+
+        let distribution = shares_holders_distribution(
+            &algod,
+            &indexer,
+            FIXTURE_SHARES_ASSET_ID,
+            DaoAppId(FIXTURE_APP_ID),
+            300,
+        )
+        .await?;
+
+        // biggest holders first
+        assert_eq!(2, distribution.len());
+        assert_eq!(holder2, distribution[0].address);
+        assert_eq!(ShareAmount::new(200), distribution[0].amount);
+        assert_eq!(
+            Decimal::from(200) / Decimal::from(300),
+            distribution[0].percentage
+        );
+        assert_eq!(holder1, distribution[1].address);
+        assert_eq!(ShareAmount::new(100), distribution[1].amount);
+        assert_eq!(
+            Decimal::from(100) / Decimal::from(300),
+            distribution[1].percentage
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_shares_holders_distribution_with_shares_decimals() -> Result<()> {
+        let decimals = 2;
+        // 300 shares
+        let supply = shares_to_base_units(Decimal::from(300), decimals)?;
+        let holding = shares_to_base_units(Decimal::from_str("1.5")?, decimals)?;
+        let holder = investor1().address();
+        let accounts = [indexer_account_json(
+            &holder,
+            &[(FIXTURE_SHARES_ASSET_ID, holding.val())],
+        )];
+        let indexer =
+            FakeIndexer::new().with_accounts_json(&format!("[{}]", accounts.join(",")))?;
+        let algod = FakeAlgod::new();
+
+        let distribution = shares_holders_distribution(
+            &algod,
+            &indexer,
+            FIXTURE_SHARES_ASSET_ID,
+            DaoAppId(FIXTURE_APP_ID),
+            supply.val(),
+        )
+        .await?;
+
+        // amounts are in base units, the percentage is the same as with the amounts in shares
+        assert_eq!(1, distribution.len());
+        assert_eq!(ShareAmount::new(150), distribution[0].amount);
+        assert_eq!(
+            Decimal::from_str("1.5")? / Decimal::from(300),
+            distribution[0].percentage
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_shares_holders_distribution_counts_opted_out_lockers_without_shares() -> Result<()>
+    {
+        let locker = investor1().address();
+        let indexer = FakeIndexer::new().with_app_account(
+            FIXTURE_APP_ID,
+            serde_json::from_str(&indexer_account_json(&locker, &[]))?,
+        );
+        // the indexer is behind: the locker already unlocked and opted out of the app
+        let algod = FakeAlgod::new().with_account(algod_account(&locker, 1_000_000, &[], None)?);
+
+        let distribution = shares_holders_distribution(
+            &algod,
+            &indexer,
+            FIXTURE_SHARES_ASSET_ID,
+            DaoAppId(FIXTURE_APP_ID),
+            300,
+        )
+        .await?;
+
+        assert_eq!(1, distribution.len());
+        assert_eq!(locker, distribution[0].address);
+        assert_eq!(ShareAmount::new(0), distribution[0].amount);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{
+    dao_algod::DaoAlgod,
+    flows::{
+        claim::claim::claim,
+        create_dao::model::Dao,
+        invest::{app_optins::invest_or_locking_app_optin_tx, invest::invest_fee},
+        lock::lock::lock,
+    },
+    min_balance::{
+        account_min_balance, dao_app_optin_min_balance, AlgosNeeded, RequiresAlgos,
+        ASSET_MIN_BALANCE,
+    },
+};
+use algonaut::{
+    core::{Address, MicroAlgos},
+    transaction::{AcceptAsset, Transaction, TxnBuilder},
+};
+use anyhow::{anyhow, Result};
+use mbase::models::{funds::FundsAmount, share_amount::ShareAmount};
+
+mod tests;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaoAction {
+    Invest { share_amount: ShareAmount },
+    Lock { share_amount: ShareAmount },
+    Claim,
+}
+
+impl DaoAction {
+    /// Fees of the txs submitted by the flow (incl. the pooled fees of the inner txs), built with the flow
+    async fn fees(&self, algod: &dyn DaoAlgod, address: &Address, dao: &Dao) -> Result<MicroAlgos> {
+        let fees =
+            |to_sign: &dyn RequiresAlgos| MicroAlgos(to_sign.txs().iter().map(|tx| tx.fee.0).sum());
+        Ok(match *self {
+            DaoAction::Invest { .. } => invest_fee(algod, dao).await?,
+            DaoAction::Lock { share_amount } => fees(
+                &lock(
+                    algod,
+                    *address,
+                    share_amount,
+                    dao.shares_asset_id,
+                    dao.app_id,
+                )
+                .await?,
+            ),
+            DaoAction::Claim => fees(&claim(algod, address, dao.app_id, dao.funds_asset_id).await?),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Prerequisite {
+    AppOptIn,
+    FundsAssetOptIn,
+    /// ALGO for the fees and the min balance (incl. the increase caused by the action and the opt-ins)
+    Algos {
+        missing: MicroAlgos,
+    },
+    /// Funds asset to pay the investment
+    Funds {
+        missing: FundsAmount,
+    },
+    Shares {
+        missing: ShareAmount,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingPrerequisite {
+    pub prerequisite: Prerequisite,
+    /// Transaction that fulfills the prerequisite, to be signed and submitted before the action.
+    /// `None` if the user has to get the assets (or ALGO) elsewhere.
+    pub fix_tx: Option<Transaction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Readiness {
+    /// In the order they should be fixed
+    pub missing: Vec<MissingPrerequisite>,
+}
+
+impl Readiness {
+    pub fn is_ready(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// What's missing for `address` to execute `action` in the DAO
+pub async fn readiness(
+    algod: &dyn DaoAlgod,
+    address: &Address,
+    dao: &Dao,
+    action: DaoAction,
+) -> Result<Readiness> {
+    let params = algod.suggested_transaction_params().await?;
+    let account = algod.account_information(address).await?;
+
+    let mut missing = vec![];
+    let mut min_balance_increase = MicroAlgos(0);
+
+    let opted_in_to_app = account
+        .apps_local_state
+        .iter()
+        .any(|s| s.id == dao.app_id.0);
+    let holding = |asset_id: u64| account.assets.iter().find(|h| h.asset_id == asset_id);
+
+    // the app calls of all the actions read the investor's local state
+    if !opted_in_to_app {
+        min_balance_increase = min_balance_increase + dao_app_optin_min_balance();
+        missing.push(MissingPrerequisite {
+            prerequisite: Prerequisite::AppOptIn,
+            fix_tx: Some(invest_or_locking_app_optin_tx(algod, dao, address).await?),
+        });
+    }
+
+    let needs_funds_asset = match action {
+        // to pay and to receive the dividend respectively
+        DaoAction::Invest { .. } | DaoAction::Claim => true,
+        DaoAction::Lock { .. } => false,
+    };
+    let funds_holding = holding(dao.funds_asset_id.0);
+    if needs_funds_asset && funds_holding.is_none() {
+        min_balance_increase = min_balance_increase + MicroAlgos(ASSET_MIN_BALANCE);
+        missing.push(MissingPrerequisite {
+            prerequisite: Prerequisite::FundsAssetOptIn,
+            fix_tx: Some(
+                TxnBuilder::with(
+                    &params,
+                    AcceptAsset::new(*address, dao.funds_asset_id.0).build(),
+                )
+                .build()?,
+            ),
+        });
+    }
+
+    match action {
+        DaoAction::Invest { share_amount } => {
+            // the invest group opts in to the shares asset
+            if holding(dao.shares_asset_id).is_none() {
+                min_balance_increase = min_balance_increase + MicroAlgos(ASSET_MIN_BALANCE);
+            }
+            let price = dao
+                .share_price
+                .val()
+                .checked_mul(share_amount.val())
+                .ok_or_else(|| anyhow!("Price of {share_amount} shares caused an overflow"))?;
+            let funds = funds_holding.map(|h| h.amount).unwrap_or(0);
+            if funds < price {
+                missing.push(MissingPrerequisite {
+                    prerequisite: Prerequisite::Funds {
+                        missing: FundsAmount::new(price - funds),
+                    },
+                    fix_tx: None,
+                });
+            }
+        }
+        DaoAction::Lock { share_amount } => {
+            let shares = holding(dao.shares_asset_id).map(|h| h.amount).unwrap_or(0);
+            if shares < share_amount.val() {
+                missing.push(MissingPrerequisite {
+                    prerequisite: Prerequisite::Shares {
+                        missing: ShareAmount::new(share_amount.val() - shares),
+                    },
+                    fix_tx: None,
+                });
+            }
+        }
+        DaoAction::Claim => {}
+    }
+
+    let fix_txs_fees: u64 = missing
+        .iter()
+        .filter_map(|m| m.fix_tx.as_ref())
+        .map(|tx| tx.fee.0)
+        .sum();
+    let algos_needed = AlgosNeeded {
+        balance: account.amount,
+        min_balance: account_min_balance(&account) + min_balance_increase,
+        fees: action.fees(algod, address, dao).await? + MicroAlgos(fix_txs_fees),
+        sent: MicroAlgos(0),
+    };
+    if !algos_needed.is_enough() {
+        missing.push(MissingPrerequisite {
+            prerequisite: Prerequisite::Algos {
+                missing: algos_needed.missing(),
+            },
+            fix_tx: None,
+        });
+    }
+
+    Ok(Readiness { missing })
+}
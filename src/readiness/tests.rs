@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dao_algod::fake::FakeAlgod,
+        flows::invest::invest::invest_fee,
+        min_balance::dao_app_optin_min_balance,
+        readiness::{readiness, DaoAction, Prerequisite},
+        testing::fixtures::{
+            algod_account, dao_fixture, FIXTURE_APP_ID, FIXTURE_FUNDS_ASSET_ID,
+            FIXTURE_SHARES_ASSET_ID,
+        },
+    };
+    use algonaut::{core::MicroAlgos, transaction::transaction::TransactionType};
+    use anyhow::Result;
+    use mbase::models::{funds::FundsAmount, share_amount::ShareAmount};
+    use network_test_util::test_data::{investor1, investor2};
+    use tokio::test;
+
+    #[test]
+    async fn test_invest_without_opt_ins_and_funds() -> Result<()> {
+        let investor = investor1().address();
+        let algod = FakeAlgod::new().with_account(algod_account(&investor, 100_000, &[], None)?);
+        let dao = dao_fixture(investor2().address())?;
+
+        let readiness = readiness(
+            &algod,
+            &investor,
+            &dao,
+            DaoAction::Invest {
+                share_amount: ShareAmount::new(2),
+            },
+        )
+        .await?;
+
+        assert!(!readiness.is_ready());
+        let prerequisites: Vec<Prerequisite> = readiness
+            .missing
+            .iter()
+            .map(|m| m.prerequisite.clone())
+            .collect();
+        assert_eq!(4, prerequisites.len());
+        assert_eq!(Prerequisite::AppOptIn, prerequisites[0]);
+        assert_eq!(Prerequisite::FundsAssetOptIn, prerequisites[1]);
+        assert_eq!(
+            Prerequisite::Funds {
+                missing: FundsAmount::new(10_000_000)
+            },
+            prerequisites[2]
+        );
+        // app opt-in, funds and shares asset opt-ins, the invest txs and the fix txs
+        let fix_txs_fees = algod.params().min_fee * 2;
+        assert_eq!(
+            Prerequisite::Algos {
+                missing: dao_app_optin_min_balance()
+                    + MicroAlgos(2 * 100_000)
+                    + invest_fee(&algod, &dao).await?
+                    + fix_txs_fees
+            },
+            prerequisites[3]
+        );
+
+        // the fix txs
+        match &readiness.missing[0].fix_tx.as_ref().unwrap().txn_type {
+            TransactionType::ApplicationCallTransaction(c) => {
+                assert_eq!(Some(FIXTURE_APP_ID), c.app_id)
+            }
+            _ => panic!("App opt-in is expected to be an app call"),
+        }
+        match &readiness.missing[1].fix_tx.as_ref().unwrap().txn_type {
+            TransactionType::AssetAcceptTransaction(a) => {
+                assert_eq!(FIXTURE_FUNDS_ASSET_ID, a.xfer)
+            }
+            _ => panic!("Funds asset opt-in is expected to be an asset accept"),
+        }
+        assert!(readiness.missing[2].fix_tx.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_lock_ready() -> Result<()> {
+        let investor = investor1().address();
+        let algod = FakeAlgod::new().with_account(algod_account(
+            &investor,
+            10_000_000,
+            &[(FIXTURE_SHARES_ASSET_ID, 10)],
+            Some(FIXTURE_APP_ID),
+        )?);
+        let dao = dao_fixture(investor2().address())?;
+
+        let readiness = readiness(
+            &algod,
+            &investor,
+            &dao,
+            DaoAction::Lock {
+                share_amount: ShareAmount::new(10),
+            },
+        )
+        .await?;
+
+        assert!(readiness.is_ready());
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_lock_without_enough_shares() -> Result<()> {
+        let investor = investor1().address();
+        let algod = FakeAlgod::new().with_account(algod_account(
+            &investor,
+            10_000_000,
+            &[(FIXTURE_SHARES_ASSET_ID, 4)],
+            Some(FIXTURE_APP_ID),
+        )?);
+        let dao = dao_fixture(investor2().address())?;
+
+        let readiness = readiness(
+            &algod,
+            &investor,
+            &dao,
+            DaoAction::Lock {
+                share_amount: ShareAmount::new(10),
+            },
+        )
+        .await?;
+
+        assert_eq!(1, readiness.missing.len());
+        assert_eq!(
+            Prerequisite::Shares {
+                missing: ShareAmount::new(6)
+            },
+            readiness.missing[0].prerequisite
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_claim_requires_funds_asset_opt_in() -> Result<()> {
+        let investor = investor1().address();
+        let algod = FakeAlgod::new().with_account(algod_account(
+            &investor,
+            10_000_000,
+            &[],
+            Some(FIXTURE_APP_ID),
+        )?);
+        let dao = dao_fixture(investor2().address())?;
+
+        let readiness = readiness(&algod, &investor, &dao, DaoAction::Claim).await?;
+
+        assert_eq!(1, readiness.missing.len());
+        assert_eq!(
+            Prerequisite::FundsAssetOptIn,
+            readiness.missing[0].prerequisite
+        );
+        assert!(readiness.missing[0].fix_tx.is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_claim_requires_app_opt_in() -> Result<()> {
+        let investor = investor1().address();
+        let algod = FakeAlgod::new().with_account(algod_account(
+            &investor,
+            10_000_000,
+            &[(FIXTURE_FUNDS_ASSET_ID, 0)],
+            None,
+        )?);
+        let dao = dao_fixture(investor2().address())?;
+
+        let readiness = readiness(&algod, &investor, &dao, DaoAction::Claim).await?;
+
+        assert_eq!(1, readiness.missing.len());
+        assert_eq!(Prerequisite::AppOptIn, readiness.missing[0].prerequisite);
+        assert!(readiness.missing[0].fix_tx.is_some());
+
+        Ok(())
+    }
+}
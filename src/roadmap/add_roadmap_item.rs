@@ -1,6 +1,10 @@
 use super::note::roadmap_item_to_note;
+use crate::{
+    dao_algod::DaoAlgod,
+    signer::{Sign, SignedTxs, TxSigner},
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
     core::{Address, MicroAlgos},
     crypto::HashDigest,
     transaction::{Pay, SignedTransaction, Transaction, TxnBuilder},
@@ -12,7 +16,7 @@ use serde::{Deserialize, Serialize};
 use sha2::Digest;
 
 pub async fn add_roadmap_item(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     dao_creator: &Address,
     item_inputs: &RoadmapItemInputs,
 ) -> Result<AddRoadmapItemToSign> {
@@ -33,12 +37,12 @@ pub async fn add_roadmap_item(
 }
 
 pub async fn submit_add_roadmap_item(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     signed: &AddRoadmapItemToSigned,
-) -> Result<String> {
+) -> Result<Submission> {
     let res = algod.broadcast_signed_transaction(&signed.tx).await?;
     log::debug!("Add roadmap item tx id: {:?}", res.tx_id);
-    Ok(res.tx_id)
+    Submission::from_response(&res)
 }
 
 #[derive(Debug, Clone)]
@@ -51,6 +55,22 @@ pub struct AddRoadmapItemToSigned {
     pub tx: SignedTransaction,
 }
 
+impl Sign for AddRoadmapItemToSign {
+    type Signed = AddRoadmapItemToSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<AddRoadmapItemToSigned> {
+        Ok(AddRoadmapItemToSigned {
+            tx: signer.sign_tx(self.tx)?,
+        })
+    }
+}
+
+impl SignedTxs for AddRoadmapItemToSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.tx.clone()]
+    }
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub struct RoadmapItemInputs {
     pub dao_id: DaoId,
@@ -1,25 +1,72 @@
 use super::{add_roadmap_item::RoadmapItem, note::base64_maybe_roadmap_note_to_roadmap_item};
+use crate::{dao_indexer::DaoIndexer, flows::transfer_ownership::history::ownership_history};
 use algonaut::{
-    core::Address, crypto::HashDigest, indexer::v2::Indexer, model::indexer::v2::QueryTransaction,
+    core::Address,
+    crypto::HashDigest,
+    model::indexer::v2::{QueryTransaction, Transaction},
 };
 use anyhow::{anyhow, Error, Result};
 use chrono::{DateTime, Utc};
 use mbase::{date_util::timestamp_seconds_to_date, models::{dao_id::DaoId, tx_id::TxId}};
 use serde::Serialize;
 
+/// `dao_owner`: the current owner. The items added by the previous owners, while they owned the dao, are included.
 pub async fn get_roadmap(
-    indexer: &Indexer,
-    dao_creator: &Address,
+    indexer: &dyn DaoIndexer,
+    dao_owner: &Address,
     dao_id: DaoId,
 ) -> Result<Roadmap> {
-    // We get all the txs sent by dao's creator and filter manually by the dao prefix
+    let history = ownership_history(indexer, dao_id, dao_owner).await?;
+
+    let mut roadmap_items = vec![];
+    for owner in history.owners() {
+        for tx in owner_roadmap_txs(indexer, &owner).await? {
+            if let Some(note) = tx.note.clone() {
+                if let Some(roadmap_item) =
+                    base64_maybe_roadmap_note_to_roadmap_item(&note, dao_id)?
+                {
+                    let round = tx.confirmed_round.ok_or_else(|| {
+                        anyhow!("Unexpected: tx has no confirmed round: {:?}", tx)
+                    })?;
+                    // items sent by previous owners after the transfer don't belong to the roadmap
+                    if history.owner_at(round) != owner {
+                        continue;
+                    }
+
+                    // Round time is documented as optional (https://developer.algorand.org/docs/rest-apis/indexer/#transaction)
+                    // Unclear when it's None. For now we just reject it.
+                    let round_time = tx
+                        .round_time
+                        .ok_or_else(|| anyhow!("Unexpected: tx has no round time: {:?}", tx))?;
+
+                    let id = tx
+                        .id
+                        .clone()
+                        .ok_or_else(|| anyhow!("Unexpected: tx has no id: {:?}", tx))?;
+
+                    let saved_roadmap_item =
+                        to_saved_roadmap_item(&roadmap_item, &id.parse()?, round_time)?;
+                    roadmap_items.push(saved_roadmap_item);
+                }
+            }
+        }
+    }
+
+    Ok(Roadmap {
+        items: roadmap_items,
+    })
+}
+
+/// Payments sent by the owner: roadmap items are 0 payments to themselves, see [super::add_roadmap_item::add_roadmap_item]
+async fn owner_roadmap_txs(indexer: &dyn DaoIndexer, owner: &Address) -> Result<Vec<Transaction>> {
+    // We get all the txs sent by the owner and filter manually by the dao prefix
     // Algorand's indexer has performance problems with note-prefix and it doesn't work at all with AlgoExplorer or PureStake currently:
     // https://github.com/algorand/indexer/issues/358
     // https://github.com/algorand/indexer/issues/669
 
-    let response = indexer
+    let txs = indexer
         .transactions(&QueryTransaction {
-            address: Some(dao_creator.to_string()),
+            address: Some(owner.to_string()),
             // indexer disabled this, for performance apparently https://github.com/algorand/indexer/commit/1216e7957d5fba7c6a858e244a2aaf7e99412e5d
             // so we filter locally
             // address_role: Some(Role::Sender),
@@ -27,39 +74,14 @@ pub async fn get_roadmap(
         })
         .await?;
 
-    let mut roadmap_items = vec![];
-
-    for tx in response.transactions {
+    let mut owner_txs = vec![];
+    for tx in txs {
         let sender_address = tx.sender.parse::<Address>().map_err(Error::msg)?;
-        if &sender_address == dao_creator {
-            // Round time is documented as optional (https://developer.algorand.org/docs/rest-apis/indexer/#transaction)
-            // Unclear when it's None. For now we just reject it.
-            let round_time = tx
-                .round_time
-                .ok_or_else(|| anyhow!("Unexpected: tx has no round time: {:?}", tx))?;
-
-            if tx.payment_transaction.is_some() {
-                if let Some(note) = tx.note.clone() {
-                    if let Some(roadmap_item) =
-                        base64_maybe_roadmap_note_to_roadmap_item(&note, dao_id)?
-                    {
-                        let id = tx
-                            .id
-                            .clone()
-                            .ok_or_else(|| anyhow!("Unexpected: tx has no id: {:?}", tx))?;
-
-                        let saved_roadmap_item =
-                            to_saved_roadmap_item(&roadmap_item, &id.parse()?, round_time)?;
-                        roadmap_items.push(saved_roadmap_item);
-                    }
-                }
-            }
+        if &sender_address == owner && tx.payment_transaction.is_some() {
+            owner_txs.push(tx);
         }
     }
-
-    Ok(Roadmap {
-        items: roadmap_items,
-    })
+    Ok(owner_txs)
 }
 
 #[derive(Debug, Clone, Serialize)]
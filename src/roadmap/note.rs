@@ -1,4 +1,7 @@
-use crate::{note::capi_note_prefix, roadmap::add_roadmap_item::RoadmapItem};
+use crate::{
+    note::{capi_note_prefix, ensure_note_fits},
+    roadmap::add_roadmap_item::RoadmapItem,
+};
 use anyhow::{anyhow, Result};
 use data_encoding::BASE64;
 use mbase::models::dao_id::DaoId;
@@ -12,7 +15,7 @@ fn roadmap_note_identifier() -> [u8; 4] {
 pub fn roadmap_item_to_note(item: &RoadmapItem) -> Result<Vec<u8>> {
     let serialized = rmp_serde::to_vec_named(item)?;
     let version_bytes = u16::to_be_bytes(1);
-    Ok([
+    let note = [
         // add some prefixes to ensure that the context is correct
         // note that this makes sense specifically for the roadmap, as we're fetching transactions sent by the dao creator
         // the creator can unrestrictedly add roadmap items - security wise we only need to know the txs come from the creator
@@ -25,7 +28,9 @@ pub fn roadmap_item_to_note(item: &RoadmapItem) -> Result<Vec<u8>> {
         &item.dao_id.bytes(),
         &serialized,
     ]
-    .concat())
+    .concat();
+    ensure_note_fits(&note, "roadmap item")?;
+    Ok(note)
 }
 
 /// Parses a possible roadmap note.
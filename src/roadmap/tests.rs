@@ -19,7 +19,6 @@ mod tests {
     #[test]
     #[serial]
     // For now ignore, as it needs a long delay (> 1 min) to wait for indexing
-    // TODO: can we trigger indexing manually?
     #[ignore]
     async fn test_add_roadmap_item() -> Result<()> {
         let td = test_dao_init().await?;
@@ -38,9 +37,9 @@ mod tests {
 
         let signed_tx = td.creator.sign_transaction(to_sign.tx)?;
 
-        let tx_id =
+        let submission =
             submit_add_roadmap_item(&algod, &AddRoadmapItemToSigned { tx: signed_tx }).await?;
-        log::debug!("Add roadmap item tx id: {}", tx_id);
+        log::debug!("Add roadmap item tx id: {:?}", submission.tx_id);
 
         log::debug!("Waiting for indexing..");
         std::thread::sleep(std::time::Duration::from_secs(120));
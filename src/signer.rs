@@ -0,0 +1,297 @@
+use algonaut::{
+    core::{Address, MultisigAddress},
+    crypto::Signature,
+    transaction::{
+        account::Account, contract_account::ContractAccount, transaction::TransactionSignature,
+        SignedTransaction, Transaction,
+    },
+};
+use anyhow::{anyhow, Result};
+
+/// Signs single transactions, independently of how the authorizing account signs (key, multisig, logic sig).
+pub trait TxSigner {
+    fn sign_tx(&self, tx: Transaction) -> Result<SignedTransaction>;
+
+    /// Who signs the transaction, e.g. to tell the wallets (see [crate::wallet::to_wallet_txs]).
+    fn authorizer(&self, tx: &Transaction) -> TxAuthorizer {
+        TxAuthorizer::Key(tx.sender())
+    }
+}
+
+/// The account authorizing a transaction: its sender or, if the sender was rekeyed, the auth address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxAuthorizer {
+    Key(Address),
+    /// `signers`: the members that sign
+    Multisig {
+        address: MultisigAddress,
+        signers: Vec<Address>,
+    },
+    /// Nothing to sign: the program approves the transaction
+    LogicSig(Address),
+}
+
+impl TxAuthorizer {
+    pub fn address(&self) -> Address {
+        match self {
+            TxAuthorizer::Key(address) | TxAuthorizer::LogicSig(address) => *address,
+            TxAuthorizer::Multisig { address, .. } => address.address(),
+        }
+    }
+}
+
+/// Implemented by the flows' `*ToSign` structs: signs all their transactions with the signer,
+/// returning the `*Signed` counterpart expected by the respective submit function.
+pub trait Sign {
+    type Signed;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<Self::Signed>;
+}
+
+/// Implemented by the flows' `*Signed` structs: their transactions, in the order they're submitted.
+pub trait SignedTxs {
+    fn signed_txs(&self) -> Vec<SignedTransaction>;
+}
+
+/// The unsigned transactions of a `*ToSign` struct, in the order they're submitted.
+/// The `*Signed` structs know the order, so we "sign" with placeholder signatures and read it from there.
+pub fn group_txs<T>(to_sign: &T) -> Result<Vec<Transaction>>
+where
+    T: Sign + Clone,
+    T::Signed: SignedTxs,
+{
+    Ok(to_sign
+        .clone()
+        .sign(&PlaceholderSigner)?
+        .signed_txs()
+        .into_iter()
+        .map(|signed| signed.transaction)
+        .collect())
+}
+
+struct PlaceholderSigner;
+
+impl TxSigner for PlaceholderSigner {
+    fn sign_tx(&self, tx: Transaction) -> Result<SignedTransaction> {
+        Ok(SignedTransaction {
+            transaction_id: tx.id()?,
+            transaction: tx,
+            sig: TransactionSignature::Single(Signature([0; 64])),
+            auth_address: None,
+        })
+    }
+}
+
+impl TxSigner for Account {
+    fn sign_tx(&self, tx: Transaction) -> Result<SignedTransaction> {
+        Ok(self.sign_transaction(tx)?)
+    }
+
+    fn authorizer(&self, _tx: &Transaction) -> TxAuthorizer {
+        TxAuthorizer::Key(self.address())
+    }
+}
+
+/// Signs with all the accounts, in order.
+/// The accounts have to be members of the multisig and there have to be at least as many as the threshold
+/// (this isn't checked here - if it isn't the case, the transaction is rejected when submitting).
+pub struct MultisigSigner {
+    pub address: MultisigAddress,
+    pub signers: Vec<Account>,
+}
+
+impl TxSigner for MultisigSigner {
+    fn sign_tx(&self, tx: Transaction) -> Result<SignedTransaction> {
+        let (first, rest) = self
+            .signers
+            .split_first()
+            .ok_or_else(|| anyhow!("Multisig signer needs at least 1 account"))?;
+
+        let mut msig = first.init_transaction_msig(&tx, &self.address)?;
+        for signer in rest {
+            msig = signer.append_to_transaction_msig(&tx, msig)?;
+        }
+
+        let msig_address = self.address.address();
+        Ok(SignedTransaction {
+            transaction_id: tx.id()?,
+            // the sender was rekeyed to the multisig
+            auth_address: if tx.sender() != msig_address {
+                Some(msig_address)
+            } else {
+                None
+            },
+            transaction: tx,
+            sig: TransactionSignature::Multi(msig),
+        })
+    }
+
+    fn authorizer(&self, _tx: &Transaction) -> TxAuthorizer {
+        TxAuthorizer::Multisig {
+            address: self.address.clone(),
+            signers: self.signers.iter().map(|s| s.address()).collect(),
+        }
+    }
+}
+
+/// Signs with the program (escrow / delegated logic sig), passing the args to it.
+pub struct LogicSigSigner {
+    pub contract: ContractAccount,
+    pub args: Vec<Vec<u8>>,
+}
+
+impl TxSigner for LogicSigSigner {
+    fn sign_tx(&self, tx: Transaction) -> Result<SignedTransaction> {
+        Ok(self.contract.sign(tx, self.args.clone())?)
+    }
+
+    fn authorizer(&self, _tx: &Transaction) -> TxAuthorizer {
+        TxAuthorizer::LogicSig(*self.contract.address())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LogicSigSigner, MultisigSigner, TxSigner};
+    use crate::dao_algod::fake::fake_params;
+    use algonaut::{
+        core::{Address, CompiledTeal, MicroAlgos, MultisigAddress},
+        crypto::Signature,
+        transaction::{
+            account::Account, contract_account::ContractAccount, transaction::TransactionSignature,
+            Pay, Transaction, TxnBuilder,
+        },
+    };
+    use anyhow::{anyhow, Error, Result};
+    use network_test_util::test_data::{creator, investor1, investor2};
+    use tokio::test;
+
+    fn payment(sender: Address) -> Result<Transaction> {
+        Ok(TxnBuilder::with(
+            &fake_params(),
+            Pay::new(sender, investor1().address(), MicroAlgos(1_000)).build(),
+        )
+        .build()?)
+    }
+
+    /// 2 of 3: creator, investor1, investor2
+    fn msig_address() -> Result<MultisigAddress> {
+        MultisigAddress::new(
+            1,
+            2,
+            &[
+                creator().address(),
+                investor1().address(),
+                investor2().address(),
+            ],
+        )
+        .map_err(Error::msg)
+    }
+
+    /// The member's signature of the tx, as a single signer (ed25519 signatures are deterministic)
+    fn single_sig(account: &Account, tx: &Transaction) -> Result<Signature> {
+        match account.sign_transaction(tx.clone())?.sig {
+            TransactionSignature::Single(sig) => Ok(sig),
+            sig => Err(anyhow!("Expected a single signature: {sig:?}")),
+        }
+    }
+
+    #[test]
+    async fn test_multisig_signer() -> Result<()> {
+        let msig_address = msig_address()?;
+        let tx = payment(msig_address.address())?;
+        let signer = MultisigSigner {
+            address: msig_address.clone(),
+            signers: vec![creator(), investor2()],
+        };
+
+        let signed = signer.sign_tx(tx.clone())?;
+
+        assert_eq!(tx, signed.transaction);
+        assert_eq!(tx.id()?, signed.transaction_id);
+        // the sender is the multisig itself
+        assert_eq!(None, signed.auth_address);
+
+        let msig = match signed.sig {
+            TransactionSignature::Multi(msig) => msig,
+            sig => return Err(anyhow!("Expected a multisig signature: {sig:?}")),
+        };
+        assert_eq!(1, msig.version);
+        assert_eq!(2, msig.threshold);
+        // a subsig per member, in the address' order, signed only by the signers
+        assert_eq!(3, msig.subsigs.len());
+        let members = [creator(), investor1(), investor2()];
+        for (subsig, member) in msig.subsigs.iter().zip(members.iter()) {
+            assert_eq!(member.address().0, subsig.key.0);
+        }
+        for (index, signer) in [(0, creator()), (2, investor2())].iter() {
+            assert_eq!(Some(single_sig(signer, &tx)?), msig.subsigs[*index].sig);
+        }
+        assert_eq!(None, msig.subsigs[1].sig);
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_multisig_signer_for_rekeyed_account() -> Result<()> {
+        let msig_address = msig_address()?;
+        // rekeyed to the multisig
+        let sender = creator().address();
+        let signer = MultisigSigner {
+            address: msig_address.clone(),
+            signers: vec![investor1(), investor2()],
+        };
+
+        let signed = signer.sign_tx(payment(sender)?)?;
+
+        assert_eq!(sender, signed.transaction.sender());
+        assert_eq!(Some(msig_address.address()), signed.auth_address);
+        assert!(matches!(signed.sig, TransactionSignature::Multi(_)));
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_multisig_signer_without_signers_fails() -> Result<()> {
+        let msig_address = msig_address()?;
+        let signer = MultisigSigner {
+            address: msig_address.clone(),
+            signers: vec![],
+        };
+
+        assert!(signer.sign_tx(payment(msig_address.address())?).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_logic_sig_signer() -> Result<()> {
+        // not evaluated when signing: any bytes work as program
+        let program = CompiledTeal(vec![6, 129, 1]);
+        let contract = ContractAccount::new(program.clone());
+        let tx = payment(*contract.address())?;
+        let args = vec![b"arg1".to_vec(), 2u64.to_be_bytes().to_vec()];
+        let signer = LogicSigSigner {
+            contract,
+            args: args.clone(),
+        };
+
+        let signed = signer.sign_tx(tx.clone())?;
+
+        assert_eq!(tx, signed.transaction);
+        assert_eq!(tx.id()?, signed.transaction_id);
+        assert_eq!(None, signed.auth_address);
+
+        let lsig = match signed.sig {
+            TransactionSignature::Logic(lsig) => lsig,
+            sig => return Err(anyhow!("Expected a logic signature: {sig:?}")),
+        };
+        // escrow: the program is the sender, nobody signs the program
+        assert_eq!(program, lsig.logic);
+        assert_eq!(args, lsig.args);
+        assert_eq!(None, lsig.sig);
+        assert_eq!(None, lsig.msig);
+
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
+use crate::dao_algod::DaoAlgod;
 use algonaut::{
-    algod::v2::Algod,
     core::Address,
     model::algod::v2::{Account, AssetHolding},
 };
@@ -12,7 +12,7 @@ use mbase::models::{
 
 /// Returns asset holdings. If not opted in, returns 0 holdings.
 pub async fn asset_holdings(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     address: &Address,
     asset_id: u64,
 ) -> Result<AssetAmount> {
@@ -33,7 +33,7 @@ pub fn asset_holdings_from_account(account: &Account, asset_id: u64) -> Result<A
 
 /// Returns asset holdings if opted in, otherwise error
 pub async fn asset_holdings_if_opted_in(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     address: &Address,
     asset_id: u64,
 ) -> Result<AssetAmount> {
@@ -54,7 +54,7 @@ pub fn asset_holdings_from_account_if_opted_in(
 }
 
 pub async fn funds_holdings(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     address: &Address,
     asset_id: FundsAssetId,
 ) -> Result<FundsAmount> {
@@ -82,7 +82,7 @@ pub fn find_asset_holding_or_err(holdings: &[AssetHolding], asset_id: u64) -> Re
 }
 
 pub async fn share_holdings(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     address: &Address,
     share_asset_id: u64,
 ) -> Result<ShareAmount> {
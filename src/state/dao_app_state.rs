@@ -0,0 +1,36 @@
+use crate::dao_algod::DaoAlgod;
+use algonaut::core::Address;
+use anyhow::Result;
+use mbase::{
+    models::dao_app_id::DaoAppId,
+    state::{
+        app_state::ApplicationGlobalState,
+        dao_app_state::{
+            central_global_state_from_gs, central_investor_state_from_acc, CentralAppGlobalState,
+            CentralAppInvestorState,
+        },
+    },
+};
+
+/// Like [mbase::state::dao_app_state::dao_global_state], but fetched with [DaoAlgod::app_global_state] (so it works with the fake too)
+pub async fn dao_global_state(
+    algod: &dyn DaoAlgod,
+    app_id: DaoAppId,
+) -> Result<CentralAppGlobalState> {
+    let key_values = algod.app_global_state(app_id.0).await?;
+    central_global_state_from_gs(&ApplicationGlobalState(key_values))
+}
+
+/// Like [mbase::state::dao_app_state::dao_investor_state], but fetched with [DaoAlgod].
+/// None if the investor isn't opted in to the app.
+pub async fn dao_investor_state(
+    algod: &dyn DaoAlgod,
+    investor: &Address,
+    app_id: DaoAppId,
+) -> Result<Option<CentralAppInvestorState>> {
+    let account = algod.account_information(investor).await?;
+    if !account.apps_local_state.iter().any(|s| s.id == app_id.0) {
+        return Ok(None);
+    }
+    Ok(Some(central_investor_state_from_acc(&account, app_id)?))
+}
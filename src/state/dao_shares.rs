@@ -1,10 +1,11 @@
-use super::account_state::asset_holdings;
-use algonaut::{algod::v2::Algod, core::to_app_address};
+use super::{account_state::asset_holdings, dao_app_state::dao_global_state};
+use crate::dao_algod::DaoAlgod;
+use algonaut::core::to_app_address;
 use anyhow::{anyhow, Result};
 use mbase::{
     checked::CheckedAdd,
     models::{dao_app_id::DaoAppId, share_amount::ShareAmount},
-    state::dao_app_state::{dao_global_state, CentralAppGlobalState},
+    state::dao_app_state::CentralAppGlobalState,
 };
 
 /// Shares on the DAO's app escrow
@@ -30,7 +31,7 @@ impl DaoShareHoldings {
 }
 
 pub async fn dao_shares(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     app_id: DaoAppId,
     shares_id: u64,
 ) -> Result<DaoShareHoldings> {
@@ -51,7 +52,7 @@ pub async fn dao_shares(
 }
 
 pub async fn dao_shares_with_dao_state(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     app_id: DaoAppId,
     shares_id: u64,
     dao_state: &CentralAppGlobalState,
@@ -61,7 +62,7 @@ pub async fn dao_shares_with_dao_state(
 }
 
 pub async fn dao_shares_with_holdings(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     app_id: DaoAppId,
     dao_holdings: ShareAmount,
 ) -> Result<DaoShareHoldings> {
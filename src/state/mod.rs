@@ -1,3 +1,3 @@
 pub mod account_state;
-// pub mod dao_app_state;
+pub mod dao_app_state;
 pub mod dao_shares;
@@ -0,0 +1,278 @@
+use crate::dao_algod::DaoAlgod;
+use algonaut::model::algod::v2::{PendingTransaction, TransactionResponse};
+use anyhow::{anyhow, Error, Result};
+use futures::future::{select, Either};
+use futures_timer::Delay;
+use instant::Instant;
+use mbase::models::tx_id::TxId;
+use std::time::Duration;
+
+/// A broadcasted transaction (or group), which can be awaited for confirmation.
+/// For groups, the id is of the transaction whose result is of interest (by default the first one).
+///
+/// The flows' `submit_*` functions return right after broadcasting: waiting is up to the caller (see [Submission::wait]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub tx_id: TxId,
+}
+
+/// Limits for waiting for a confirmation: waiting ends with an error when either is exceeded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmationSettings {
+    pub timeout: Duration,
+    /// Rounds passed since starting to wait
+    pub max_rounds: u64,
+}
+
+impl Default for ConfirmationSettings {
+    fn default() -> Self {
+        // txs are confirmed in the next round normally - the extra rounds are for congestion
+        ConfirmationSettings {
+            timeout: Duration::from_secs(60),
+            max_rounds: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Confirmation {
+    pub tx_id: TxId,
+    pub confirmed_round: u64,
+    pub inner_txs: Vec<PendingTransaction>,
+    /// Assets created by the tx or its inner txs (recursively)
+    pub created_asset_ids: Vec<u64>,
+    /// Apps created by the tx or its inner txs (recursively)
+    pub created_app_ids: Vec<u64>,
+    pub pending_tx: PendingTransaction,
+}
+
+impl Submission {
+    pub fn new(tx_id: TxId) -> Submission {
+        Submission { tx_id }
+    }
+
+    pub fn from_response(response: &TransactionResponse) -> Result<Submission> {
+        Ok(Submission::new(response.tx_id.parse()?))
+    }
+
+    /// Waits for the confirmation with the default settings
+    pub async fn wait(&self, algod: &dyn DaoAlgod) -> Result<Confirmation> {
+        self.wait_with(algod, &ConfirmationSettings::default())
+            .await
+    }
+
+    pub async fn wait_with(
+        &self,
+        algod: &dyn DaoAlgod,
+        settings: &ConfirmationSettings,
+    ) -> Result<Confirmation> {
+        let start = Instant::now();
+        let start_round = algod.last_round().await?;
+        let mut round = start_round;
+
+        loop {
+            let p_tx = algod
+                .pending_transaction_with_id(&self.tx_id.to_string())
+                .await?;
+
+            if !p_tx.pool_error.is_empty() {
+                return Err(anyhow!(
+                    "Tx: {:?} was rejected by the pool: {}",
+                    self.tx_id,
+                    p_tx.pool_error
+                ));
+            }
+
+            if let Some(confirmed_round) = p_tx.confirmed_round.filter(|r| *r > 0) {
+                return Ok(to_confirmation(self.tx_id.clone(), confirmed_round, p_tx));
+            }
+
+            if round - start_round >= settings.max_rounds {
+                return Err(anyhow!(
+                    "Tx: {:?} not confirmed after {} rounds",
+                    self.tx_id,
+                    settings.max_rounds
+                ));
+            }
+            let remaining = settings.timeout.saturating_sub(start.elapsed());
+            if remaining.is_zero() {
+                return Err(self.timeout_error(settings));
+            }
+
+            // algod holds the request until the round (or its own timeout), which can exceed what's left
+            round = match select(algod.wait_for_round_after(round), Delay::new(remaining)).await {
+                Either::Left((round, _)) => round?,
+                Either::Right(_) => return Err(self.timeout_error(settings)),
+            };
+        }
+    }
+
+    fn timeout_error(&self, settings: &ConfirmationSettings) -> Error {
+        anyhow!(
+            "Tx: {:?} not confirmed after {:?}",
+            self.tx_id,
+            settings.timeout
+        )
+    }
+}
+
+impl Confirmation {
+    /// Id of the asset created by the tx itself (not by its inner txs)
+    pub fn created_asset_id(&self) -> Result<u64> {
+        self.pending_tx
+            .asset_index
+            .ok_or_else(|| anyhow!("Tx: {:?} didn't create an asset", self.tx_id))
+    }
+
+    /// Id of the app created by the tx itself (not by its inner txs)
+    pub fn created_app_id(&self) -> Result<u64> {
+        self.pending_tx
+            .application_index
+            .ok_or_else(|| anyhow!("Tx: {:?} didn't create an app", self.tx_id))
+    }
+}
+
+fn to_confirmation(tx_id: TxId, confirmed_round: u64, p_tx: PendingTransaction) -> Confirmation {
+    let mut created_asset_ids = vec![];
+    let mut created_app_ids = vec![];
+    collect_created_ids(&p_tx, &mut created_asset_ids, &mut created_app_ids);
+
+    Confirmation {
+        tx_id,
+        confirmed_round,
+        inner_txs: p_tx.inner_txs.clone(),
+        created_asset_ids,
+        created_app_ids,
+        pending_tx: p_tx,
+    }
+}
+
+fn collect_created_ids(
+    p_tx: &PendingTransaction,
+    asset_ids: &mut Vec<u64>,
+    app_ids: &mut Vec<u64>,
+) {
+    asset_ids.extend(p_tx.asset_index);
+    app_ids.extend(p_tx.application_index);
+    for inner in &p_tx.inner_txs {
+        collect_created_ids(inner, asset_ids, app_ids);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConfirmationSettings, Submission};
+    use crate::dao_algod::{
+        fake::{fake_params, FakeAlgod},
+        DaoAlgod,
+    };
+    use algonaut::{
+        core::MicroAlgos,
+        model::algod::v2::PendingTransaction,
+        transaction::{Pay, TxnBuilder},
+    };
+    use anyhow::Result;
+    use network_test_util::test_data::{creator, investor1};
+    use serde_json::json;
+    use std::time::Duration;
+    use tokio::test;
+
+    const TX_ID: &str = "ZKLYCEWKDO64V6WCGGZZUI64JWTYN37YCR6E44VZQB3YLL7OJC5Q";
+
+    /// Pending tx, as returned by algod. `inner_txs`: pending txs as json
+    fn pending_tx(
+        confirmed_round: Option<u64>,
+        pool_error: &str,
+        asset_index: Option<u64>,
+        inner_txs: Vec<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        let sender = creator();
+        let tx = TxnBuilder::with(
+            &fake_params(),
+            Pay::new(sender.address(), investor1().address(), MicroAlgos(1_000)).build(),
+        )
+        .build()?;
+        let mut p_tx = json!({
+            "pool-error": pool_error,
+            "txn": serde_json::to_value(&sender.sign_transaction(tx)?)?,
+            "inner-txns": inner_txs,
+        });
+        if let Some(round) = confirmed_round {
+            p_tx["confirmed-round"] = json!(round);
+        }
+        if let Some(asset_id) = asset_index {
+            p_tx["asset-index"] = json!(asset_id);
+        }
+        Ok(p_tx)
+    }
+
+    fn to_p_tx(json: serde_json::Value) -> Result<PendingTransaction> {
+        Ok(serde_json::from_value(json)?)
+    }
+
+    fn submission() -> Result<Submission> {
+        Ok(Submission::new(TX_ID.parse()?))
+    }
+
+    #[test]
+    async fn test_wait_confirmed_after_some_rounds() -> Result<()> {
+        let inner = pending_tx(Some(4), "", Some(11), vec![])?;
+        let p_tx = to_p_tx(pending_tx(Some(4), "", Some(10), vec![inner])?)?;
+        // the fake starts at round 1
+        let algod = FakeAlgod::new().with_pending_transaction_confirmed_at(TX_ID, p_tx, 4);
+
+        let confirmation = submission()?.wait(&algod).await?;
+
+        assert_eq!(4, confirmation.confirmed_round);
+        assert_eq!(vec![10, 11], confirmation.created_asset_ids);
+        assert!(confirmation.created_app_ids.is_empty());
+        assert_eq!(10, confirmation.created_asset_id()?);
+        assert!(confirmation.created_app_id().is_err());
+        Ok(())
+    }
+
+    #[test]
+    async fn test_wait_fails_after_max_rounds() -> Result<()> {
+        let p_tx = to_p_tx(pending_tx(Some(20), "", None, vec![])?)?;
+        let algod = FakeAlgod::new().with_pending_transaction_confirmed_at(TX_ID, p_tx, 20);
+
+        let settings = ConfirmationSettings {
+            max_rounds: 5,
+            ..ConfirmationSettings::default()
+        };
+        let res = submission()?.wait_with(&algod, &settings).await;
+
+        assert!(res.is_err());
+        // stopped waiting when reaching the limit
+        assert_eq!(6, algod.last_round().await?);
+        Ok(())
+    }
+
+    #[test]
+    async fn test_wait_fails_after_timeout_when_rounds_dont_advance() -> Result<()> {
+        let p_tx = to_p_tx(pending_tx(Some(2), "", None, vec![])?)?;
+        let algod = FakeAlgod::new()
+            .with_pending_transaction_confirmed_at(TX_ID, p_tx, 2)
+            .with_stalled_rounds();
+
+        let settings = ConfirmationSettings {
+            timeout: Duration::from_millis(100),
+            ..ConfirmationSettings::default()
+        };
+        let res = submission()?.wait_with(&algod, &settings).await;
+
+        assert!(res.is_err());
+        Ok(())
+    }
+
+    #[test]
+    async fn test_wait_fails_when_rejected_by_pool() -> Result<()> {
+        let p_tx = to_p_tx(pending_tx(None, "overspend", None, vec![])?)?;
+        let algod = FakeAlgod::new().with_pending_transaction(TX_ID, p_tx);
+
+        let res = submission()?.wait(&algod).await;
+
+        assert!(res.is_err());
+        Ok(())
+    }
+}
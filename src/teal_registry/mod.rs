@@ -0,0 +1,302 @@
+use crate::{
+    flows::create_dao::setup::create_app::{approval_v1_values, render_central_app_clear_v1},
+    teal::TealApi,
+};
+use algonaut::core::Address;
+use anyhow::{anyhow, Result};
+use mbase::{
+    api::{
+        contract::Contract,
+        version::{Version, VersionedTealSourceTemplate},
+    },
+    models::{
+        capi_deps::CapiAddress, funds::FundsAmount, share_amount::ShareAmount,
+        shares_percentage::SharesPercentage,
+    },
+    teal::{render_template_new, TealSource, TealSourceTemplate},
+};
+use std::fmt;
+
+#[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+use mbase::teal::save_rendered_teal;
+
+mod tests;
+
+/// Values the DAO app approval templates are rendered with.
+/// Each version uses a subset of them, see [ApprovalVersion::params].
+#[derive(Debug)]
+pub struct ApprovalTemplateParams {
+    pub share_supply: ShareAmount,
+    pub precision: u64,
+    pub investors_share: SharesPercentage,
+    pub capi_address: CapiAddress,
+    pub capi_percentage: SharesPercentage,
+    pub share_price: FundsAmount,
+    pub max_raisable_amount: FundsAmount,
+}
+
+const PLACEHOLDER_PREFIX: &str = "TMPL_";
+
+/// A placeholder in the approval template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateParam {
+    ShareSupply,
+    InvestorsShare,
+    Precision,
+    PrecisionSquare,
+    CapiEscrowAddress,
+    CapiShare,
+    SharePrice,
+    MaxRaisableAmount,
+}
+
+impl TemplateParam {
+    pub fn placeholder(&self) -> &'static str {
+        match self {
+            TemplateParam::ShareSupply => "TMPL_SHARE_SUPPLY",
+            TemplateParam::InvestorsShare => "TMPL_INVESTORS_SHARE",
+            TemplateParam::Precision => "TMPL_PRECISION__",
+            TemplateParam::PrecisionSquare => "TMPL_PRECISION_SQUARE",
+            TemplateParam::CapiEscrowAddress => "TMPL_CAPI_ESCROW_ADDRESS",
+            TemplateParam::CapiShare => "TMPL_CAPI_SHARE",
+            TemplateParam::SharePrice => "TMPL_SHARE_PRICE",
+            TemplateParam::MaxRaisableAmount => "TMPL_MAX_RAISABLE_AMOUNT",
+        }
+    }
+}
+
+/// Value a placeholder is replaced with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateValue {
+    Int(u64),
+    Address(Address),
+}
+
+impl fmt::Display for TemplateValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateValue::Int(value) => write!(f, "{value}"),
+            TemplateValue::Address(address) => write!(f, "{address}"),
+        }
+    }
+}
+
+/// Maps the params to the values of the version's placeholders
+pub type ApprovalValues =
+    fn(&ApprovalTemplateParams) -> Result<Vec<(TemplateParam, TemplateValue)>>;
+pub type RenderClear = fn(&TealSourceTemplate) -> Result<TealSource>;
+
+pub struct ApprovalVersion {
+    pub version: Version,
+    /// The placeholders the version's template contains
+    pub params: &'static [TemplateParam],
+    pub values: ApprovalValues,
+}
+
+pub struct ClearVersion {
+    pub version: Version,
+    pub render: RenderClear,
+}
+
+/// The supported versions of the DAO app's programs.
+/// A new contract version is supported by registering it here, with the values of its placeholders.
+#[derive(Default)]
+pub struct TealRegistry {
+    approval: Vec<ApprovalVersion>,
+    clear: Vec<ClearVersion>,
+}
+
+const APPROVAL_V1_PARAMS: &[TemplateParam] = &[
+    TemplateParam::ShareSupply,
+    TemplateParam::InvestorsShare,
+    TemplateParam::Precision,
+    TemplateParam::PrecisionSquare,
+    TemplateParam::CapiEscrowAddress,
+    TemplateParam::CapiShare,
+    TemplateParam::SharePrice,
+    TemplateParam::MaxRaisableAmount,
+];
+
+/// The registry with all the versions of the DAO app
+pub fn dao_teal_registry() -> TealRegistry {
+    TealRegistry::default()
+        .with_approval(ApprovalVersion {
+            version: Version(1),
+            params: APPROVAL_V1_PARAMS,
+            values: approval_v1_values,
+        })
+        .with_clear(ClearVersion {
+            version: Version(1),
+            render: render_central_app_clear_v1,
+        })
+}
+
+/// Replaces the placeholders with the values, see [render_template_strict]
+pub fn render_approval_values(
+    template: &TealSourceTemplate,
+    values: &[(TemplateParam, TemplateValue)],
+) -> Result<TealSource> {
+    let values: Vec<(&str, String)> = values
+        .iter()
+        .map(|(param, value)| (param.placeholder(), value.to_string()))
+        .collect();
+    let key_values: Vec<(&str, &str)> = values
+        .iter()
+        .map(|(placeholder, value)| (*placeholder, value.as_str()))
+        .collect();
+
+    let source = render_template_strict(template, &key_values)?;
+    // debugging - not in release builds, which may render many programs (e.g. verifying daos)
+    #[cfg(all(debug_assertions, not(target_arch = "wasm32")))]
+    save_rendered_teal("dao_app_approval", source.clone())?;
+    Ok(source)
+}
+
+/// Renders the template, failing if a key isn't in the template
+/// or if a placeholder (`TMPL_` token) isn't replaced.
+pub fn render_template_strict(
+    template: &TealSourceTemplate,
+    key_values: &[(&str, &str)],
+) -> Result<TealSource> {
+    let template_str = std::str::from_utf8(&template.0)?;
+
+    let unused: Vec<&str> = key_values
+        .iter()
+        .map(|(key, _)| *key)
+        .filter(|key| !contains_token(template_str, key))
+        .collect();
+    if !unused.is_empty() {
+        return Err(anyhow!("Keys not in the template: {unused:?}"));
+    }
+
+    let source = render_template_new(template, key_values)?;
+
+    let unreplaced = placeholders(std::str::from_utf8(&source.0)?);
+    if !unreplaced.is_empty() {
+        return Err(anyhow!("Placeholders not replaced: {unreplaced:?}"));
+    }
+    Ok(source)
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Whether `token` is in the source as a whole token, e.g. not as part of a longer placeholder
+fn contains_token(source: &str, token: &str) -> bool {
+    source.match_indices(token).any(|(start, _)| {
+        let end = start + token.len();
+        !source[..start].ends_with(is_token_char) && !source[end..].starts_with(is_token_char)
+    })
+}
+
+/// The `TMPL_` tokens in the source
+fn placeholders(source: &str) -> Vec<&str> {
+    let mut placeholders: Vec<&str> = source
+        .match_indices(PLACEHOLDER_PREFIX)
+        .map(|(start, _)| {
+            let len = source[start..]
+                .find(|c: char| !is_token_char(c))
+                .unwrap_or(source.len() - start);
+            &source[start..start + len]
+        })
+        .collect();
+    placeholders.sort_unstable();
+    placeholders.dedup();
+    placeholders
+}
+
+impl TealRegistry {
+    pub fn with_approval(mut self, version: ApprovalVersion) -> TealRegistry {
+        self.approval.retain(|v| v.version.0 != version.version.0);
+        self.approval.push(version);
+        self
+    }
+
+    pub fn with_clear(mut self, version: ClearVersion) -> TealRegistry {
+        self.clear.retain(|v| v.version.0 != version.version.0);
+        self.clear.push(version);
+        self
+    }
+
+    pub fn approval(&self, version: Version) -> Result<&ApprovalVersion> {
+        self.approval
+            .iter()
+            .find(|v| v.version.0 == version.0)
+            .ok_or_else(|| anyhow!("Dao app approval version not supported: {version:?}"))
+    }
+
+    pub fn clear(&self, version: Version) -> Result<&ClearVersion> {
+        self.clear
+            .iter()
+            .find(|v| v.version.0 == version.0)
+            .ok_or_else(|| anyhow!("Dao app clear version not supported: {version:?}"))
+    }
+
+    /// The parameters the approval template of `version` is rendered with
+    pub fn approval_params(&self, version: Version) -> Result<&'static [TemplateParam]> {
+        Ok(self.approval(version)?.params)
+    }
+
+    pub fn approval_versions(&self) -> Vec<Version> {
+        self.approval.iter().map(|v| v.version).collect()
+    }
+
+    pub fn clear_versions(&self) -> Vec<Version> {
+        self.clear.iter().map(|v| v.version).collect()
+    }
+
+    /// The values of the placeholders of the approval template of `version`
+    pub fn approval_values(
+        &self,
+        version: Version,
+        params: &ApprovalTemplateParams,
+    ) -> Result<Vec<(TemplateParam, TemplateValue)>> {
+        let approval = self.approval(version)?;
+        let values = (approval.values)(params)?;
+
+        if let Some(missing) = approval
+            .params
+            .iter()
+            .find(|param| !values.iter().any(|(p, _)| p == *param))
+        {
+            return Err(anyhow!(
+                "No value for: {missing:?} in approval version: {version:?}"
+            ));
+        }
+        Ok(values)
+    }
+
+    pub fn render_approval(
+        &self,
+        template: &VersionedTealSourceTemplate,
+        params: &ApprovalTemplateParams,
+    ) -> Result<TealSource> {
+        let values = self.approval_values(template.version, params)?;
+        render_approval_values(&template.template, &values)
+    }
+
+    pub fn render_clear(&self, template: &VersionedTealSourceTemplate) -> Result<TealSource> {
+        (self.clear(template.version)?.render)(&template.template)
+    }
+
+    /// Fetches the approval template, if the version is supported
+    pub async fn load_approval(
+        &self,
+        api: &dyn TealApi,
+        version: Version,
+    ) -> Result<VersionedTealSourceTemplate> {
+        self.approval(version)?;
+        api.template(Contract::DaoAppApproval, version).await
+    }
+
+    /// Fetches the clear template, if the version is supported
+    pub async fn load_clear(
+        &self,
+        api: &dyn TealApi,
+        version: Version,
+    ) -> Result<VersionedTealSourceTemplate> {
+        self.clear(version)?;
+        api.template(Contract::DaoAppClear, version).await
+    }
+}
@@ -0,0 +1,235 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        teal_registry::{
+            dao_teal_registry, render_template_strict, ApprovalTemplateParams, ApprovalVersion,
+            TemplateParam, TemplateValue,
+        },
+        testing::{local_teal_api::LocalTealApi, TESTS_DEFAULT_PRECISION},
+    };
+    use anyhow::{anyhow, Result};
+    use mbase::{
+        api::version::{Version, VersionedTealSourceTemplate},
+        models::{capi_deps::CapiAddress, funds::FundsAmount, share_amount::ShareAmount},
+        teal::{TealSource, TealSourceTemplate},
+    };
+    use network_test_util::test_data::investor1;
+    use rust_decimal::Decimal;
+    use std::{convert::TryInto, path::PathBuf, str::FromStr};
+    use tokio::test;
+
+    fn approval_params() -> Result<ApprovalTemplateParams> {
+        Ok(ApprovalTemplateParams {
+            share_supply: ShareAmount::new(100),
+            precision: TESTS_DEFAULT_PRECISION,
+            investors_share: Decimal::from_str("0.4")?.try_into()?,
+            capi_address: CapiAddress(investor1().address()),
+            capi_percentage: Decimal::from_str("0.1")?.try_into()?,
+            share_price: FundsAmount::new(10),
+            max_raisable_amount: FundsAmount::new(5_000_000),
+        })
+    }
+
+    #[test]
+    async fn test_v1_approval_template_has_the_registered_params() -> Result<()> {
+        let registry = dao_teal_registry();
+        let template = registry.load_approval(&LocalTealApi, Version(1)).await?;
+        let template_str = String::from_utf8(template.template.0.clone())?;
+
+        for param in registry.approval_params(Version(1))? {
+            assert!(
+                template_str.contains(param.placeholder()),
+                "Missing placeholder: {}",
+                param.placeholder()
+            );
+        }
+
+        let rendered = registry.render_approval(&template, &approval_params()?)?;
+        assert!(!String::from_utf8(rendered.0)?.contains("TMPL_"));
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_v1_clear_is_rendered_as_is() -> Result<()> {
+        let registry = dao_teal_registry();
+        let template = registry.load_clear(&LocalTealApi, Version(1)).await?;
+
+        let rendered = registry.render_clear(&template)?;
+
+        assert_eq!(template.template.0, rendered.0);
+        Ok(())
+    }
+
+    #[test]
+    async fn test_unsupported_version_is_rejected() -> Result<()> {
+        let registry = dao_teal_registry();
+
+        assert!(registry.approval_params(Version(2)).is_err());
+        assert!(registry
+            .load_approval(&LocalTealApi, Version(2))
+            .await
+            .is_err());
+        assert!(registry
+            .load_clear(&LocalTealApi, Version(2))
+            .await
+            .is_err());
+        assert!(registry
+            .render_approval(
+                &VersionedTealSourceTemplate::new(TealSourceTemplate(vec![]), Version(2)),
+                &approval_params()?
+            )
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_register_version() -> Result<()> {
+        fn values_v2(
+            params: &ApprovalTemplateParams,
+        ) -> Result<Vec<(TemplateParam, TemplateValue)>> {
+            Ok(vec![(
+                TemplateParam::SharePrice,
+                TemplateValue::Int(params.share_price.val()),
+            )])
+        }
+
+        let registry = dao_teal_registry().with_approval(ApprovalVersion {
+            version: Version(2),
+            params: &[TemplateParam::SharePrice],
+            values: values_v2,
+        });
+
+        assert_eq!(
+            vec![1, 2],
+            registry
+                .approval_versions()
+                .iter()
+                .map(|v| v.0)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            &[TemplateParam::SharePrice],
+            registry.approval_params(Version(2))?
+        );
+
+        let rendered = registry.render_approval(
+            &VersionedTealSourceTemplate::new(
+                TealSourceTemplate(b"int TMPL_SHARE_PRICE".to_vec()),
+                Version(2),
+            ),
+            &approval_params()?,
+        )?;
+        assert_eq!(b"int 10".to_vec(), rendered.0);
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_strict_render_rejects_unreplaced_placeholder() -> Result<()> {
+        let template = TealSourceTemplate(b"int TMPL_SUPPLY\nint TMPL_PRICE".to_vec());
+
+        let res = render_template_strict(&template, &[("TMPL_SUPPLY", "100")]);
+
+        let error = res.unwrap_err().to_string();
+        assert!(error.contains("TMPL_PRICE"), "error: {error}");
+        Ok(())
+    }
+
+    #[test]
+    async fn test_strict_render_rejects_unused_key() -> Result<()> {
+        let template = TealSourceTemplate(b"int TMPL_SUPPLY".to_vec());
+
+        let res = render_template_strict(&template, &[("TMPL_SUPPLY", "100"), ("TMPL_PRICE", "1")]);
+
+        let error = res.unwrap_err().to_string();
+        assert!(error.contains("TMPL_PRICE"), "error: {error}");
+        Ok(())
+    }
+
+    #[test]
+    async fn test_strict_render_matches_whole_keys() -> Result<()> {
+        // the key is only a part of the placeholder
+        let template = TealSourceTemplate(b"int TMPL_SHARE_PRICE".to_vec());
+
+        let res = render_template_strict(&template, &[("TMPL_SHARE", "1")]);
+
+        let error = res.unwrap_err().to_string();
+        assert!(
+            error.contains("Keys not in the template") && error.contains("TMPL_SHARE"),
+            "error: {error}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    async fn test_golden_fractional_investors_share() -> Result<()> {
+        let rendered = render_v1(&ApprovalTemplateParams {
+            share_supply: ShareAmount::new(1_000_000),
+            precision: 10_000,
+            investors_share: Decimal::from_str("0.123")?.try_into()?,
+            capi_address: zero_capi_address()?,
+            capi_percentage: Decimal::from_str("0.025")?.try_into()?,
+            share_price: FundsAmount::new(1_500_000),
+            max_raisable_amount: FundsAmount::new(5_000_000_000_000),
+        })
+        .await?;
+        assert_golden("fractional_investors_share", &rendered)
+    }
+
+    #[test]
+    async fn test_golden_large_precision() -> Result<()> {
+        let rendered = render_v1(&ApprovalTemplateParams {
+            share_supply: ShareAmount::new(100),
+            precision: 1_000_000_000,
+            investors_share: Decimal::from_str("0.4")?.try_into()?,
+            capi_address: zero_capi_address()?,
+            capi_percentage: Decimal::from_str("0.1")?.try_into()?,
+            share_price: FundsAmount::new(10),
+            max_raisable_amount: FundsAmount::new(5_000_000),
+        })
+        .await?;
+        assert_golden("large_precision", &rendered)
+    }
+
+    fn golden_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/teal_registry/golden")
+    }
+
+    fn zero_capi_address() -> Result<CapiAddress> {
+        Ok(CapiAddress(
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAY5HFKQ"
+                .parse()
+                .map_err(anyhow::Error::msg)?,
+        ))
+    }
+
+    /// Renders the v1 approval template, as served to the flows
+    async fn render_v1(params: &ApprovalTemplateParams) -> Result<TealSource> {
+        let registry = dao_teal_registry();
+        let template = registry.load_approval(&LocalTealApi, Version(1)).await?;
+        registry.render_approval(&template, params)
+    }
+
+    /// Compares with the golden file. Run with UPDATE_GOLDEN=1 to (re)write the golden files.
+    fn assert_golden(name: &str, rendered: &TealSource) -> Result<()> {
+        let path = golden_dir().join(format!("{name}.teal"));
+        let rendered = String::from_utf8(rendered.0.clone())?;
+
+        if std::env::var("UPDATE_GOLDEN").is_ok() {
+            std::fs::create_dir_all(golden_dir())?;
+            std::fs::write(&path, &rendered)?;
+            return Ok(());
+        }
+
+        let golden = std::fs::read_to_string(&path).map_err(|e| {
+            anyhow!("Couldn't read golden file: {path:?} (record it with UPDATE_GOLDEN=1): {e}")
+        })?;
+        assert_eq!(
+            golden, rendered,
+            "Rendered program differs from golden file: {path:?}"
+        );
+        Ok(())
+    }
+}
@@ -1,15 +1,19 @@
+use crate::{
+    dao_algod::DaoAlgod,
+    signer::{Sign, SignedTxs, TxSigner},
+    submission::Submission,
+};
 use algonaut::{
-    algod::v2::Algod,
     core::{Address, SuggestedTransactionParams},
     transaction::{builder::CallApplication, SignedTransaction, Transaction, TxnBuilder},
 };
 use anyhow::Result;
-use mbase::models::{dao_app_id::DaoAppId, tx_id::TxId};
+use mbase::models::dao_app_id::DaoAppId;
 use serde::{Deserialize, Serialize};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn team(
-    algod: &Algod,
+    algod: &dyn DaoAlgod,
     sender: &Address,
     app_id: DaoAppId,
     url: &str,
@@ -59,13 +63,13 @@ pub fn team_app_call(
     Ok(tx)
 }
 
-pub async fn submit_team(algod: &Algod, signed: &SetTeamSigned) -> Result<TxId> {
+pub async fn submit_team(algod: &dyn DaoAlgod, signed: &SetTeamSigned) -> Result<Submission> {
     log::debug!("calling submit team..");
 
     let res = algod
         .broadcast_signed_transactions(&[signed.app_call_tx.clone()])
         .await?;
-    res.tx_id.parse()
+    Submission::from_response(&res)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -77,3 +81,19 @@ pub struct SetTeamToSign {
 pub struct SetTeamSigned {
     pub app_call_tx: SignedTransaction,
 }
+
+impl Sign for SetTeamToSign {
+    type Signed = SetTeamSigned;
+
+    fn sign(self, signer: &dyn TxSigner) -> Result<SetTeamSigned> {
+        Ok(SetTeamSigned {
+            app_call_tx: signer.sign_tx(self.app_call_tx)?,
+        })
+    }
+}
+
+impl SignedTxs for SetTeamSigned {
+    fn signed_txs(&self) -> Vec<SignedTransaction> {
+        vec![self.app_call_tx.clone()]
+    }
+}
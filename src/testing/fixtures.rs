@@ -0,0 +1,110 @@
+#[cfg(test)]
+pub use test::{
+    algod_account, dao_fixture, lock_to_sign, FIXTURE_APP_ID, FIXTURE_FUNDS_ASSET_ID,
+    FIXTURE_SHARES_ASSET_ID,
+};
+
+// need wrapper module for auto imports to work https://github.com/rust-analyzer/rust-analyzer/issues/9391
+#[cfg(test)]
+mod test {
+    use crate::{
+        dao_algod::fake::FakeAlgod,
+        flows::{
+            create_dao::model::{Dao, DaoStatus},
+            lock::lock::{lock, LockToSign},
+        },
+    };
+    use algonaut::{
+        core::Address, model::algod::v2::Account as AlgodAccount, transaction::account::Account,
+    };
+    use anyhow::Result;
+    use mbase::models::{
+        dao_app_id::DaoAppId,
+        funds::{FundsAmount, FundsAssetId},
+        share_amount::ShareAmount,
+        timestamp::Timestamp,
+    };
+    use rust_decimal::Decimal;
+    use std::{convert::TryInto, str::FromStr};
+
+    pub const FIXTURE_APP_ID: u64 = 123;
+    pub const FIXTURE_SHARES_ASSET_ID: u64 = 456;
+    pub const FIXTURE_FUNDS_ASSET_ID: u64 = 789;
+
+    /// An active DAO, as loaded from the chain, for the tests that don't use the network
+    pub fn dao_fixture(owner: Address) -> Result<Dao> {
+        Ok(Dao {
+            app_id: DaoAppId(FIXTURE_APP_ID),
+            owner,
+            shares_asset_id: FIXTURE_SHARES_ASSET_ID,
+            funds_asset_id: FundsAssetId(FIXTURE_FUNDS_ASSET_ID),
+            name: "Pancakes ltd".to_owned(),
+            descr_url: None,
+            token_name: "PCK".to_owned(),
+            token_supply: ShareAmount::new(300),
+            token_decimals: 0,
+            investors_share: Decimal::from_str("0.4")?.try_into()?,
+            share_price: FundsAmount::new(5_000_000),
+            image_nft: None,
+            social_media_url: "https://twitter.com/capi_fin".to_owned(),
+            raise_end_date: Timestamp::now(),
+            raise_min_target: FundsAmount::new(0),
+            raised: FundsAmount::new(0),
+            setup_date: Timestamp::now(),
+            prospectus: None,
+            min_invest_amount: ShareAmount::new(0),
+            max_invest_amount: ShareAmount::new(u64::MAX),
+            team_url: None,
+            status: DaoStatus::Active,
+        })
+    }
+
+    /// Locking 10 shares of the fixture DAO, built with the fake algod
+    pub async fn lock_to_sign(investor: &Account) -> Result<LockToSign> {
+        let algod = FakeAlgod::new();
+        lock(
+            &algod,
+            investor.address(),
+            ShareAmount::new(10),
+            FIXTURE_SHARES_ASSET_ID,
+            DaoAppId(FIXTURE_APP_ID),
+        )
+        .await
+    }
+
+    /// An account as returned by algod. `assets`: (asset id, amount)
+    pub fn algod_account(
+        address: &Address,
+        algos: u64,
+        assets: &[(u64, u64)],
+        opted_in_app: Option<u64>,
+    ) -> Result<AlgodAccount> {
+        let assets_json = assets
+            .iter()
+            .map(|(id, amount)| {
+                format!(r#"{{ "amount": {amount}, "asset-id": {id}, "is-frozen": false }}"#)
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        let apps_json = opted_in_app
+            .map(|id| {
+                format!(r#"{{ "id": {id}, "schema": {{ "num-byte-slice": 0, "num-uint": 0 }} }}"#)
+            })
+            .unwrap_or_default();
+
+        Ok(serde_json::from_str(&format!(
+            r#"{{
+                "address": "{address}",
+                "amount": {algos},
+                "amount-without-pending-rewards": {algos},
+                "apps-local-state": [{apps_json}],
+                "assets": [{assets_json}],
+                "pending-rewards": 0,
+                "reward-base": 0,
+                "rewards": 0,
+                "round": 1000,
+                "status": "Offline"
+            }}"#
+        ))?)
+    }
+}
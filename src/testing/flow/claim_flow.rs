@@ -7,7 +7,8 @@ pub mod test {
     use crate::testing::flow::customer_payment_and_drain_flow::CustomerPaymentAndDrainFlowRes;
     use crate::testing::flow::invest_in_dao_flow::invests_optins_flow;
     use crate::{
-        flows::claim::claim::{claim, submit_claim, ClaimSigned},
+        flows::claim::claim::{claim, submit_claim},
+        signer::Sign,
         testing::flow::{
             create_dao_flow::create_dao_flow,
             customer_payment_and_drain_flow::customer_payment_and_drain_flow,
@@ -19,7 +20,6 @@ pub mod test {
     use anyhow::Result;
     use mbase::models::funds::FundsAmount;
     use mbase::models::share_amount::ShareAmount;
-    use mbase::util::network_util::wait_for_pending_transaction;
 
     /// invests and drains
     pub async fn claim_precs(
@@ -85,11 +85,10 @@ pub mod test {
 
         let to_sign = claim(&algod, &claimer.address(), dao.app_id, td.funds_asset_id).await?;
 
-        let app_call_tx_signed = claimer.sign_transaction(to_sign.app_call_tx)?;
-
-        let claim_tx_id = submit_claim(&algod, &ClaimSigned { app_call_tx_signed }).await?;
-
-        wait_for_pending_transaction(&algod, &claim_tx_id).await?;
+        submit_claim(&algod, &to_sign.sign(claimer)?)
+            .await?
+            .wait(algod)
+            .await?;
 
         Ok(ClaimTestFlowRes {
             dao: dao.clone(),
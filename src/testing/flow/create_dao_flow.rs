@@ -2,12 +2,15 @@
 pub use test::create_dao_flow;
 #[cfg(test)]
 pub mod test {
+    use crate::compile_cache::{CompileCache, MemoryCompileStore};
     use crate::flows::create_dao::setup_dao::Programs;
     use crate::flows::create_dao::{
-        model::{Dao, SetupDaoSigned},
-        setup::create_shares::{create_assets, submit_create_assets, CreateDaoAssetsSigned},
+        model::Dao,
+        setup::create_shares::{create_assets, submit_create_assets},
         setup_dao::{setup_dao_txs, submit_setup_dao},
+        shares_config::SharesAssetConfig,
     };
+    use crate::signer::Sign;
     use crate::testing::network_test_util::TestDeps;
     use anyhow::Result;
     use mbase::api::version::{Version, VersionedTealSourceTemplate};
@@ -15,6 +18,13 @@ pub mod test {
     use mbase::teal::load_teal_template;
 
     pub async fn create_dao_flow(td: &TestDeps) -> Result<Dao> {
+        create_dao_flow_with_shares_config(td, &SharesAssetConfig::default()).await
+    }
+
+    pub async fn create_dao_flow_with_shares_config(
+        td: &TestDeps,
+        shares_config: &SharesAssetConfig,
+    ) -> Result<Dao> {
         let algod = &td.algod;
 
         // Create asset first: id needed in app template
@@ -22,32 +32,20 @@ pub mod test {
             &algod,
             &td.creator.address(),
             &td.specs,
+            shares_config,
             &td.programs.central_app_approval,
             &td.programs.central_app_clear,
             td.precision,
             &td.dao_deps(),
             FundsAmount::new(5_000_000_000_000),
+            &CompileCache::new(MemoryCompileStore::default()),
         )
         .await?;
 
-        let signed_create_shares_tx = td
-            .creator
-            .sign_transaction(create_assets_txs.create_shares_tx)?;
-        let signed_create_app_tx = td
-            .creator
-            .sign_transaction(create_assets_txs.create_app_tx)?;
-
-        let create_assets_res = submit_create_assets(
-            algod,
-            &CreateDaoAssetsSigned {
-                create_shares: signed_create_shares_tx,
-                create_app: signed_create_app_tx,
-            },
-        )
-        .await?;
-
-        let image_url = td.specs.image_url.clone();
-        let prospectus = td.specs.prospectus.clone();
+        let create_assets_res = submit_create_assets(algod, &create_assets_txs.sign(&td.creator)?)
+            .await?
+            .wait(algod)
+            .await?;
 
         // Rest of create dao txs
         let to_sign = setup_dao_txs(
@@ -59,37 +57,19 @@ pub mod test {
             &td.programs,
             td.precision,
             create_assets_res.app_id,
-            image_url.clone(),
+            td.specs.image_url.clone(),
+            None,
             td.specs.prospectus.clone(),
         )
         .await?;
 
-        let signed_fund_app_tx = td.creator.sign_transaction(to_sign.fund_app_tx)?;
-        let signed_setup_app_tx = td.creator.sign_transaction(to_sign.setup_app_tx)?;
-        let signed_transfer_shares_to_app_tx = td
-            .creator
-            .sign_transaction(to_sign.transfer_shares_to_app_tx)?;
-
         // Create the asset (submit signed tx) and generate escrow funding tx
         // Note that the escrow is generated after the asset, because it uses the asset id (in teal, inserted with template)
 
-        let create_res = submit_setup_dao(
-            &algod,
-            SetupDaoSigned {
-                specs: to_sign.specs,
-                creator: td.creator.address(),
-                shares_asset_id: create_assets_res.shares_asset_id,
-                funds_asset_id: td.funds_asset_id.clone(),
-                app_funding_tx: signed_fund_app_tx,
-                setup_app_tx: signed_setup_app_tx,
-                app_id: create_assets_res.app_id,
-                transfer_shares_to_app_tx: signed_transfer_shares_to_app_tx,
-                image_url,
-                setup_date: to_sign.setup_date,
-                prospectus,
-            },
-        )
-        .await?;
+        let create_res = submit_setup_dao(&algod, to_sign.sign(&td.creator)?)
+            .await?
+            .wait(&algod)
+            .await?;
 
         log::debug!("Created dao: {:?}", create_res.dao);
 
@@ -5,10 +5,10 @@ pub use test::{customer_payment_and_drain_flow, drain_flow, CustomerPaymentAndDr
 pub mod test {
     use crate::{
         flows::create_dao::model::Dao,
-        flows::drain::drain::{
-            drain, submit_drain, to_drain_amounts, DaoAndCapiDrainAmounts, DrainSigned,
-        },
-        flows::pay_dao::pay_dao::{pay_dao_app, submit_pay_dao, PayDaoSigned},
+        flows::drain::drain::{drain, submit_drain, to_drain_amounts, DaoAndCapiDrainAmounts},
+        flows::pay_dao::pay_dao::{pay_dao_app, submit_pay_dao},
+        signer::Sign,
+        submission::Submission,
         testing::network_test_util::TestDeps,
     };
     use algonaut::{
@@ -17,13 +17,9 @@ pub mod test {
         transaction::{account::Account, Transaction},
     };
     use anyhow::Result;
-    use mbase::{
-        models::{
-            dao_app_id::DaoAppId,
-            funds::{FundsAmount, FundsAssetId},
-            tx_id::TxId,
-        },
-        util::network_util::wait_for_pending_transaction,
+    use mbase::models::{
+        dao_app_id::DaoAppId,
+        funds::{FundsAmount, FundsAssetId},
     };
 
     pub async fn customer_payment_and_drain_flow(
@@ -35,15 +31,16 @@ pub mod test {
         let algod = &td.algod;
 
         // Customer sends a payment
-        let customer_payment_tx_id = send_payment_to_app(
+        send_payment_to_app(
             algod,
             &td.customer,
             dao.app_id,
             td.funds_asset_id,
             customer_payment_amount,
         )
+        .await?
+        .wait(algod)
         .await?;
-        wait_for_pending_transaction(&algod, &customer_payment_tx_id).await?;
 
         drain_flow(td, &drainer, dao).await
     }
@@ -75,22 +72,17 @@ pub mod test {
         )
         .await?;
 
-        let app_call_tx_signed = drainer.sign_transaction(drain_to_sign.app_call_tx)?;
+        let drain_signed = drain_to_sign.sign(drainer)?;
 
-        let drain_tx_id = submit_drain(
-            &algod,
-            &DrainSigned {
-                app_call_tx_signed: app_call_tx_signed.clone(),
-            },
-        )
-        .await?;
-
-        wait_for_pending_transaction(&algod, &drain_tx_id).await?;
+        submit_drain(&algod, &drain_signed)
+            .await?
+            .wait(algod)
+            .await?;
 
         Ok(CustomerPaymentAndDrainFlowRes {
             dao: dao.to_owned(),
             initial_drainer_balance,
-            app_call_tx: app_call_tx_signed.transaction,
+            app_call_tx: drain_signed.app_call_tx_signed.transaction,
             drained_amounts: drain_amounts,
         })
     }
@@ -111,13 +103,11 @@ pub mod test {
         app_id: DaoAppId,
         funds_asset_id: FundsAssetId,
         amount: FundsAmount,
-    ) -> Result<TxId> {
-        let tx = pay_dao_app(algod, &customer.address(), app_id, funds_asset_id, amount)
-            .await?
-            .tx;
-        let signed_tx = customer.sign_transaction(tx)?;
-        let tx_id = submit_pay_dao(algod, PayDaoSigned { tx: signed_tx }).await?;
-        log::debug!("Customer payment tx id: {:?}", tx_id);
-        Ok(tx_id)
+    ) -> Result<Submission> {
+        let to_sign =
+            pay_dao_app(algod, &customer.address(), app_id, funds_asset_id, amount).await?;
+        let submission = submit_pay_dao(algod, to_sign.sign(customer)?).await?;
+        log::debug!("Customer payment tx id: {:?}", submission.tx_id);
+        Ok(submission)
     }
 }
@@ -8,12 +8,10 @@ pub mod test {
     };
     use crate::flows::{
         create_dao::model::Dao,
+        invest::invest::{invest_txs, submit_invest},
         invest::model::InvestResult,
-        invest::{
-            invest::{invest_txs, submit_invest},
-            model::InvestSigned,
-        },
     };
+    use crate::signer::Sign;
     use crate::state::account_state::funds_holdings;
     use crate::testing::network_test_util::TestDeps;
     use algonaut::{algod::v2::Algod, transaction::account::Account};
@@ -31,9 +29,10 @@ pub mod test {
 
         let app_optin_signed_tx = investor.sign_transaction(app_optin_tx)?;
 
-        let app_optin_tx_id =
-            submit_invest_or_locking_app_optin(algod, app_optin_signed_tx.clone()).await?;
-        wait_for_pending_transaction(&algod, &app_optin_tx_id).await?;
+        submit_invest_or_locking_app_optin(algod, app_optin_signed_tx.clone())
+            .await?
+            .wait(algod)
+            .await?;
 
         Ok(())
     }
@@ -72,20 +71,7 @@ pub mod test {
         )
         .await?;
 
-        let signed_central_app_setup_tx = investor.sign_transaction(to_sign.app_call)?;
-        let signed_shares_optin_tx = investor.sign_transaction(to_sign.shares_asset_optin_tx)?;
-        let signed_payment_tx = investor.sign_transaction(to_sign.payment_tx)?;
-
-        let invest_res = submit_invest(
-            &algod,
-            &InvestSigned {
-                dao: to_sign.dao,
-                central_app_setup_tx: signed_central_app_setup_tx,
-                shares_asset_optin_tx: signed_shares_optin_tx,
-                payment_tx: signed_payment_tx,
-            },
-        )
-        .await?;
+        let invest_res = submit_invest(&algod, &to_sign.sign(investor)?).await?;
 
         // wait for tx to go through (so everything is on chain when returning to caller, e.g. to test)
         // TODO (low prio) should be in the tests rather?
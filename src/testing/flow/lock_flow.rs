@@ -4,11 +4,11 @@ pub use test::lock_flow;
 #[cfg(test)]
 pub mod test {
     use crate::flows::create_dao::model::Dao;
-    use crate::flows::lock::lock::{lock, submit_lock, LockSigned};
+    use crate::flows::lock::lock::{lock, submit_lock};
+    use crate::signer::Sign;
     use algonaut::{algod::v2::Algod, transaction::account::Account};
     use anyhow::Result;
     use mbase::models::share_amount::ShareAmount;
-    use mbase::util::network_util::wait_for_pending_transaction;
 
     pub async fn lock_flow(
         algod: &Algod,
@@ -25,19 +25,10 @@ pub mod test {
         )
         .await?;
 
-        let signed_app_call_tx =
-            investor.sign_transaction(lock_to_sign.central_app_call_setup_tx)?;
-
-        let signed_shares_xfer_tx = investor.sign_transaction(lock_to_sign.shares_xfer_tx)?;
-        let tx_id = submit_lock(
-            algod,
-            LockSigned {
-                central_app_call_setup_tx: signed_app_call_tx,
-                shares_xfer_tx_signed: signed_shares_xfer_tx,
-            },
-        )
-        .await?;
-        let _ = wait_for_pending_transaction(&algod, &tx_id);
+        submit_lock(algod, lock_to_sign.sign(investor)?)
+            .await?
+            .wait(algod)
+            .await?;
 
         Ok(())
     }
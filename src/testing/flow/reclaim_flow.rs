@@ -1,12 +1,12 @@
 #[cfg(test)]
 pub mod test {
     use crate::flows::create_dao::model::Dao;
-    use crate::flows::reclaim::reclaim::{reclaim, submit_reclaim, ReclaimSigned};
+    use crate::flows::reclaim::reclaim::{reclaim, submit_reclaim};
+    use crate::signer::Sign;
     use crate::testing::network_test_util::TestDeps;
     use algonaut::transaction::account::Account;
     use anyhow::Result;
     use mbase::models::share_amount::ShareAmount;
-    use mbase::util::network_util::wait_for_pending_transaction;
 
     pub async fn reclaim_flow(
         td: &TestDeps,
@@ -30,19 +30,10 @@ pub mod test {
         )
         .await?;
 
-        let app_call_tx_signed = reclaimer.sign_transaction(to_sign.app_call_tx)?;
-        let shares_xfer_tx_signed = reclaimer.sign_transaction(to_sign.shares_xfer_tx)?;
-
-        let claim_tx_id = submit_reclaim(
-            &algod,
-            &ReclaimSigned {
-                app_call_tx_signed,
-                shares_xfer_tx_signed,
-            },
-        )
-        .await?;
-
-        wait_for_pending_transaction(&algod, &claim_tx_id).await?;
+        submit_reclaim(&algod, &to_sign.sign(reclaimer)?)
+            .await?
+            .wait(algod)
+            .await?;
 
         Ok(())
     }
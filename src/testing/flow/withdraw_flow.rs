@@ -10,13 +10,13 @@ pub mod test {
             create_dao::model::Dao,
             withdraw::withdraw::{submit_withdraw, withdraw, WithdrawSigned, WithdrawalInputs},
         },
+        signer::Sign,
         testing::flow::customer_payment_and_drain_flow::CustomerPaymentAndDrainFlowRes,
     };
     use algonaut::{algod::v2::Algod, core::MicroAlgos, transaction::account::Account};
     use anyhow::Result;
     use mbase::models::dao_app_id::DaoAppId;
     use mbase::models::funds::{FundsAmount, FundsAssetId};
-    use mbase::util::network_util::wait_for_pending_transaction;
     use network_test_util::tests_msig::TestsMsig;
 
     /// dao creation,
@@ -69,16 +69,10 @@ pub mod test {
         )
         .await?;
 
-        let withdraw_signed = withdrawer.sign_transaction(to_sign.withdraw_tx)?;
-
-        let withdraw_tx_id = submit_withdraw(
-            &algod,
-            &WithdrawSigned {
-                withdraw_tx: withdraw_signed,
-            },
-        )
-        .await?;
-        wait_for_pending_transaction(&algod, &withdraw_tx_id).await?;
+        submit_withdraw(&algod, &to_sign.sign(withdrawer)?)
+            .await?
+            .wait(algod)
+            .await?;
 
         Ok(WithdrawTestFlowRes {
             dao: dao.clone(),
@@ -109,14 +103,15 @@ pub mod test {
 
         let withdraw_signed = withdrawer.sign(to_sign.withdraw_tx)?;
 
-        let withdraw_tx_id = submit_withdraw(
+        submit_withdraw(
             &algod,
             &WithdrawSigned {
                 withdraw_tx: withdraw_signed,
             },
         )
+        .await?
+        .wait(algod)
         .await?;
-        wait_for_pending_transaction(&algod, &withdraw_tx_id).await?;
 
         Ok(())
     }
@@ -143,14 +138,15 @@ pub mod test {
 
         let withdraw_signed = withdrawer.sign_incomplete(to_sign.withdraw_tx)?;
 
-        let withdraw_tx_id = submit_withdraw(
+        submit_withdraw(
             &algod,
             &WithdrawSigned {
                 withdraw_tx: withdraw_signed,
             },
         )
+        .await?
+        .wait(algod)
         .await?;
-        wait_for_pending_transaction(&algod, &withdraw_tx_id).await?;
 
         Ok(())
     }
@@ -0,0 +1,39 @@
+use crate::teal::TealApi;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use mbase::{
+    api::{
+        contract::Contract,
+        version::{Version, VersionedTealSourceTemplate, Versions},
+    },
+    teal::load_teal_template,
+};
+
+/// Serves the local templates, as the last versions
+pub struct LocalTealApi;
+
+#[async_trait]
+impl TealApi for LocalTealApi {
+    async fn last_versions(&self) -> Result<Versions> {
+        Ok(Versions {
+            app_approval: Version(1),
+            app_clear: Version(1),
+        })
+    }
+
+    async fn template(
+        &self,
+        contract: Contract,
+        version: Version,
+    ) -> Result<VersionedTealSourceTemplate> {
+        let name = match contract {
+            Contract::DaoAppApproval => "dao_app_approval",
+            Contract::DaoAppClear => "dao_app_clear",
+            Contract::DaoCustomer => return Err(anyhow!("Not supported: {contract:?}")),
+        };
+        Ok(VersionedTealSourceTemplate::new(
+            load_teal_template(name)?,
+            version,
+        ))
+    }
+}
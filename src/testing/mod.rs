@@ -2,8 +2,11 @@
 pub mod algorand_checks;
 pub mod create_and_submit_txs;
 pub mod dao_general;
+pub mod fixtures;
 pub mod flow;
 pub mod generate_mnemonic;
+#[cfg(test)]
+pub mod local_teal_api;
 pub mod network_test_util;
 pub mod wasm;
 
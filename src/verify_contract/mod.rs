@@ -0,0 +1,313 @@
+use crate::{
+    capi_error::program::{constants, ProgramConstants},
+    compile_cache::CompileCache,
+    dao_algod::DaoAlgod,
+    flows::create_dao::storage::load_dao::load_dao,
+    state::dao_app_state::dao_global_state,
+    teal::TealApi,
+    teal_registry::{
+        dao_teal_registry, render_approval_values, ApprovalTemplateParams, TealRegistry,
+        TemplateParam, TemplateValue,
+    },
+};
+use anyhow::{anyhow, Result};
+use mbase::{
+    api::version::{Version, VersionedTealSourceTemplate},
+    models::{
+        capi_deps::{CapiAddress, CapiAssetDaoDeps},
+        dao_id::DaoId,
+        funds::FundsAmount,
+    },
+};
+
+mod tests;
+
+/// Result of comparing the DAO app's programs with the ones rendered from the Capi templates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractVerification {
+    pub approval_version: Version,
+    pub clear_version: Version,
+    pub approval_matches: bool,
+    pub clear_matches: bool,
+    /// Not saved in the DAO: recovered from the deployed approval
+    pub precision: u64,
+    pub max_raisable_amount: FundsAmount,
+    /// The expected value of each template parameter, and whether the deployed approval contains it
+    pub params: Vec<ParamCheck>,
+    /// Constants of the deployed approval that the expected one doesn't contain:
+    /// normally the values the mismatched parameters were rendered with.
+    pub unexpected_ints: Vec<u64>,
+    pub unexpected_bytes: Vec<Vec<u8>>,
+}
+
+impl ContractVerification {
+    /// Whether the app runs the Capi programs, rendered with the DAO's parameters
+    pub fn is_genuine(&self) -> bool {
+        self.approval_matches && self.clear_matches
+    }
+
+    /// Parameters whose expected value isn't in the deployed approval
+    pub fn mismatched_params(&self) -> Vec<&ParamCheck> {
+        self.params.iter().filter(|p| !p.found).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamCheck {
+    pub param: TemplateParam,
+    pub expected: TemplateValue,
+    /// Whether the value is a constant of the deployed approval.
+    /// Small ints can be there for other reasons: only `false` proves a mismatch.
+    pub found: bool,
+}
+
+/// The approval's values derived from the precision and the DAO's data.
+/// Not the capi share: it depends on the passed capi deps, which are verified too.
+const PRECISION_PARAMS: &[TemplateParam] = &[
+    TemplateParam::Precision,
+    TemplateParam::PrecisionSquare,
+    TemplateParam::InvestorsShare,
+];
+
+/// Max int constants tried as max raisable amount, when it's not the only one not explained by the rest of the params
+const MAX_RAISABLE_AMOUNT_CANDIDATES: usize = 5;
+
+/// Checks that the DAO's app runs the genuine approval and clear programs,
+/// of the versions recorded in its global state.
+/// `capi_deps` aren't stored in the DAO: they're the ones the platform creates DAOs with.
+/// The precision and max raisable amount (chosen per DAO when creating it) are recovered from the deployed approval's constants.
+/// The programs of the same version and parameters are compiled once per `compile_cache`.
+pub async fn verify_dao_contract(
+    algod: &dyn DaoAlgod,
+    teal_api: &dyn TealApi,
+    dao_id: DaoId,
+    capi_deps: &CapiAssetDaoDeps,
+    compile_cache: &CompileCache,
+) -> Result<ContractVerification> {
+    let dao = load_dao(algod, dao_id).await?;
+    let dao_state = dao_global_state(algod, dao.app_id).await?;
+    let app = algod.application_information(dao.app_id.0).await?;
+
+    let approval_version = dao_state.app_approval_version;
+    let clear_version = dao_state.app_clear_version;
+
+    let registry = dao_teal_registry();
+    let approval_template = registry.load_approval(teal_api, approval_version).await?;
+    let clear_template = registry.load_clear(teal_api, clear_version).await?;
+
+    let deployed_approval = &app.params.approval_program;
+    let deployed_constants = constants(deployed_approval)?;
+
+    let mut params = ApprovalTemplateParams {
+        share_supply: dao.token_supply,
+        precision: 0,
+        investors_share: dao.investors_share,
+        capi_address: CapiAddress(capi_deps.address.0),
+        capi_percentage: capi_deps.escrow_percentage,
+        share_price: dao.share_price,
+        max_raisable_amount: FundsAmount::new(0),
+    };
+    params.precision =
+        deployed_precision(&registry, approval_version, &params, &deployed_constants)?;
+    params.max_raisable_amount = deployed_max_raisable_amount(
+        algod,
+        &registry,
+        &approval_template,
+        &params,
+        deployed_approval,
+        compile_cache,
+    )
+    .await?;
+
+    let values = registry.approval_values(approval_version, &params)?;
+    let approval_source = render_approval_values(&approval_template.template, &values)?;
+    let expected_approval = compile_cache.compile(algod, &approval_source.0).await?;
+
+    let clear_source = registry.render_clear(&clear_template)?;
+    let expected_clear = compile_cache.compile(algod, &clear_source.0).await?;
+
+    let verification = compare(
+        approval_version,
+        clear_version,
+        &expected_approval.0,
+        deployed_approval,
+        &expected_clear.0,
+        &app.params.clear_state_program,
+        values,
+    )?;
+    Ok(ContractVerification {
+        precision: params.precision,
+        max_raisable_amount: params.max_raisable_amount,
+        ..verification
+    })
+}
+
+/// The precision the approval was rendered with: the largest int constant whose derived values are constants too.
+/// 0 if the version doesn't use a precision.
+fn deployed_precision(
+    registry: &TealRegistry,
+    version: Version,
+    params: &ApprovalTemplateParams,
+    deployed: &ProgramConstants,
+) -> Result<u64> {
+    if !registry
+        .approval_params(version)?
+        .contains(&TemplateParam::Precision)
+    {
+        return Ok(0);
+    }
+
+    let mut candidates: Vec<u64> = deployed.ints.iter().cloned().filter(|i| *i > 1).collect();
+    candidates.sort_unstable_by(|a, b| b.cmp(a));
+    candidates.dedup();
+    for precision in candidates {
+        let values = match registry.approval_values(
+            version,
+            &ApprovalTemplateParams {
+                precision,
+                capi_address: CapiAddress(params.capi_address.0),
+                ..*params
+            },
+        ) {
+            Ok(values) => values,
+            // e.g. the square overflows
+            Err(_) => continue,
+        };
+        let derived_found = values
+            .iter()
+            .filter(|(param, _)| PRECISION_PARAMS.contains(param))
+            .all(|(_, value)| contains_value(deployed, value));
+        if derived_found {
+            return Ok(precision);
+        }
+    }
+    Err(anyhow!(
+        "No precision found in the deployed approval: it's not a Capi approval"
+    ))
+}
+
+/// The max raisable amount the approval was rendered with: the int constant that,
+/// rendered with the rest of `params`, produces the deployed approval.
+/// If none does (the approval isn't genuine), the first int constant not explained by the rest of `params`.
+/// Compiles (with `compile_cache`) at most [MAX_RAISABLE_AMOUNT_CANDIDATES] + 1 approvals.
+async fn deployed_max_raisable_amount(
+    algod: &dyn DaoAlgod,
+    registry: &TealRegistry,
+    template: &VersionedTealSourceTemplate,
+    params: &ApprovalTemplateParams,
+    deployed_approval: &[u8],
+    compile_cache: &CompileCache,
+) -> Result<FundsAmount> {
+    let compile = |max_raisable_amount: u64| async move {
+        let source = registry.render_approval(
+            template,
+            &ApprovalTemplateParams {
+                capi_address: CapiAddress(params.capi_address.0),
+                max_raisable_amount: FundsAmount::new(max_raisable_amount),
+                ..*params
+            },
+        )?;
+        compile_cache.compile(algod, &source.0).await
+    };
+
+    let deployed = constants(deployed_approval)?;
+    // a value the deployed approval doesn't contain, so all its constants are explained by the rest of the params
+    let placeholder = (0..=u64::MAX)
+        .rev()
+        .find(|i| !deployed.ints.contains(i))
+        .ok_or_else(|| anyhow!("Unexpected: no free int"))?;
+    let with_placeholder = constants(&compile(placeholder).await?.0)?;
+    let unexpected: Vec<u64> = deployed
+        .ints
+        .iter()
+        .cloned()
+        .filter(|i| !with_placeholder.ints.contains(i))
+        .collect();
+
+    // rendered with the rest of the params, the amount is the only int that can differ: more show that the approval isn't genuine
+    if unexpected.len() > 1 {
+        return Ok(FundsAmount::new(unexpected[0]));
+    }
+    // if none differs, the amount is also one of the other constants: normally the largest (the small ints are the program's)
+    let candidates = if unexpected.is_empty() {
+        let mut ints = deployed.ints.clone();
+        ints.sort_unstable_by(|a, b| b.cmp(a));
+        ints.dedup();
+        ints.truncate(MAX_RAISABLE_AMOUNT_CANDIDATES);
+        ints
+    } else {
+        unexpected.clone()
+    };
+    for candidate in candidates {
+        if compile(candidate).await?.0 == deployed_approval {
+            return Ok(FundsAmount::new(candidate));
+        }
+    }
+    unexpected
+        .first()
+        .map(|amount| FundsAmount::new(*amount))
+        .ok_or_else(|| anyhow!("No max raisable amount found in the deployed approval"))
+}
+
+fn compare(
+    approval_version: Version,
+    clear_version: Version,
+    expected_approval: &[u8],
+    deployed_approval: &[u8],
+    expected_clear: &[u8],
+    deployed_clear: &[u8],
+    values: Vec<(TemplateParam, TemplateValue)>,
+) -> Result<ContractVerification> {
+    let expected_constants = constants(expected_approval)?;
+    let deployed_constants = constants(deployed_approval)?;
+
+    let params = values
+        .into_iter()
+        .map(|(param, expected)| {
+            let found = contains_value(&deployed_constants, &expected);
+            ParamCheck {
+                param,
+                expected,
+                found,
+            }
+        })
+        .collect();
+
+    let mut unexpected_ints: Vec<u64> = deployed_constants
+        .ints
+        .into_iter()
+        .filter(|i| !expected_constants.ints.contains(i))
+        .collect();
+    unexpected_ints.sort_unstable();
+    unexpected_ints.dedup();
+    let mut unexpected_bytes: Vec<Vec<u8>> = deployed_constants
+        .bytes
+        .into_iter()
+        .filter(|b| !expected_constants.bytes.contains(b))
+        .collect();
+    unexpected_bytes.sort_unstable();
+    unexpected_bytes.dedup();
+
+    Ok(ContractVerification {
+        approval_version,
+        clear_version,
+        approval_matches: expected_approval == deployed_approval,
+        clear_matches: expected_clear == deployed_clear,
+        precision: 0,
+        max_raisable_amount: FundsAmount::new(0),
+        params,
+        unexpected_ints,
+        unexpected_bytes,
+    })
+}
+
+fn contains_value(constants: &ProgramConstants, value: &TemplateValue) -> bool {
+    match value {
+        TemplateValue::Int(int) => constants.ints.contains(int),
+        // addresses are rendered as `addr` constants: the public key bytes
+        TemplateValue::Address(address) => constants
+            .bytes
+            .iter()
+            .any(|b| b.as_slice() == address.0.as_slice()),
+    }
+}
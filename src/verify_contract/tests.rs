@@ -0,0 +1,201 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        capi_error::program::constants,
+        compile_cache::{CompileCache, MemoryCompileStore},
+        teal_registry::{dao_teal_registry, ApprovalTemplateParams, TemplateParam, TemplateValue},
+        testing::{
+            flow::create_dao_flow::create_dao_flow, local_teal_api::LocalTealApi,
+            network_test_util::test_dao_init,
+        },
+        verify_contract::{compare, deployed_precision, verify_dao_contract},
+    };
+    use anyhow::Result;
+    use mbase::{
+        api::version::Version,
+        models::{
+            capi_deps::{CapiAddress, CapiAssetDaoDeps},
+            funds::FundsAmount,
+            share_amount::ShareAmount,
+        },
+    };
+    use network_test_util::test_data::investor1;
+    use rust_decimal::Decimal;
+    use serial_test::serial;
+    use std::{convert::TryInto, str::FromStr};
+    use tokio::test;
+
+    /// intcblock with 2 ints (varuint encoded), bytecblock with the address
+    fn program(ints: &[u8], address: &[u8]) -> Vec<u8> {
+        let mut program = vec![0x06, 0x20, 0x02];
+        program.extend_from_slice(ints);
+        program.extend_from_slice(&[0x26, 0x01, 0x20]);
+        program.extend_from_slice(address);
+        program
+    }
+
+    fn values() -> Vec<(TemplateParam, TemplateValue)> {
+        vec![
+            (TemplateParam::ShareSupply, TemplateValue::Int(100)),
+            (TemplateParam::SharePrice, TemplateValue::Int(1000)),
+            (
+                TemplateParam::CapiEscrowAddress,
+                TemplateValue::Address(investor1().address()),
+            ),
+        ]
+    }
+
+    #[test]
+    async fn test_compare_same_programs() -> Result<()> {
+        let address = investor1().address();
+        let expected = program(&[0x64, 0xe8, 0x07], &address.0);
+
+        let verification = compare(
+            Version(1),
+            Version(1),
+            &expected,
+            &expected,
+            &[0x06, 0x81, 0x01],
+            &[0x06, 0x81, 0x01],
+            values(),
+        )?;
+
+        assert!(verification.is_genuine());
+        assert!(verification.mismatched_params().is_empty());
+        assert!(verification.unexpected_ints.is_empty());
+        assert!(verification.unexpected_bytes.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    async fn test_compare_reports_mismatched_param() -> Result<()> {
+        let address = investor1().address();
+        let expected = program(&[0x64, 0xe8, 0x07], &address.0);
+        // share price 2000 instead of 1000
+        let deployed = program(&[0x64, 0xd0, 0x0f], &address.0);
+
+        let verification = compare(
+            Version(1),
+            Version(1),
+            &expected,
+            &deployed,
+            &[0x06, 0x81, 0x01],
+            &[0x06, 0x81, 0x01],
+            values(),
+        )?;
+
+        assert!(!verification.is_genuine());
+        assert!(!verification.approval_matches);
+        assert!(verification.clear_matches);
+        assert_eq!(
+            vec![TemplateParam::SharePrice],
+            verification
+                .mismatched_params()
+                .iter()
+                .map(|p| p.param)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(vec![2000], verification.unexpected_ints);
+        Ok(())
+    }
+
+    /// Investors share 0.4, the precision to be recovered
+    fn template_params() -> Result<ApprovalTemplateParams> {
+        Ok(ApprovalTemplateParams {
+            share_supply: ShareAmount::new(100),
+            precision: 0,
+            investors_share: Decimal::from_str("0.4")?.try_into()?,
+            capi_address: CapiAddress(investor1().address()),
+            capi_percentage: Decimal::from_str("0.1")?.try_into()?,
+            share_price: FundsAmount::new(1000),
+            max_raisable_amount: FundsAmount::new(0),
+        })
+    }
+
+    #[test]
+    async fn test_deployed_precision() -> Result<()> {
+        // intcblock: 1, 1000 (precision), 1000000 (its square), 400 (investors share)
+        let program = [
+            0x06, 0x20, 0x04, 0x01, 0xe8, 0x07, 0xc0, 0x84, 0x3d, 0x90, 0x03,
+        ];
+
+        let precision = deployed_precision(
+            &dao_teal_registry(),
+            Version(1),
+            &template_params()?,
+            &constants(&program)?,
+        )?;
+
+        assert_eq!(1000, precision);
+        Ok(())
+    }
+
+    #[test]
+    async fn test_deployed_precision_not_found() -> Result<()> {
+        // intcblock: 1, 1000 (without its square)
+        let program = [0x06, 0x20, 0x02, 0x01, 0xe8, 0x07];
+
+        assert!(deployed_precision(
+            &dao_teal_registry(),
+            Version(1),
+            &template_params()?,
+            &constants(&program)?
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    async fn test_verify_genuine_dao() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let dao = create_dao_flow(td).await?;
+
+        let verification = verify_dao_contract(
+            &td.algod,
+            &LocalTealApi,
+            dao.id(),
+            &td.dao_deps(),
+            &CompileCache::new(MemoryCompileStore::default()),
+        )
+        .await?;
+
+        assert!(verification.is_genuine());
+        assert!(verification.mismatched_params().is_empty());
+        // the ones create_dao_flow uses
+        assert_eq!(td.precision, verification.precision);
+        assert_eq!(
+            FundsAmount::new(5_000_000_000_000),
+            verification.max_raisable_amount
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    async fn test_verify_dao_with_other_params() -> Result<()> {
+        let td = &test_dao_init().await?;
+        let dao = create_dao_flow(td).await?;
+
+        // verify against a different capi share than the dao was created with
+        let capi_deps = CapiAssetDaoDeps {
+            escrow_percentage: Decimal::from_str("0.5")?.try_into()?,
+            address: td.dao_deps().address,
+        };
+        let verification = verify_dao_contract(
+            &td.algod,
+            &LocalTealApi,
+            dao.id(),
+            &capi_deps,
+            &CompileCache::new(MemoryCompileStore::default()),
+        )
+        .await?;
+
+        assert!(!verification.is_genuine());
+        assert!(verification
+            .mismatched_params()
+            .iter()
+            .any(|p| p.param == TemplateParam::CapiShare));
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use crate::signer::{group_txs, Sign, SignedTxs};
+use algonaut::transaction::{transaction::TransactionType, Transaction};
+use anyhow::Result;
+
+mod tests;
+
+/// A difference between what we generated and what was signed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Discrepancy {
+    /// Position in the group. `None` for group level discrepancies (the tx count).
+    pub tx_index: Option<usize>,
+    pub field: TxField,
+    pub expected: String,
+    pub actual: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxField {
+    TxCount,
+    /// E.g. a payment signed as asset transfer
+    Type,
+    Sender,
+    Receiver,
+    Amount,
+    AssetId,
+    AppId,
+    AppArgs,
+    Fee,
+    GroupId,
+    RekeyTo,
+    CloseTo,
+    /// A field not compared individually (e.g. the note or validity rounds): the tx ids differ
+    Other,
+}
+
+/// Compares the signed transactions (e.g. returned by a wallet) with the ones generated by the flow.
+/// An empty list means that exactly what was generated was signed.
+pub fn verify_signed<T>(to_sign: &T, signed: &T::Signed) -> Result<Vec<Discrepancy>>
+where
+    T: Sign + Clone,
+    T::Signed: SignedTxs,
+{
+    let expected_txs = group_txs(to_sign)?;
+    let signed_txs = signed.signed_txs();
+
+    if expected_txs.len() != signed_txs.len() {
+        return Ok(vec![Discrepancy {
+            tx_index: None,
+            field: TxField::TxCount,
+            expected: expected_txs.len().to_string(),
+            actual: signed_txs.len().to_string(),
+        }]);
+    }
+
+    let mut discrepancies = vec![];
+    for (index, (expected, signed)) in expected_txs.iter().zip(signed_txs.iter()).enumerate() {
+        discrepancies.extend(tx_discrepancies(index, expected, &signed.transaction)?);
+    }
+    Ok(discrepancies)
+}
+
+fn tx_discrepancies(
+    index: usize,
+    expected: &Transaction,
+    actual: &Transaction,
+) -> Result<Vec<Discrepancy>> {
+    let expected_fields = fields(expected);
+    let actual_fields = fields(actual);
+
+    let mut discrepancies: Vec<Discrepancy> = expected_fields
+        .iter()
+        .zip(actual_fields.iter())
+        .filter(|(e, a)| e.1 != a.1)
+        .map(|(e, a)| Discrepancy {
+            tx_index: Some(index),
+            field: e.0,
+            expected: e.1.clone(),
+            actual: a.1.clone(),
+        })
+        .collect();
+
+    if discrepancies.is_empty() && expected.id()? != actual.id()? {
+        discrepancies.push(Discrepancy {
+            tx_index: Some(index),
+            field: TxField::Other,
+            expected: format!("{expected:?}"),
+            actual: format!("{actual:?}"),
+        });
+    }
+
+    Ok(discrepancies)
+}
+
+/// The compared fields, with values formatted to be displayed. Fields that don't apply to the tx type are empty.
+fn fields(tx: &Transaction) -> Vec<(TxField, String)> {
+    let type_fields = type_fields(&tx.txn_type);
+    vec![
+        (TxField::Type, type_fields.type_name.to_owned()),
+        (TxField::Sender, tx.sender().to_string()),
+        (TxField::Receiver, opt_to_string(type_fields.receiver)),
+        (TxField::Amount, opt_to_string(type_fields.amount)),
+        (TxField::AssetId, opt_to_string(type_fields.asset_id)),
+        (TxField::AppId, opt_to_string(type_fields.app_id)),
+        (TxField::AppArgs, format!("{:?}", type_fields.app_args)),
+        (TxField::Fee, tx.fee.0.to_string()),
+        (TxField::GroupId, format!("{:?}", tx.group)),
+        (TxField::RekeyTo, opt_to_string(tx.rekey_to)),
+        (TxField::CloseTo, opt_to_string(type_fields.close_to)),
+    ]
+}
+
+#[derive(Default)]
+struct TypeFields {
+    type_name: &'static str,
+    receiver: Option<String>,
+    amount: Option<u64>,
+    asset_id: Option<u64>,
+    app_id: Option<u64>,
+    app_args: Vec<Vec<u8>>,
+    close_to: Option<String>,
+}
+
+fn type_fields(txn_type: &TransactionType) -> TypeFields {
+    match txn_type {
+        TransactionType::Payment(p) => TypeFields {
+            type_name: "pay",
+            receiver: Some(p.receiver.to_string()),
+            amount: Some(p.amount.0),
+            close_to: p.close_remainder_to.map(|a| a.to_string()),
+            ..TypeFields::default()
+        },
+        TransactionType::AssetTransferTransaction(t) => TypeFields {
+            type_name: "axfer",
+            receiver: Some(t.receiver.to_string()),
+            amount: Some(t.amount),
+            asset_id: Some(t.xfer),
+            close_to: t.close_to.map(|a| a.to_string()),
+            ..TypeFields::default()
+        },
+        TransactionType::AssetAcceptTransaction(t) => TypeFields {
+            type_name: "axfer (opt-in)",
+            asset_id: Some(t.xfer),
+            ..TypeFields::default()
+        },
+        TransactionType::ApplicationCallTransaction(c) => TypeFields {
+            type_name: "appl",
+            app_id: c.app_id,
+            app_args: c.app_arguments.clone().unwrap_or_default(),
+            ..TypeFields::default()
+        },
+        TransactionType::AssetConfigurationTransaction(_) => TypeFields {
+            type_name: "acfg",
+            ..TypeFields::default()
+        },
+        _ => TypeFields {
+            type_name: "other",
+            ..TypeFields::default()
+        },
+    }
+}
+
+fn opt_to_string<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        flows::lock::lock::LockSigned,
+        signer::Sign,
+        testing::fixtures::lock_to_sign,
+        verify_signed::{verify_signed, TxField},
+    };
+    use algonaut::transaction::transaction::TransactionType;
+    use anyhow::Result;
+    use network_test_util::test_data::{investor1, investor2};
+    use tokio::test;
+
+    #[test]
+    async fn test_unchanged_signed_txs_have_no_discrepancies() -> Result<()> {
+        let investor = investor1();
+        let to_sign = lock_to_sign(&investor).await?;
+
+        let signed = to_sign.clone().sign(&investor)?;
+
+        assert!(verify_signed(&to_sign, &signed)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_detects_changed_transfer() -> Result<()> {
+        let investor = investor1();
+        let attacker = investor2();
+        let to_sign = lock_to_sign(&investor).await?;
+
+        let mut tampered_xfer = to_sign.shares_xfer_tx.clone();
+        match &mut tampered_xfer.txn_type {
+            TransactionType::AssetTransferTransaction(xfer) => {
+                xfer.receiver = attacker.address();
+                xfer.amount = 20;
+            }
+            _ => panic!("Lock is expected to send shares to the app"),
+        }
+        let signed = LockSigned {
+            central_app_call_setup_tx: investor
+                .sign_transaction(to_sign.central_app_call_setup_tx.clone())?,
+            shares_xfer_tx_signed: investor.sign_transaction(tampered_xfer)?,
+        };
+
+        let discrepancies = verify_signed(&to_sign, &signed)?;
+
+        assert_eq!(2, discrepancies.len());
+        assert!(discrepancies.iter().all(|d| d.tx_index == Some(1)));
+        assert_eq!(TxField::Receiver, discrepancies[0].field);
+        assert_eq!(attacker.address().to_string(), discrepancies[0].actual);
+        assert_eq!(TxField::Amount, discrepancies[1].field);
+        assert_eq!("10", discrepancies[1].expected);
+        assert_eq!("20", discrepancies[1].actual);
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_detects_added_rekey() -> Result<()> {
+        let investor = investor1();
+        let attacker = investor2();
+        let to_sign = lock_to_sign(&investor).await?;
+
+        let mut tampered_app_call = to_sign.central_app_call_setup_tx.clone();
+        tampered_app_call.rekey_to = Some(attacker.address());
+        let signed = LockSigned {
+            central_app_call_setup_tx: investor.sign_transaction(tampered_app_call)?,
+            shares_xfer_tx_signed: investor.sign_transaction(to_sign.shares_xfer_tx.clone())?,
+        };
+
+        let discrepancies = verify_signed(&to_sign, &signed)?;
+
+        assert_eq!(1, discrepancies.len());
+        assert_eq!(Some(0), discrepancies[0].tx_index);
+        assert_eq!(TxField::RekeyTo, discrepancies[0].field);
+        assert_eq!("", discrepancies[0].expected);
+        assert_eq!(attacker.address().to_string(), discrepancies[0].actual);
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_detects_changed_note() -> Result<()> {
+        let investor = investor1();
+        let to_sign = lock_to_sign(&investor).await?;
+
+        let mut tampered_xfer = to_sign.shares_xfer_tx.clone();
+        tampered_xfer.note = Some("changed".as_bytes().to_vec());
+        let signed = LockSigned {
+            central_app_call_setup_tx: investor
+                .sign_transaction(to_sign.central_app_call_setup_tx.clone())?,
+            shares_xfer_tx_signed: investor.sign_transaction(tampered_xfer)?,
+        };
+
+        let discrepancies = verify_signed(&to_sign, &signed)?;
+
+        assert_eq!(1, discrepancies.len());
+        assert_eq!(TxField::Other, discrepancies[0].field);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use crate::signer::{group_txs, Sign, SignedTxs, TxAuthorizer, TxSigner};
+use algonaut::{
+    core::{Address, MultisigAddress},
+    transaction::{SignedTransaction, Transaction},
+};
+use anyhow::{anyhow, Result};
+use data_encoding::BASE64;
+use serde::{Deserialize, Serialize};
+
+mod tests;
+
+/// A transaction to be signed by an external wallet, as specified in ARC-0001
+/// (https://github.com/algorandfoundation/ARCs/blob/main/ARCs/arc-0001.md)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletTransaction {
+    /// Base64 encoded msgpack of the unsigned transaction
+    pub txn: String,
+    /// Addresses that have to sign the transaction. Empty if the wallet doesn't have to sign (logic sigs).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signers: Option<Vec<String>>,
+    /// The sender's auth address, if it was rekeyed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_addr: Option<String>,
+    /// Set if the transaction is authorized by a multisig
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub msig: Option<MultisigMetadata>,
+    /// Shown to the user by the wallet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultisigMetadata {
+    pub version: u8,
+    pub threshold: u8,
+    /// All the members, in the multisig's order
+    pub addrs: Vec<String>,
+}
+
+impl From<&MultisigAddress> for MultisigMetadata {
+    fn from(address: &MultisigAddress) -> Self {
+        MultisigMetadata {
+            version: address.version,
+            threshold: address.threshold,
+            addrs: address
+                .public_keys
+                .iter()
+                .map(|key| Address(key.0).to_string())
+                .collect(),
+        }
+    }
+}
+
+/// The transactions of a `*ToSign` struct, in the order they're submitted (i.e. the group's order),
+/// to be passed to the wallet's `signTxns`.
+/// `signer` is the one the wallet signs for: its authorizer tells the wallet which keys sign (see [TxSigner::authorizer]).
+pub fn to_wallet_txs<T>(
+    to_sign: &T,
+    signer: &dyn TxSigner,
+    message: Option<&str>,
+) -> Result<Vec<WalletTransaction>>
+where
+    T: Sign + Clone,
+    T::Signed: SignedTxs,
+{
+    group_txs(to_sign)?
+        .iter()
+        .map(|tx| {
+            let authorizer = signer.authorizer(tx);
+            let signers = match &authorizer {
+                TxAuthorizer::Key(address) => vec![address.to_string()],
+                TxAuthorizer::Multisig { signers, .. } => {
+                    signers.iter().map(|s| s.to_string()).collect()
+                }
+                TxAuthorizer::LogicSig(_) => vec![],
+            };
+            let msig = match &authorizer {
+                TxAuthorizer::Multisig { address, .. } => Some(address.into()),
+                _ => None,
+            };
+            let auth_address = authorizer.address();
+
+            Ok(WalletTransaction {
+                txn: BASE64.encode(&rmp_serde::to_vec_named(tx)?),
+                signers: Some(signers),
+                auth_addr: if auth_address != tx.sender() {
+                    Some(auth_address.to_string())
+                } else {
+                    None
+                },
+                msig,
+                message: message.map(|m| m.to_owned()),
+            })
+        })
+        .collect()
+}
+
+/// Rebuilds the `*Signed` struct from the result of the wallet's `signTxns`: base64 encoded msgpack signed transactions
+/// (`None` where the wallet didn't sign), in the order of [to_wallet_txs].
+/// Fails if the wallet changed the transactions (including group ids) or their order.
+pub fn from_wallet_txs<T>(to_sign: &T, signed_txs: &[Option<String>]) -> Result<T::Signed>
+where
+    T: Sign + Clone,
+    T::Signed: SignedTxs,
+{
+    let expected_txs = group_txs(to_sign)?;
+
+    if signed_txs.len() != expected_txs.len() {
+        return Err(anyhow!(
+            "Wallet returned {} txs, expected: {}",
+            signed_txs.len(),
+            expected_txs.len()
+        ));
+    }
+
+    let signed_txs = signed_txs
+        .iter()
+        .enumerate()
+        .map(|(index, signed_tx)| match signed_tx {
+            Some(signed_tx) => decode_signed_tx(signed_tx),
+            None => Err(anyhow!("Tx {index} wasn't signed by the wallet")),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let expected_ids = expected_txs
+        .iter()
+        .map(|tx| tx.id())
+        .collect::<Result<Vec<_>, _>>()?;
+    for (index, (signed_tx, expected_tx)) in signed_txs.iter().zip(expected_txs.iter()).enumerate()
+    {
+        let id = signed_tx.transaction.id()?;
+        if id == expected_ids[index] {
+            continue;
+        }
+        return Err(if expected_ids.contains(&id) {
+            anyhow!("Tx {index} was reordered by the wallet")
+        } else if signed_tx.transaction.group != expected_tx.group {
+            anyhow!(
+                "Tx {index} group id was changed by the wallet: {:?}, expected: {:?}",
+                signed_tx.transaction.group,
+                expected_tx.group
+            )
+        } else {
+            anyhow!("Tx {index} was modified by the wallet")
+        });
+    }
+
+    to_sign.clone().sign(&PresignedSigner { signed_txs })
+}
+
+fn decode_signed_tx(signed_tx: &str) -> Result<SignedTransaction> {
+    let bytes = BASE64.decode(signed_tx.as_bytes())?;
+    Ok(rmp_serde::from_slice(&bytes)?)
+}
+
+/// "Signs" with the matching transaction signed by the wallet
+struct PresignedSigner {
+    signed_txs: Vec<SignedTransaction>,
+}
+
+impl TxSigner for PresignedSigner {
+    fn sign_tx(&self, tx: Transaction) -> Result<SignedTransaction> {
+        let id = tx.id()?;
+        for signed_tx in &self.signed_txs {
+            if signed_tx.transaction.id()? == id {
+                return Ok(signed_tx.clone());
+            }
+        }
+        Err(anyhow!("No signed tx for: {id}"))
+    }
+}
@@ -0,0 +1,185 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        signer::{MultisigSigner, Sign},
+        testing::fixtures::lock_to_sign,
+        wallet::{from_wallet_txs, to_wallet_txs, MultisigMetadata, WalletTransaction},
+    };
+    use algonaut::{
+        core::MultisigAddress,
+        crypto::HashDigest,
+        transaction::{account::Account, Transaction},
+    };
+    use anyhow::{Error, Result};
+    use data_encoding::BASE64;
+    use network_test_util::test_data::{creator, investor1, investor2};
+    use tokio::test;
+
+    fn decode_tx(wallet_tx: &WalletTransaction) -> Result<Transaction> {
+        Ok(rmp_serde::from_slice(
+            &BASE64.decode(wallet_tx.txn.as_bytes())?,
+        )?)
+    }
+
+    /// What the wallet does
+    fn wallet_sign(account: &Account, tx: Transaction) -> Result<Option<String>> {
+        let signed = account.sign_transaction(tx)?;
+        Ok(Some(BASE64.encode(&rmp_serde::to_vec_named(&signed)?)))
+    }
+
+    #[test]
+    async fn test_export_import_roundtrip() -> Result<()> {
+        let investor = investor1();
+        let to_sign = lock_to_sign(&investor).await?;
+
+        let wallet_txs = to_wallet_txs(&to_sign, &investor, Some("Lock shares"))?;
+
+        // group order
+        assert_eq!(2, wallet_txs.len());
+        assert_eq!(
+            to_sign.central_app_call_setup_tx,
+            decode_tx(&wallet_txs[0])?
+        );
+        assert_eq!(to_sign.shares_xfer_tx, decode_tx(&wallet_txs[1])?);
+        for wallet_tx in &wallet_txs {
+            assert_eq!(
+                Some(vec![investor.address().to_string()]),
+                wallet_tx.signers
+            );
+            assert_eq!(Some("Lock shares".to_owned()), wallet_tx.message);
+            assert_eq!(None, wallet_tx.auth_addr);
+            assert_eq!(None, wallet_tx.msig);
+        }
+
+        // the JSON expected by wallets
+        let json = serde_json::to_value(&wallet_txs[0])?;
+        assert!(json.get("txn").is_some());
+        assert!(json.get("signers").is_some());
+
+        let wallet_signed = wallet_txs
+            .iter()
+            .map(|wallet_tx| wallet_sign(&investor, decode_tx(wallet_tx)?))
+            .collect::<Result<Vec<_>>>()?;
+
+        let signed = from_wallet_txs(&to_sign, &wallet_signed)?;
+
+        assert_eq!(to_sign.sign(&investor)?, signed);
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_export_for_rekeyed_account() -> Result<()> {
+        let investor = investor1();
+        let auth = creator();
+        let to_sign = lock_to_sign(&investor).await?;
+
+        let wallet_txs = to_wallet_txs(&to_sign, &auth, None)?;
+
+        for wallet_tx in &wallet_txs {
+            assert_eq!(Some(vec![auth.address().to_string()]), wallet_tx.signers);
+            assert_eq!(Some(auth.address().to_string()), wallet_tx.auth_addr);
+        }
+        let json = serde_json::to_value(&wallet_txs[0])?;
+        assert_eq!(
+            Some(auth.address().to_string()),
+            json["authAddr"].as_str().map(|a| a.to_owned())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_export_for_multisig() -> Result<()> {
+        let investor = investor1();
+        let members = [
+            creator().address(),
+            investor2().address(),
+            investor.address(),
+        ];
+        let msig_address = MultisigAddress::new(1, 2, &members).map_err(Error::msg)?;
+        // the investor's account was rekeyed to the multisig
+        let to_sign = lock_to_sign(&investor).await?;
+        let signer = MultisigSigner {
+            address: msig_address.clone(),
+            signers: vec![creator(), investor2()],
+        };
+
+        let wallet_txs = to_wallet_txs(&to_sign, &signer, None)?;
+
+        for wallet_tx in &wallet_txs {
+            assert_eq!(
+                Some(vec![members[0].to_string(), members[1].to_string()]),
+                wallet_tx.signers
+            );
+            assert_eq!(
+                Some(MultisigMetadata {
+                    version: 1,
+                    threshold: 2,
+                    addrs: members.iter().map(|m| m.to_string()).collect(),
+                }),
+                wallet_tx.msig
+            );
+            assert_eq!(
+                Some(msig_address.address().to_string()),
+                wallet_tx.auth_addr
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_import_fails_if_order_changed() -> Result<()> {
+        let investor = investor1();
+        let to_sign = lock_to_sign(&investor).await?;
+
+        let mut wallet_signed = to_wallet_txs(&to_sign, &investor, None)?
+            .iter()
+            .map(|wallet_tx| wallet_sign(&investor, decode_tx(wallet_tx)?))
+            .collect::<Result<Vec<_>>>()?;
+        wallet_signed.swap(0, 1);
+
+        let res = from_wallet_txs(&to_sign, &wallet_signed);
+
+        assert!(res.is_err());
+        assert!(format!("{:?}", res.unwrap_err()).contains("reordered"));
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_import_fails_if_group_changed() -> Result<()> {
+        let investor = investor1();
+        let to_sign = lock_to_sign(&investor).await?;
+
+        let wallet_signed = to_wallet_txs(&to_sign, &investor, None)?
+            .iter()
+            .map(|wallet_tx| {
+                let mut tx = decode_tx(wallet_tx)?;
+                tx.group = Some(HashDigest([1; 32]));
+                wallet_sign(&investor, tx)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let res = from_wallet_txs(&to_sign, &wallet_signed);
+
+        assert!(res.is_err());
+        assert!(format!("{:?}", res.unwrap_err()).contains("group id"));
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_import_fails_if_tx_not_signed() -> Result<()> {
+        let investor = investor1();
+        let to_sign = lock_to_sign(&investor).await?;
+
+        let wallet_txs = to_wallet_txs(&to_sign, &investor, None)?;
+        let wallet_signed = vec![wallet_sign(&investor, decode_tx(&wallet_txs[0])?)?, None];
+
+        assert!(from_wallet_txs(&to_sign, &wallet_signed).is_err());
+
+        Ok(())
+    }
+}